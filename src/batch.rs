@@ -0,0 +1,192 @@
+use clap::error::ErrorKind;
+use console::Style;
+use prettytable::{row, Table};
+use std::error::Error;
+use crate::cli::{run_command, Command, ProfileCommand, ReplLine};
+use crate::db::BudgetManager;
+use crate::i18n::tr;
+use crate::output::OutputFormat;
+use crate::repl::parse_line;
+
+// Fichier de commandes : une commande par ligne, avec la même syntaxe que le
+// mode commande (`budget add --name Loyer --amount 900`, `add Loyer "Mars" 900`).
+// Les lignes vides et les commentaires (#) sont ignorés.
+
+// Ligne validée : numéro dans le fichier, texte et commande analysée
+struct BatchLine {
+    number: usize,
+    text: String,
+    parsed: ReplLine,
+}
+
+// Résultat d'une ligne, affiché dans le rapport final
+#[derive(Debug, Clone)]
+enum Outcome {
+    Done,
+    Failed(String),
+    // Exécutée, puis annulée par l'échec d'une ligne suivante
+    Undone,
+    NotRun,
+}
+
+//==================================Function to run a command file====================================
+// Toutes les lignes sont validées avant d'exécuter quoi que ce soit, puis exécutées
+// dans une seule transaction SQLite : au premier échec, tout est annulé.
+// Retourne false si le fichier est invalide ou si une commande a échoué.
+pub fn run_batch(budget_manager: &BudgetManager, file_path: &str, format: OutputFormat, config_path: &str) -> Result<bool, Box<dyn Error>> {
+    let error_style = Style::new().red();
+    let success_style = Style::new().green();
+
+    let lines = match parse_batch(&std::fs::read_to_string(file_path)?) {
+        Ok(lines) => lines,
+        Err(errors) => {
+            for (number, message) in &errors {
+                eprintln!("{}", error_style.apply_to(tr!(BatchLineInvalid, number, message)));
+            }
+            eprintln!("{}", error_style.apply_to(tr!(BatchInvalid, errors.len())));
+            return Ok(false);
+        }
+    };
+
+    let mut outcomes = vec![Outcome::NotRun; lines.len()];
+    let texts: Vec<(usize, String)> = lines.iter().map(|line| (line.number, line.text.clone())).collect();
+    let result = budget_manager.atomically(|| {
+        for (index, BatchLine { number, parsed, .. }) in lines.into_iter().enumerate() {
+            match run_command(budget_manager, parsed.command, parsed.format.unwrap_or(format), config_path) {
+                Ok(true) => outcomes[index] = Outcome::Done,
+                Ok(false) => {
+                    outcomes[index] = Outcome::Failed(tr!(BatchCommandFailed).to_string());
+                    return Err(BatchError::Line(number));
+                }
+                Err(err) => {
+                    outcomes[index] = Outcome::Failed(err.to_string());
+                    return Err(BatchError::Line(number));
+                }
+            }
+        }
+        Ok(())
+    });
+
+    // Une erreur de la base pendant l'annulation remonte telle quelle
+    let failed_line = match result {
+        Ok(()) => None,
+        Err(BatchError::Line(number)) => Some(number),
+        Err(BatchError::Database(err)) => return Err(err.into()),
+    };
+    if failed_line.is_some() {
+        for outcome in outcomes.iter_mut().filter(|outcome| matches!(outcome, Outcome::Done)) {
+            *outcome = Outcome::Undone;
+        }
+    }
+
+    let mut table = Table::new();
+    table.add_row(row![tr!(ColLine), tr!(ColCommand), tr!(ColResult)]);
+    for ((number, text), outcome) in texts.iter().zip(&outcomes) {
+        let result = match outcome {
+            Outcome::Done => tr!(BatchDone).to_string(),
+            Outcome::Failed(message) => tr!(BatchFailed, message),
+            Outcome::Undone => tr!(BatchUndone).to_string(),
+            Outcome::NotRun => tr!(BatchNotRun).to_string(),
+        };
+        table.add_row(row![number, text, result]);
+    }
+    table.printstd();
+
+    match failed_line {
+        None => {
+            println!("{}", success_style.apply_to(tr!(BatchCommitted, texts.len())));
+            Ok(true)
+        }
+        Some(number) => {
+            eprintln!("{}", error_style.apply_to(tr!(BatchRolledBack, number)));
+            Ok(false)
+        }
+    }
+}
+
+// Échec d'une ligne (numéro) ou de la base lors de l'annulation
+enum BatchError {
+    Line(usize),
+    Database(rusqlite::Error),
+}
+
+impl From<rusqlite::Error> for BatchError {
+    fn from(err: rusqlite::Error) -> Self {
+        BatchError::Database(err)
+    }
+}
+
+// Valide toutes les lignes ; en cas d'erreur, liste des (numéro de ligne, message)
+fn parse_batch(text: &str) -> Result<Vec<BatchLine>, Vec<(usize, String)>> {
+    let mut lines = Vec::new();
+    let mut errors = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match parse_line(line) {
+            Ok(parsed)
+                if matches!(
+                    parsed.command,
+                    Command::Repl | Command::Dashboard | Command::Batch { .. } | Command::Config(_)
+                        | Command::Profile(ProfileCommand::Create { .. })
+                ) =>
+            {
+                let name = line.split_whitespace().next().unwrap_or(line);
+                errors.push((index + 1, tr!(BatchNotAllowed, name)));
+            }
+            // Aucune question ne peut être posée pendant l'exécution du fichier
+            Ok(ReplLine { command: Command::Import { yes: false, .. } | Command::Quick { yes: false, .. }, .. }) => {
+                let name = line.split_whitespace().next().unwrap_or(line);
+                errors.push((index + 1, tr!(BatchNeedsYes, name)));
+            }
+            // L'annulation du fichier n'effacerait pas le fichier écrit
+            Ok(ReplLine { command: Command::Export { output: Some(_), .. }, .. }) => {
+                errors.push((index + 1, tr!(BatchExportOutput).to_string()));
+            }
+            Ok(parsed) => lines.push(BatchLine { number: index + 1, text: line.to_string(), parsed }),
+            Err(err) if err.kind() == ErrorKind::DisplayHelp => errors.push((index + 1, tr!(BatchNotAllowed, "help"))),
+            Err(err) => {
+                // Message de clap sans le préfixe "error: " ; à défaut (sous-commande
+                // manquante), la ligne d'usage
+                let rendered = err.render().to_string();
+                let message = rendered
+                    .lines()
+                    .find_map(|line| line.strip_prefix("error: "))
+                    .or_else(|| rendered.lines().find(|line| line.starts_with("Usage:")))
+                    .unwrap_or_default();
+                errors.push((index + 1, message.to_string()));
+            }
+        }
+    }
+    if errors.is_empty() {
+        Ok(lines)
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn errors(text: &str) -> Vec<usize> {
+        match parse_batch(text) {
+            Ok(_) => Vec::new(),
+            Err(errors) => errors.into_iter().map(|(number, _)| number).collect(),
+        }
+    }
+
+    #[test]
+    fn accepts_commands_that_can_be_undone() {
+        let text = "# commentaire\nbudget add --name Loyer --amount 900\n\nquick café 3,50 --yes\nimport --file releve.csv --yes\nexport";
+        assert_eq!(parse_batch(text).map(|lines| lines.len()).ok(), Some(4));
+    }
+
+    #[test]
+    fn rejects_prompts_and_written_files() {
+        let text = "quick café 3,50\nimport --file releve.csv\nexport --output sortie.csv\nrepl";
+        assert_eq!(errors(text), vec![1, 2, 3, 4]);
+    }
+}
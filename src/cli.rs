@@ -0,0 +1,699 @@
+use chrono::NaiveDate;
+use clap::{Parser, Subcommand};
+use console::Style;
+use std::error::Error;
+use std::fs::File;
+use crate::db::{AlertRuleKind, AssignmentRule, BudgetManager, NewTransaction, OverspendPolicy};
+use crate::config::{self, Config, UnknownBudgetPolicy};
+use crate::i18n::{tr, Lang};
+use crate::menu::{create_import_profile, print_rule_test, run_import, run_query, run_quick_add, ImportOptions};
+use crate::output::{self, print_transactions, OutputFormat};
+use crate::query::{Filter, Query};
+use crate::{batch, dashboard, export, parse, repl};
+
+// Interface en ligne de commande : chaque option du menu interactif a sa
+// sous-commande. Sans sous-commande, le menu interactif est lancé. Les textes
+// d'aide viennent de i18n, pour suivre --lang et BUDGET_LANG.
+#[derive(Debug, Parser)]
+#[command(name = "projet_gestion_budgets", version, about = tr!(HelpApp))]
+pub struct Cli {
+    #[arg(long, global = true, help = tr!(HelpDb))]
+    pub db: Option<String>,
+
+    #[arg(long, global = true, default_value = config::DEFAULT_PATH, help = tr!(HelpConfigFile))]
+    pub config: String,
+
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Table, help = tr!(HelpFormat))]
+    pub format: OutputFormat,
+
+    #[arg(long, global = true, value_parser = parse_lang, help = tr!(HelpLang))]
+    pub lang: Option<Lang>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    #[command(about = tr!(HelpAdd))]
+    Add {
+        budget: String,
+        name: String,
+        #[arg(value_parser = parse::amount, help = tr!(HelpAmount))]
+        amount: f64,
+        #[arg(long, value_parser = parse::date, help = tr!(HelpDate))]
+        date: Option<NaiveDate>,
+        #[arg(long, default_value = "")]
+        payee: String,
+        #[arg(long, default_value = "")]
+        notes: String,
+        #[arg(long = "tag", help = tr!(HelpTag))]
+        tags: Vec<String>,
+        #[arg(long, help = tr!(HelpConfirmOverspend))]
+        yes: bool,
+    },
+    #[command(about = tr!(HelpQuick))]
+    Quick {
+        #[arg(required = true)]
+        text: Vec<String>,
+        #[arg(long, help = tr!(HelpQuickYes))]
+        yes: bool,
+    },
+    #[command(subcommand, about = tr!(HelpBudget))]
+    Budget(BudgetCommand),
+    #[command(subcommand, about = tr!(HelpTx))]
+    Tx(TxCommand),
+    #[command(subcommand, about = tr!(HelpAlert))]
+    Alert(AlertCommand),
+    #[command(about = tr!(HelpBalance))]
+    Balance {
+        #[arg(long)]
+        budget: String,
+    },
+    #[command(about = tr!(HelpSearch))]
+    Search {
+        #[arg(required = true)]
+        terms: Vec<String>,
+    },
+    #[command(about = tr!(HelpQuery))]
+    Query {
+        #[arg(required = true)]
+        terms: Vec<String>,
+    },
+    #[command(about = tr!(HelpImport))]
+    Import {
+        #[arg(long)]
+        file: String,
+        #[arg(long, help = tr!(HelpImportProfile))]
+        profile: Option<String>,
+        #[arg(long, help = tr!(HelpImportAccount))]
+        account: Option<String>,
+        #[arg(long, help = tr!(HelpImportDryRun))]
+        dry_run: bool,
+        #[arg(long, help = tr!(HelpImportForce))]
+        force: bool,
+        #[arg(long, help = tr!(HelpImportYes))]
+        yes: bool,
+        #[arg(long, value_parser = parse_unknown_budget, help = tr!(HelpImportUnknownBudget))]
+        unknown_budget: Option<UnknownBudgetPolicy>,
+    },
+    #[command(subcommand, about = tr!(HelpProfile))]
+    Profile(ProfileCommand),
+    #[command(subcommand, about = tr!(HelpAccount))]
+    Account(AccountCommand),
+    #[command(subcommand, about = tr!(HelpCategory))]
+    Category(CategoryCommand),
+    #[command(subcommand, about = tr!(HelpRule))]
+    Rule(RuleCommand),
+    #[command(subcommand, about = tr!(HelpAlias))]
+    Alias(AliasCommand),
+    #[command(subcommand, about = tr!(HelpInbox))]
+    Inbox(InboxCommand),
+    #[command(about = tr!(HelpExport))]
+    Export {
+        #[arg(long, help = tr!(HelpExportOutput))]
+        output: Option<String>,
+        #[arg(long, help = tr!(HelpExportQuery))]
+        query: Option<String>,
+    },
+    #[command(about = tr!(HelpDashboard))]
+    Dashboard,
+    #[command(subcommand, about = tr!(HelpConfig))]
+    Config(ConfigCommand),
+    #[command(about = tr!(HelpRepl))]
+    Repl,
+    #[command(about = tr!(HelpBatch))]
+    Batch {
+        #[arg(long)]
+        file: String,
+    },
+}
+
+// Une ligne saisie en mode commande : mêmes sous-commandes que la ligne de commande
+#[derive(Debug, Parser)]
+#[command(name = "", no_binary_name = true, disable_version_flag = true)]
+pub struct ReplLine {
+    #[arg(long, global = true, value_enum, help = tr!(HelpReplFormat))]
+    pub format: Option<OutputFormat>,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigCommand {
+    #[command(about = tr!(HelpConfigShow))]
+    Show,
+    #[command(about = tr!(HelpConfigSet))]
+    Set {
+        key: String,
+        value: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum BudgetCommand {
+    #[command(about = tr!(HelpBudgetAdd))]
+    Add {
+        #[arg(long)]
+        name: String,
+        #[arg(long, value_parser = parse::amount)]
+        amount: f64,
+    },
+    #[command(about = tr!(HelpBudgetList))]
+    List,
+    #[command(about = tr!(HelpBudgetEdit))]
+    Edit {
+        #[arg(long)]
+        name: String,
+        #[arg(long, help = tr!(HelpNewName))]
+        new_name: Option<String>,
+        #[arg(long, value_parser = parse::amount, help = tr!(HelpNewTotal))]
+        amount: Option<f64>,
+    },
+    #[command(about = tr!(HelpBudgetRm))]
+    Rm {
+        #[arg(long)]
+        name: String,
+    },
+    #[command(about = tr!(HelpBudgetPolicy))]
+    Policy {
+        #[arg(long)]
+        name: String,
+        #[arg(long, value_parser = parse_policy)]
+        policy: OverspendPolicy,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum TxCommand {
+    #[command(about = tr!(HelpTxAdd))]
+    Add {
+        #[arg(long)]
+        budget: String,
+        #[arg(long)]
+        name: String,
+        #[arg(long, value_parser = parse::amount)]
+        amount: f64,
+        #[arg(long, value_parser = parse::date, help = tr!(HelpDate))]
+        date: Option<NaiveDate>,
+        #[arg(long, default_value = "")]
+        payee: String,
+        #[arg(long, default_value = "")]
+        notes: String,
+        #[arg(long = "tag", help = tr!(HelpTag))]
+        tags: Vec<String>,
+        #[arg(long, help = tr!(HelpConfirmOverspend))]
+        yes: bool,
+    },
+    #[command(about = tr!(HelpTxList))]
+    List {
+        #[arg(long, help = tr!(HelpTxListBudget))]
+        budget: Option<String>,
+    },
+    #[command(about = tr!(HelpTxEdit))]
+    Edit {
+        #[arg(long)]
+        budget: String,
+        #[arg(long)]
+        name: String,
+        #[arg(long, help = tr!(HelpNewName))]
+        new_name: Option<String>,
+        #[arg(long, value_parser = parse::amount, help = tr!(HelpNewAmount))]
+        amount: Option<f64>,
+        #[arg(long, help = tr!(HelpConfirmOverspend))]
+        yes: bool,
+    },
+    #[command(about = tr!(HelpTxRm))]
+    Rm {
+        #[arg(long)]
+        budget: String,
+        #[arg(long)]
+        name: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum AlertCommand {
+    #[command(about = tr!(HelpAlertList))]
+    List {
+        #[arg(long)]
+        budget: String,
+    },
+    #[command(about = tr!(HelpAlertAdd))]
+    Add {
+        #[arg(long)]
+        budget: String,
+        #[arg(long, value_parser = parse_alert_kind)]
+        kind: AlertRuleKind,
+        #[arg(long, value_parser = parse::amount)]
+        threshold: f64,
+    },
+    #[command(about = tr!(HelpRuleEdit))]
+    Edit {
+        #[arg(long)]
+        id: i32,
+        #[arg(long, value_parser = parse_alert_kind)]
+        kind: AlertRuleKind,
+        #[arg(long, value_parser = parse::amount)]
+        threshold: f64,
+    },
+    #[command(about = tr!(HelpRuleRm))]
+    Rm {
+        #[arg(long)]
+        id: i32,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ProfileCommand {
+    #[command(about = tr!(HelpProfileList))]
+    List,
+    #[command(about = tr!(HelpProfileCreate))]
+    Create {
+        #[arg(long)]
+        sample: String,
+        #[arg(long)]
+        name: Option<String>,
+    },
+    #[command(about = tr!(HelpProfileRm))]
+    Rm {
+        #[arg(long)]
+        name: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum AccountCommand {
+    #[command(about = tr!(HelpAccountList))]
+    List,
+    #[command(about = tr!(HelpAccountMap))]
+    Map {
+        #[arg(long)]
+        account: String,
+        #[arg(long)]
+        budget: String,
+    },
+    #[command(about = tr!(HelpAccountUnmap))]
+    Unmap {
+        #[arg(long)]
+        account: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum CategoryCommand {
+    #[command(about = tr!(HelpCategoryList))]
+    List,
+    #[command(about = tr!(HelpCategoryMap))]
+    Map {
+        #[arg(long)]
+        category: String,
+        #[arg(long)]
+        budget: String,
+    },
+    #[command(about = tr!(HelpCategoryUnmap))]
+    Unmap {
+        #[arg(long)]
+        category: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum AliasCommand {
+    #[command(about = tr!(HelpAliasList))]
+    List,
+    #[command(about = tr!(HelpAliasMap))]
+    Map {
+        #[arg(long)]
+        name: String,
+        #[arg(long)]
+        budget: String,
+    },
+    #[command(about = tr!(HelpAliasUnmap))]
+    Unmap {
+        #[arg(long)]
+        name: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum InboxCommand {
+    #[command(about = tr!(HelpInboxList))]
+    List,
+    #[command(about = tr!(HelpInboxAssign))]
+    Assign {
+        #[arg(long)]
+        id: i64,
+        #[arg(long)]
+        budget: String,
+        #[arg(long, help = tr!(HelpConfirmOverspend))]
+        yes: bool,
+    },
+    #[command(about = tr!(HelpInboxRm))]
+    Rm {
+        #[arg(long)]
+        id: i64,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum RuleCommand {
+    #[command(about = tr!(HelpRuleList))]
+    List,
+    #[command(about = tr!(HelpRuleAdd))]
+    Add {
+        #[arg(long, default_value = "", help = tr!(HelpRuleContains))]
+        contains: String,
+        #[arg(long, default_value = "", help = tr!(HelpRuleRegex))]
+        regex: String,
+        #[arg(long, default_value = "", help = tr!(HelpRulePayee))]
+        payee: String,
+        #[arg(long, value_parser = parse::amount, help = tr!(HelpRuleMin))]
+        min: Option<f64>,
+        #[arg(long, value_parser = parse::amount, help = tr!(HelpRuleMax))]
+        max: Option<f64>,
+        #[arg(long, default_value = "")]
+        budget: String,
+        #[arg(long, default_value = "")]
+        category: String,
+        #[arg(long = "tag", help = tr!(HelpTag))]
+        tags: Vec<String>,
+        #[arg(long, default_value_t = 0, allow_negative_numbers = true, help = tr!(HelpRulePriority))]
+        priority: i32,
+    },
+    #[command(about = tr!(HelpRuleRm))]
+    Rm {
+        #[arg(long)]
+        id: i32,
+    },
+    #[command(about = tr!(HelpRuleTest))]
+    Test {
+        #[arg(long)]
+        name: String,
+        #[arg(long, value_parser = parse::amount)]
+        amount: f64,
+        #[arg(long, default_value = "")]
+        payee: String,
+        #[arg(long, default_value = "")]
+        notes: String,
+    },
+    #[command(about = tr!(HelpRuleSuggest))]
+    Suggest {
+        #[arg(long, default_value_t = 3, help = tr!(HelpRuleMinCount))]
+        min_count: usize,
+        #[arg(long, help = tr!(HelpRuleApply))]
+        apply: bool,
+    },
+}
+
+fn parse_policy(policy: &str) -> Result<OverspendPolicy, String> {
+    OverspendPolicy::parse(policy).ok_or_else(|| tr!(PossibleValues, "allow, warn, block"))
+}
+
+fn parse_unknown_budget(policy: &str) -> Result<UnknownBudgetPolicy, String> {
+    UnknownBudgetPolicy::parse(policy).ok_or_else(|| tr!(PossibleValues, "skip, create, map, inbox"))
+}
+
+fn parse_lang(lang: &str) -> Result<Lang, String> {
+    Lang::parse(lang).ok_or_else(|| tr!(PossibleValues, "fr, en"))
+}
+
+fn parse_alert_kind(kind: &str) -> Result<AlertRuleKind, String> {
+    AlertRuleKind::parse(kind).ok_or_else(|| {
+        let kinds: Vec<&str> = AlertRuleKind::ALL.iter().map(|kind| kind.as_str()).collect();
+        tr!(PossibleValues, kinds.join(", "))
+    })
+}
+
+//==================================Run a config sub-command====================================
+// Ne nécessite pas la base de données. Un fichier invalide est signalé : `show`
+// affiche alors les valeurs par défaut (et échoue), `set` réécrit le fichier
+// avec les valeurs par défaut et la clé modifiée.
+pub fn run_config_command(config_path: &str, command: ConfigCommand) -> Result<bool, Box<dyn Error>> {
+    let warning_style = Style::new().yellow();
+    let (mut config, valid) = match Config::load(config_path) {
+        Ok(config) => (config, true),
+        Err(err) => {
+            eprintln!("{}", warning_style.apply_to(tr!(Error, err)));
+            (Config::default(), false)
+        }
+    };
+    match command {
+        ConfigCommand::Show => {
+            println!("# {}", tr!(ConfigFile, config_path));
+            print!("{}", config.to_documented_toml());
+            return Ok(valid);
+        }
+        ConfigCommand::Set { key, value } => {
+            config.set_value(&key, &value)?;
+            if !valid {
+                println!("{}", warning_style.apply_to(tr!(ConfigRewritten)));
+            }
+            config.save(config_path)?;
+            println!("{}", tr!(ConfigSaved));
+        }
+    }
+    Ok(true)
+}
+
+// add_budget, edit_budget et add_transaction affichent déjà leurs erreurs de
+// saisie : seul le code de sortie reste à donner
+fn already_reported(result: rusqlite::Result<bool>) -> Result<bool, Box<dyn Error>> {
+    match result {
+        Err(rusqlite::Error::InvalidParameterName(_)) => Ok(false),
+        result => Ok(result?),
+    }
+}
+
+//==================================Run a sub-command====================================
+// Retourne false si l'opération a échoué sans erreur de base (budget introuvable...)
+pub fn run_command(budget_manager: &BudgetManager, command: Command, format: OutputFormat, config_path: &str) -> Result<bool, Box<dyn Error>> {
+    match command {
+        Command::Budget(BudgetCommand::Add { name, amount }) => {
+            return already_reported(budget_manager.add_budget(&name, amount).map(|_| true));
+        }
+        Command::Budget(BudgetCommand::List) => budget_manager.display_budgets(format)?,
+        Command::Budget(BudgetCommand::Edit { name, new_name, amount }) => {
+            let new_name = new_name.unwrap_or_else(|| name.clone());
+            return already_reported(budget_manager.edit_budget(new_name, name, amount));
+        }
+        Command::Budget(BudgetCommand::Rm { name }) => return Ok(budget_manager.remove_budget(&name)?),
+        Command::Budget(BudgetCommand::Policy { name, policy }) => budget_manager.set_overspend_policy(&name, policy)?,
+
+        Command::Add { budget, name, amount, date, payee, notes, tags, yes }
+        | Command::Tx(TxCommand::Add { budget, name, amount, date, payee, notes, tags, yes }) => {
+            let transaction_date = date.map(|date| date.to_string());
+            let mut transaction = NewTransaction { payee, notes, tags, transaction_date, ..NewTransaction::new(name, amount) };
+            // Le budget est donné : les règles n'ajoutent que catégorie et tags
+            budget_manager.apply_assignment_rules(&mut transaction)?;
+            return already_reported(budget_manager.add_transaction(&budget, transaction, yes).map(|_| true));
+        }
+        Command::Quick { text, yes } => return run_quick_add(budget_manager, &text.join(" "), yes),
+        Command::Tx(TxCommand::List { budget }) => {
+            let query = Query { filters: budget.into_iter().map(Filter::Budget).collect() };
+            print_transactions(&budget_manager.query_transactions(&query)?, format)?;
+        }
+        Command::Tx(TxCommand::Edit { budget, name, new_name, amount, yes }) => {
+            let new_name = new_name.unwrap_or_else(|| name.clone());
+            return Ok(budget_manager.edit_transaction(&budget, name, new_name, amount, yes)?);
+        }
+        Command::Tx(TxCommand::Rm { budget, name }) => return Ok(budget_manager.remove_transaction(&budget, &name)?),
+
+        Command::Alert(AlertCommand::List { budget }) => {
+            output::render(&budget_manager.list_alert_rules(&budget)?, format, std::io::stdout())?;
+        }
+        Command::Alert(AlertCommand::Add { budget, kind, threshold }) => budget_manager.add_alert_rule(&budget, kind, threshold)?,
+        Command::Alert(AlertCommand::Edit { id, kind, threshold }) => return Ok(budget_manager.edit_alert_rule(id, kind, threshold)?),
+        Command::Alert(AlertCommand::Rm { id }) => return Ok(budget_manager.remove_alert_rule(id)?),
+
+        Command::Balance { budget } if format == OutputFormat::Table => return Ok(budget_manager.show_remaining_amount(&budget)?),
+        Command::Balance { budget } => match budget_manager.get_budget(&budget)? {
+            Some(budget) => output::render(&[budget], format, std::io::stdout())?,
+            None => {
+                eprintln!("{}", tr!(Error, tr!(BudgetNotFound, budget)));
+                return Ok(false);
+            }
+        },
+        Command::Search { terms } => print_transactions(&budget_manager.search_transactions(&terms.join(" "))?, format)?,
+        Command::Query { terms } => run_query(budget_manager, &terms.join(" "), format)?,
+        Command::Import { file, profile, account, dry_run, force, yes, unknown_budget } => {
+            let profile = match profile {
+                Some(name) => match budget_manager.get_import_profile(&name)? {
+                    Some(profile) => Some(profile),
+                    None => {
+                        eprintln!("{}", tr!(Error, tr!(ProfileNotFound, name)));
+                        return Ok(false);
+                    }
+                },
+                None => None,
+            };
+            let options = ImportOptions { account, dry_run, force, assume_yes: yes, unknown_budget };
+            return run_import(budget_manager, &file, profile.as_ref(), options);
+        }
+        Command::Profile(ProfileCommand::List) => {
+            let profiles = budget_manager.list_import_profiles()?;
+            if format == OutputFormat::Table && profiles.is_empty() {
+                println!("{}", tr!(NoProfiles));
+            } else {
+                output::render(&profiles, format, std::io::stdout())?;
+            }
+        }
+        Command::Profile(ProfileCommand::Create { sample, name }) => {
+            return Ok(create_import_profile(budget_manager, &sample, name.as_deref())?.is_some());
+        }
+        Command::Profile(ProfileCommand::Rm { name }) => {
+            if !budget_manager.remove_import_profile(&name)? {
+                eprintln!("{}", tr!(Error, tr!(ProfileNotFound, name)));
+                return Ok(false);
+            }
+            println!("{}", tr!(ProfileRemoved, name));
+        }
+        Command::Account(AccountCommand::List) => {
+            let mappings = budget_manager.list_account_budgets()?;
+            if format == OutputFormat::Table && mappings.is_empty() {
+                println!("{}", tr!(NoAccounts));
+            } else {
+                output::render(&mappings, format, std::io::stdout())?;
+            }
+        }
+        Command::Account(AccountCommand::Map { account, budget }) => {
+            budget_manager.set_account_budget(&account, &budget)?;
+            println!("{}", tr!(AccountMapped, account, budget));
+        }
+        Command::Account(AccountCommand::Unmap { account }) => {
+            if !budget_manager.remove_account_budget(&account)? {
+                eprintln!("{}", tr!(Error, tr!(AccountNotMapped, account)));
+                return Ok(false);
+            }
+            println!("{}", tr!(AccountUnmapped, account));
+        }
+        Command::Category(CategoryCommand::List) => {
+            let mappings = budget_manager.list_category_budgets()?;
+            if format == OutputFormat::Table && mappings.is_empty() {
+                println!("{}", tr!(NoCategories));
+            } else {
+                output::render(&mappings, format, std::io::stdout())?;
+            }
+        }
+        Command::Category(CategoryCommand::Map { category, budget }) => {
+            budget_manager.set_category_budget(&category, &budget)?;
+            println!("{}", tr!(CategoryMapped, category, budget));
+        }
+        Command::Category(CategoryCommand::Unmap { category }) => {
+            if !budget_manager.remove_category_budget(&category)? {
+                eprintln!("{}", tr!(Error, tr!(CategoryNotMapped, category)));
+                return Ok(false);
+            }
+            println!("{}", tr!(CategoryUnmapped, category));
+        }
+        Command::Alias(AliasCommand::List) => {
+            let aliases = budget_manager.list_budget_aliases()?;
+            if format == OutputFormat::Table && aliases.is_empty() {
+                println!("{}", tr!(NoAliases));
+            } else {
+                output::render(&aliases, format, std::io::stdout())?;
+            }
+        }
+        Command::Alias(AliasCommand::Map { name, budget }) => {
+            budget_manager.set_budget_alias(&name, &budget)?;
+            println!("{}", tr!(AliasMapped, name, budget));
+        }
+        Command::Alias(AliasCommand::Unmap { name }) => {
+            if !budget_manager.remove_budget_alias(&name)? {
+                eprintln!("{}", tr!(Error, tr!(AliasNotMapped, name)));
+                return Ok(false);
+            }
+            println!("{}", tr!(AliasUnmapped, name));
+        }
+        Command::Inbox(InboxCommand::List) => {
+            let transactions = budget_manager.list_unassigned()?;
+            if format == OutputFormat::Table && transactions.is_empty() {
+                println!("{}", tr!(NoUnassigned));
+            } else {
+                output::render(&transactions, format, std::io::stdout())?;
+            }
+        }
+        Command::Inbox(InboxCommand::Assign { id, budget, yes }) => {
+            if !budget_manager.assign_unassigned(id, &budget, yes)? {
+                eprintln!("{}", tr!(Error, tr!(UnassignedNotFound, id)));
+                return Ok(false);
+            }
+            println!("{}", tr!(UnassignedAssigned, id, budget));
+        }
+        Command::Inbox(InboxCommand::Rm { id }) => {
+            if !budget_manager.remove_unassigned(id)? {
+                eprintln!("{}", tr!(Error, tr!(UnassignedNotFound, id)));
+                return Ok(false);
+            }
+            println!("{}", tr!(UnassignedRemoved, id));
+        }
+        Command::Rule(RuleCommand::List) => {
+            let rules = budget_manager.list_assignment_rules()?;
+            if format == OutputFormat::Table && rules.is_empty() {
+                println!("{}", tr!(NoAssignRules));
+            } else {
+                output::render(&rules, format, std::io::stdout())?;
+            }
+        }
+        Command::Rule(RuleCommand::Add { contains, regex, payee, min, max, budget, category, tags, priority }) => {
+            let rule = AssignmentRule {
+                priority,
+                contains,
+                regex,
+                payee,
+                min_amount: min,
+                max_amount: max,
+                budget_name: budget,
+                category,
+                tags,
+                ..Default::default()
+            };
+            let id = budget_manager.add_assignment_rule(&rule)?;
+            println!("{}", tr!(AssignRuleAdded, id));
+        }
+        Command::Rule(RuleCommand::Rm { id }) => {
+            if !budget_manager.remove_assignment_rule(id)? {
+                eprintln!("{}", tr!(Error, tr!(AssignRuleNotFound, id)));
+                return Ok(false);
+            }
+            println!("{}", tr!(AssignRuleRemoved, id));
+        }
+        Command::Rule(RuleCommand::Test { name, amount, payee, notes }) => {
+            let transaction = NewTransaction { payee, notes, ..NewTransaction::new(name, amount) };
+            if format == OutputFormat::Table {
+                print_rule_test(budget_manager, &transaction)?;
+            } else {
+                let matching: Vec<AssignmentRule> = budget_manager.assignment_rules()?.matching(&transaction).into_iter().cloned().collect();
+                output::render(&matching, format, std::io::stdout())?;
+            }
+        }
+        Command::Rule(RuleCommand::Suggest { min_count, apply }) => {
+            let suggestions = budget_manager.suggest_assignment_rules(min_count)?;
+            if format == OutputFormat::Table && suggestions.is_empty() {
+                println!("{}", tr!(NoRuleSuggestions));
+            } else {
+                output::render(&suggestions, format, std::io::stdout())?;
+            }
+            if apply {
+                budget_manager.atomically(|| {
+                    suggestions.iter().try_for_each(|suggestion| budget_manager.add_assignment_rule(&suggestion.rule()).map(|_| ()))
+                })?;
+                println!("{}", tr!(RuleSuggestionsCreated, suggestions.len()));
+            }
+        }
+        Command::Export { output, query } => {
+            let query = Query::parse(query.as_deref().unwrap_or(""))?;
+            let records = budget_manager.query_transactions(&query)?;
+            match output {
+                Some(path) => export::export_csv(&records, File::create(path)?)?,
+                None => export::export_csv(&records, std::io::stdout())?,
+            }
+        }
+        Command::Dashboard => dashboard::run(budget_manager)?,
+        Command::Config(command) => return run_config_command(config_path, command),
+        Command::Repl => repl::run(budget_manager, format, config_path)?,
+        Command::Batch { file } => return batch::run_batch(budget_manager, &file, format, config_path),
+    }
+    Ok(true)
+}
@@ -0,0 +1,355 @@
+use serde::Deserialize;
+use std::fmt;
+use std::path::Path;
+use std::sync::OnceLock;
+use crate::i18n::{self, tr, Lang};
+
+// Préférences de l'application, lues au démarrage dans un fichier TOML.
+// Toutes les clés sont facultatives ; les valeurs absentes prennent leur
+// valeur par défaut. Les options de la ligne de commande (--db, --lang)
+// sont prioritaires sur le fichier.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    // Langue de l'interface ; absente : variables d'environnement
+    pub language: Option<Lang>,
+    // Symbole monétaire affiché avec les montants
+    pub currency: String,
+    // Fichier de base de données SQLite
+    pub database: String,
+    pub colors: ColorMode,
+    pub budgets: BudgetSettings,
+    pub import: ImportSettings,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorMode {
+    // Couleurs uniquement quand la sortie est un terminal
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BudgetSettings {
+    // Plafond du montant total d'un budget
+    pub max_total_amount: f64,
+    // Seuil (% dépensé) de la règle d'alerte créée avec chaque nouveau budget
+    pub default_alert_percent: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ImportSettings {
+    // Sort des lignes importées dont le budget n'existe pas
+    pub unknown_budget: UnknownBudgetPolicy,
+    // Montant total des budgets créés par l'import (politique "create")
+    pub new_budget_amount: f64,
+}
+
+// Ligne importée dont le budget n'existe pas (ni alias, voir `alias map`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UnknownBudgetPolicy {
+    // Ligne ignorée
+    Skip,
+    // Budget créé avec `new_budget_amount`
+    Create,
+    // Budget existant choisi à l'import puis retenu comme alias
+    Map,
+    // Ligne mise de côté parmi les transactions à classer
+    Inbox,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            language: None,
+            currency: "$".to_string(),
+            database: "budget_manager.db".to_string(),
+            colors: ColorMode::Auto,
+            budgets: BudgetSettings::default(),
+            import: ImportSettings::default(),
+        }
+    }
+}
+
+impl Default for BudgetSettings {
+    fn default() -> Self {
+        Self { max_total_amount: 1_000_000.0, default_alert_percent: 90.0 }
+    }
+}
+
+impl Default for ImportSettings {
+    fn default() -> Self {
+        Self { unknown_budget: UnknownBudgetPolicy::Skip, new_budget_amount: 0.0 }
+    }
+}
+
+pub const DEFAULT_PATH: &str = "budget_manager.toml";
+
+// Clés modifiables avec `config set`, dans l'ordre d'affichage
+pub const KEYS: [&str; 8] = [
+    "language",
+    "currency",
+    "database",
+    "colors",
+    "budgets.max_total_amount",
+    "budgets.default_alert_percent",
+    "import.unknown_budget",
+    "import.new_budget_amount",
+];
+
+#[derive(Debug)]
+pub struct ConfigError(String);
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", tr!(ConfigInvalid, self.0))
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+// Configuration active ; à fixer une seule fois au démarrage avec `set`
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(Config::default)
+}
+
+pub fn set(config: Config) {
+    let _ = CONFIG.set(config);
+}
+
+impl Config {
+//==================================Load and save====================================
+    // Un fichier absent donne la configuration par défaut
+    pub fn load(path: &str) -> Result<Self, ConfigError> {
+        if !Path::new(path).exists() {
+            return Ok(Self::default());
+        }
+        let text = std::fs::read_to_string(path).map_err(|err| ConfigError(format!("{} : {}", path, err)))?;
+        let config: Config = toml::from_str(&text).map_err(|err| ConfigError(format!("{} : {}", path, err.message())))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), ConfigError> {
+        self.validate()?;
+        std::fs::write(path, self.to_documented_toml()).map_err(|err| ConfigError(format!("{} : {}", path, err)))
+    }
+
+    fn validate(&self) -> Result<(), ConfigError> {
+        let error = |key: &str, message: String| Err(ConfigError(format!("{} : {}", key, message)));
+        let currency = self.currency.trim();
+        if currency.is_empty() || currency.chars().count() > 5 {
+            return error("currency", tr!(ConfigCurrency).to_string());
+        }
+        if self.database.trim().is_empty() {
+            return error("database", tr!(FieldRequired).to_string());
+        }
+        let max_total = self.budgets.max_total_amount;
+        if !max_total.is_finite() || max_total <= 0.0 {
+            return error("budgets.max_total_amount", tr!(ConfigPositive).to_string());
+        }
+        let percent = self.budgets.default_alert_percent;
+        if !percent.is_finite() || percent <= 0.0 || percent > 100.0 {
+            return error("budgets.default_alert_percent", tr!(ConfigPercent).to_string());
+        }
+        let new_budget_amount = self.import.new_budget_amount;
+        if !(0.0..=max_total).contains(&new_budget_amount) {
+            return error("import.new_budget_amount", tr!(BudgetAmountOutOfRange, i18n::amount(max_total)));
+        }
+        self.check_new_budget_amount(self.import.unknown_budget)
+    }
+
+    // Avec la politique "create", les budgets créés par l'import ont besoin d'un
+    // montant : vérifié aussi pour l'option `--unknown-budget` de l'import
+    pub fn check_new_budget_amount(&self, policy: UnknownBudgetPolicy) -> Result<(), ConfigError> {
+        if policy == UnknownBudgetPolicy::Create && self.import.new_budget_amount <= 0.0 {
+            return Err(ConfigError(format!("import.new_budget_amount : {}", tr!(ConfigCreateNeedsAmount))));
+        }
+        Ok(())
+    }
+
+//==================================View and edit====================================
+    // Valeur d'une clé telle qu'elle s'écrit dans le fichier
+    pub fn value(&self, key: &str) -> Option<String> {
+        Some(match key {
+            "language" => self.language.as_ref().map_or("auto", Lang::as_str).to_string(),
+            "currency" => self.currency.clone(),
+            "database" => self.database.clone(),
+            "colors" => self.colors.as_str().to_string(),
+            "budgets.max_total_amount" => self.budgets.max_total_amount.to_string(),
+            "budgets.default_alert_percent" => self.budgets.default_alert_percent.to_string(),
+            "import.unknown_budget" => self.import.unknown_budget.as_str().to_string(),
+            "import.new_budget_amount" => self.import.new_budget_amount.to_string(),
+            _ => return None,
+        })
+    }
+
+    // Modifie une clé ; la configuration n'est pas modifiée si la valeur est invalide
+    pub fn set_value(&mut self, key: &str, value: &str) -> Result<(), ConfigError> {
+        let mut updated = self.clone();
+        let value = value.trim();
+        let invalid = || ConfigError(format!("{} : {}", key, tr!(ConfigBadValue, value)));
+        match key {
+            "language" if value == "auto" => updated.language = None,
+            "language" => updated.language = Some(Lang::parse(value).ok_or_else(invalid)?),
+            "currency" => updated.currency = value.to_string(),
+            "database" => updated.database = value.to_string(),
+            "colors" => updated.colors = ColorMode::parse(value).ok_or_else(invalid)?,
+            "budgets.max_total_amount" => updated.budgets.max_total_amount = value.parse().map_err(|_| invalid())?,
+            "budgets.default_alert_percent" => updated.budgets.default_alert_percent = value.parse().map_err(|_| invalid())?,
+            "import.unknown_budget" => updated.import.unknown_budget = UnknownBudgetPolicy::parse(value).ok_or_else(invalid)?,
+            "import.new_budget_amount" => updated.import.new_budget_amount = value.parse().map_err(|_| invalid())?,
+            _ => return Err(ConfigError(tr!(ConfigUnknownKey, key, KEYS.join(", ")))),
+        }
+        updated.validate()?;
+        *self = updated;
+        Ok(())
+    }
+
+    // Fichier TOML avec un commentaire pour chaque clé, dans la langue de l'interface
+    pub fn to_documented_toml(&self) -> String {
+        let quote = |text: &str| toml::Value::String(text.to_string()).to_string();
+        let comment = |out: &mut String, text: &str| {
+            for line in text.lines() {
+                out.push_str(&format!("# {}\n", line));
+            }
+        };
+        let mut out = String::new();
+        comment(&mut out, tr!(ConfigDocTitle));
+        out.push('\n');
+        comment(&mut out, tr!(ConfigDocLanguage));
+        match self.language {
+            Some(lang) => out.push_str(&format!("language = {}\n", quote(lang.as_str()))),
+            None => out.push_str("# language = \"fr\"\n"),
+        }
+        out.push('\n');
+        comment(&mut out, tr!(ConfigDocCurrency));
+        out.push_str(&format!("currency = {}\n", quote(&self.currency)));
+        out.push('\n');
+        comment(&mut out, tr!(ConfigDocDatabase));
+        out.push_str(&format!("database = {}\n", quote(&self.database)));
+        out.push('\n');
+        comment(&mut out, tr!(ConfigDocColors));
+        out.push_str(&format!("colors = {}\n", quote(self.colors.as_str())));
+        out.push_str("\n[budgets]\n");
+        comment(&mut out, tr!(ConfigDocMaxTotal));
+        out.push_str(&format!("max_total_amount = {:?}\n", self.budgets.max_total_amount));
+        comment(&mut out, tr!(ConfigDocAlertPercent));
+        out.push_str(&format!("default_alert_percent = {:?}\n", self.budgets.default_alert_percent));
+        out.push_str("\n[import]\n");
+        comment(&mut out, tr!(ConfigDocUnknownBudget));
+        out.push_str(&format!("unknown_budget = {}\n", quote(self.import.unknown_budget.as_str())));
+        comment(&mut out, tr!(ConfigDocNewBudgetAmount));
+        out.push_str(&format!("new_budget_amount = {:?}\n", self.import.new_budget_amount));
+        out
+    }
+}
+
+impl ColorMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ColorMode::Auto => "auto",
+            ColorMode::Always => "always",
+            ColorMode::Never => "never",
+        }
+    }
+
+    pub fn parse(mode: &str) -> Option<Self> {
+        [ColorMode::Auto, ColorMode::Always, ColorMode::Never].into_iter().find(|m| m.as_str() == mode)
+    }
+}
+
+impl UnknownBudgetPolicy {
+    pub const ALL: [UnknownBudgetPolicy; 4] =
+        [UnknownBudgetPolicy::Skip, UnknownBudgetPolicy::Create, UnknownBudgetPolicy::Map, UnknownBudgetPolicy::Inbox];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UnknownBudgetPolicy::Skip => "skip",
+            UnknownBudgetPolicy::Create => "create",
+            UnknownBudgetPolicy::Map => "map",
+            UnknownBudgetPolicy::Inbox => "inbox",
+        }
+    }
+
+    pub fn parse(policy: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.as_str() == policy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Fichier de configuration temporaire, propre à chaque test
+    fn config_file(name: &str, text: &str) -> String {
+        let path = std::env::temp_dir().join(format!("budget_config_{}_{}.toml", std::process::id(), name));
+        std::fs::write(&path, text).unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn invalid_values_leave_the_config_unchanged() {
+        let mut config = Config::default();
+        config.set_value("currency", " CHF ").unwrap();
+        config.set_value("language", "en").unwrap();
+        config.set_value("budgets.max_total_amount", "5000").unwrap();
+        assert_eq!(config.value("currency").as_deref(), Some("CHF"));
+        assert_eq!(config.language, Some(Lang::En));
+        assert_eq!(config.budgets.max_total_amount, 5000.0);
+
+        let before = config.clone();
+        for (key, value) in [
+            ("colors", "rainbow"),
+            ("language", "de"),
+            ("currency", "dollars"),
+            ("budgets.max_total_amount", "-1"),
+            ("budgets.default_alert_percent", "150"),
+            ("budgets.default_alert_percent", "beaucoup"),
+            ("database", " "),
+            ("theme", "dark"),
+        ] {
+            assert!(config.set_value(key, value).is_err(), "{} = {}", key, value);
+        }
+        assert_eq!(config, before);
+        config.set_value("language", "auto").unwrap();
+        assert_eq!(config.language, None);
+    }
+
+    #[test]
+    fn documented_file_reads_back_the_same() {
+        let mut config = Config::default();
+        assert_eq!(toml::from_str::<Config>(&config.to_documented_toml()).unwrap(), config);
+        for (key, value) in [("language", "fr"), ("currency", "€"), ("database", "C:\\budgets \"perso\".db"), ("colors", "never")] {
+            config.set_value(key, value).unwrap();
+        }
+        config.set_value("budgets.default_alert_percent", "75.5").unwrap();
+        assert_eq!(toml::from_str::<Config>(&config.to_documented_toml()).unwrap(), config);
+        assert!(KEYS.iter().all(|key| config.value(key).is_some()));
+    }
+
+    #[test]
+    fn loads_files_with_missing_keys_and_rejects_unknown_ones() {
+        let missing = std::env::temp_dir().join("budget_config_absent.toml");
+        assert_eq!(Config::load(&missing.to_string_lossy()).unwrap(), Config::default());
+
+        let path = config_file("partial", "currency = \"CHF\"\n[budgets]\ndefault_alert_percent = 80\n");
+        let config = Config::load(&path).unwrap();
+        assert_eq!((config.currency.as_str(), config.budgets.default_alert_percent), ("CHF", 80.0));
+        assert_eq!(config.budgets.max_total_amount, BudgetSettings::default().max_total_amount);
+
+        for (name, text) in [("unknown", "theme = \"dark\"\n"), ("invalid", "[budgets]\ndefault_alert_percent = 0\n")] {
+            let path = config_file(name, text);
+            assert!(Config::load(&path).is_err(), "{}", text);
+            std::fs::remove_file(path).unwrap();
+        }
+        std::fs::remove_file(path).unwrap();
+    }
+}
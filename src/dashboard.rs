@@ -0,0 +1,519 @@
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::execute;
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
+use std::error::Error;
+use std::io::{self, Stdout};
+use tui::backend::{Backend, CrosstermBackend};
+use tui::layout::{Constraint, Direction, Layout, Rect};
+use tui::style::{Color, Modifier, Style};
+use tui::symbols;
+use tui::text::{Span, Spans};
+use tui::widgets::{Block, Borders, Cell, Clear, LineGauge, Paragraph, Row, Table, TableState};
+use tui::{Frame, Terminal};
+use crate::db::{Budget, BudgetManager, NewTransaction, OverspendCheck, TransactionRecord};
+use crate::i18n::{self, tr};
+use crate::query::{Filter, Query};
+
+// Tableau de bord plein écran : budgets avec barres de progression, transactions
+// du budget sélectionné et alertes en cours. Les formulaires permettent
+// d'ajouter, modifier et supprimer budgets et transactions sans quitter l'écran.
+
+const TRANSACTION_WIDTHS: [Constraint; 4] =
+    [Constraint::Percentage(40), Constraint::Percentage(20), Constraint::Percentage(20), Constraint::Percentage(20)];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Focus {
+    Budgets,
+    Transactions,
+}
+
+// Opération déclenchée par la validation d'un formulaire
+#[derive(Debug, Clone)]
+enum FormKind {
+    AddBudget,
+    EditBudget { old_name: String },
+    AddTransaction { budget_name: String },
+    EditTransaction { budget_name: String, transaction_id: i64 },
+}
+
+#[derive(Debug, Clone)]
+struct Form {
+    kind: FormKind,
+    title: &'static str,
+    fields: Vec<(&'static str, String)>,
+    focus: usize,
+}
+
+// Opération en attente d'une confirmation (o/n)
+#[derive(Debug, Clone)]
+enum Pending {
+    DeleteBudget { budget_name: String },
+    DeleteTransaction { budget_name: String, transaction_id: i64 },
+    OverspendAdd { budget_name: String, transaction: NewTransaction },
+    OverspendEdit { budget_name: String, transaction_id: i64, new_name: String, amount: f64 },
+}
+
+#[derive(Debug)]
+enum Mode {
+    Normal,
+    Form(Form),
+    Confirm(Pending, String),
+}
+
+struct App<'a> {
+    budget_manager: &'a BudgetManager,
+    budgets: Vec<Budget>,
+    transactions: Vec<TransactionRecord>,
+    alerts: Vec<String>,
+    focus: Focus,
+    budget_index: usize,
+    transaction_state: TableState,
+    mode: Mode,
+    status: String,
+}
+
+//==================================Run the dashboard====================================
+pub fn run(budget_manager: &BudgetManager) -> Result<(), Box<dyn Error>> {
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+
+    let result = event_loop(&mut terminal, budget_manager);
+
+    // Toujours restaurer le terminal, même en cas d'erreur
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+    result
+}
+
+fn event_loop(terminal: &mut Terminal<CrosstermBackend<Stdout>>, budget_manager: &BudgetManager) -> Result<(), Box<dyn Error>> {
+    let mut app = App {
+        budget_manager,
+        budgets: Vec::new(),
+        transactions: Vec::new(),
+        alerts: Vec::new(),
+        focus: Focus::Budgets,
+        budget_index: 0,
+        transaction_state: TableState::default(),
+        mode: Mode::Normal,
+        status: tr!(DashHelp).to_string(),
+    };
+    app.refresh()?;
+
+    loop {
+        terminal.draw(|f| draw(f, &mut app))?;
+
+        let Event::Key(key) = event::read()? else { continue };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        let changed = match std::mem::replace(&mut app.mode, Mode::Normal) {
+            Mode::Normal => match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                code => {
+                    app.handle_normal_key(code);
+                    false
+                }
+            },
+            Mode::Form(form) => app.handle_form_key(form, key.code),
+            Mode::Confirm(pending, question) => app.handle_confirm_key(pending, question, key.code),
+        };
+
+        if changed {
+            app.refresh()?;
+            // Les fonctions de la base écrivent sur la sortie standard : on
+            // force un redessin complet pour effacer ces messages
+            terminal.clear()?;
+        }
+    }
+}
+
+impl App<'_> {
+    // Recharge budgets, transactions et alertes depuis la base
+    fn refresh(&mut self) -> Result<(), Box<dyn Error>> {
+        self.budgets = self.budget_manager.list_budgets()?;
+        self.budget_index = self.budget_index.min(self.budgets.len().saturating_sub(1));
+
+        self.transactions = match self.selected_budget() {
+            Some(budget) => {
+                let query = Query { filters: vec![Filter::Budget(budget.budget_name.clone())] };
+                self.budget_manager.query_transactions(&query)?
+            }
+            None => Vec::new(),
+        };
+        let selected = self.transaction_state.selected().unwrap_or(0);
+        self.transaction_state
+            .select((!self.transactions.is_empty()).then(|| selected.min(self.transactions.len() - 1)));
+
+        self.alerts.clear();
+        for budget in &self.budgets {
+            self.alerts.extend(self.budget_manager.current_alerts(&budget.budget_name)?);
+        }
+        Ok(())
+    }
+
+    fn selected_budget(&self) -> Option<&Budget> {
+        self.budgets.get(self.budget_index)
+    }
+
+    fn selected_transaction(&self) -> Option<&TransactionRecord> {
+        self.transaction_state.selected().and_then(|i| self.transactions.get(i))
+    }
+
+    fn handle_normal_key(&mut self, code: KeyCode) {
+        match (code, self.focus) {
+            (KeyCode::Tab, Focus::Budgets) => self.focus = Focus::Transactions,
+            (KeyCode::Tab, Focus::Transactions) => self.focus = Focus::Budgets,
+            (KeyCode::Up | KeyCode::Char('k'), Focus::Budgets) => {
+                self.budget_index = self.budget_index.saturating_sub(1);
+                self.transaction_state.select(Some(0));
+                let _ = self.refresh();
+            }
+            (KeyCode::Down | KeyCode::Char('j'), Focus::Budgets) if self.budget_index + 1 < self.budgets.len() => {
+                self.budget_index += 1;
+                self.transaction_state.select(Some(0));
+                let _ = self.refresh();
+            }
+            (KeyCode::Up | KeyCode::Char('k'), Focus::Transactions) => {
+                let i = self.transaction_state.selected().unwrap_or(0);
+                self.transaction_state.select(Some(i.saturating_sub(1)));
+            }
+            (KeyCode::Down | KeyCode::Char('j'), Focus::Transactions) => {
+                let i = self.transaction_state.selected().unwrap_or(0);
+                if i + 1 < self.transactions.len() {
+                    self.transaction_state.select(Some(i + 1));
+                }
+            }
+            (KeyCode::Char('a'), Focus::Budgets) => {
+                self.mode = Mode::Form(Form::new(FormKind::AddBudget, tr!(DashNewBudget), vec![(tr!(DashFieldName), ""), (tr!(DashFieldTotal), "")]));
+            }
+            (KeyCode::Char('e'), Focus::Budgets) => {
+                if let Some(budget) = self.selected_budget() {
+                    let total = budget.total_amount.to_string();
+                    self.mode = Mode::Form(Form::new(
+                        FormKind::EditBudget { old_name: budget.budget_name.clone() },
+                        tr!(DashEditBudget),
+                        vec![(tr!(DashFieldName), &budget.budget_name), (tr!(DashFieldTotal), &total)],
+                    ));
+                }
+            }
+            (KeyCode::Char('d'), Focus::Budgets) => {
+                if let Some(budget) = self.selected_budget() {
+                    let question = tr!(DashConfirmRemoveBudget, budget.budget_name);
+                    self.mode = Mode::Confirm(Pending::DeleteBudget { budget_name: budget.budget_name.clone() }, question);
+                }
+            }
+            (KeyCode::Char('a'), Focus::Transactions) => {
+                if let Some(budget) = self.selected_budget() {
+                    self.mode = Mode::Form(Form::new(
+                        FormKind::AddTransaction { budget_name: budget.budget_name.clone() },
+                        tr!(DashNewTransaction),
+                        vec![
+                            (tr!(DashFieldName), ""),
+                            (tr!(DashFieldAmount), ""),
+                            (tr!(ColPayee), ""),
+                            (tr!(ColNotes), ""),
+                            (tr!(ColTags), ""),
+                        ],
+                    ));
+                }
+            }
+            (KeyCode::Char('e'), Focus::Transactions) => {
+                if let Some(transaction) = self.selected_transaction() {
+                    let amount = transaction.amount.to_string();
+                    self.mode = Mode::Form(Form::new(
+                        FormKind::EditTransaction {
+                            budget_name: transaction.budget_name.clone(),
+                            transaction_id: transaction.id,
+                        },
+                        tr!(DashEditTransaction),
+                        vec![(tr!(DashFieldName), &transaction.transaction_name), (tr!(DashFieldAmount), &amount)],
+                    ));
+                }
+            }
+            (KeyCode::Char('d'), Focus::Transactions) => {
+                if let Some(transaction) = self.selected_transaction() {
+                    let question = tr!(DashConfirmRemoveTransaction, transaction.transaction_name);
+                    self.mode = Mode::Confirm(
+                        Pending::DeleteTransaction {
+                            budget_name: transaction.budget_name.clone(),
+                            transaction_id: transaction.id,
+                        },
+                        question,
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Retourne true si la base a été modifiée
+    fn handle_form_key(&mut self, mut form: Form, code: KeyCode) -> bool {
+        match code {
+            KeyCode::Esc => {
+                self.status = tr!(DashInputCancelled).to_string();
+                false
+            }
+            KeyCode::Tab | KeyCode::Down => {
+                form.focus = (form.focus + 1) % form.fields.len();
+                self.mode = Mode::Form(form);
+                false
+            }
+            KeyCode::BackTab | KeyCode::Up => {
+                form.focus = (form.focus + form.fields.len() - 1) % form.fields.len();
+                self.mode = Mode::Form(form);
+                false
+            }
+            KeyCode::Backspace => {
+                form.fields[form.focus].1.pop();
+                self.mode = Mode::Form(form);
+                false
+            }
+            KeyCode::Char(c) => {
+                form.fields[form.focus].1.push(c);
+                self.mode = Mode::Form(form);
+                false
+            }
+            KeyCode::Enter => self.submit(form),
+            _ => {
+                self.mode = Mode::Form(form);
+                false
+            }
+        }
+    }
+
+    fn submit(&mut self, form: Form) -> bool {
+        let value = |i: usize| form.fields[i].1.trim().to_string();
+        let Ok(amount) = crate::parse::amount(&value(1)) else {
+            self.status = tr!(InvalidAmount).to_string();
+            self.mode = Mode::Form(form);
+            return false;
+        };
+
+        let result = match &form.kind {
+            FormKind::AddBudget => self.budget_manager.add_budget(&value(0), amount),
+            FormKind::EditBudget { old_name } => {
+                self.budget_manager.edit_budget(value(0), old_name.clone(), Some(amount)).map(|_| ())
+            }
+            FormKind::AddTransaction { budget_name } => {
+                let mut transaction = NewTransaction {
+                    payee: value(2),
+                    notes: value(3),
+                    tags: value(4).split_whitespace().map(String::from).collect(),
+                    ..NewTransaction::new(value(0), amount)
+                };
+                let check = self
+                    .budget_manager
+                    .apply_assignment_rules(&mut transaction)
+                    .and_then(|_| self.budget_manager.check_overspend(budget_name, amount));
+                match check {
+                    Ok(OverspendCheck::NeedsConfirmation { remaining_after }) => {
+                        let question = tr!(DashConfirmOverspend, i18n::amount(remaining_after));
+                        self.mode = Mode::Confirm(Pending::OverspendAdd { budget_name: budget_name.clone(), transaction }, question);
+                        return false;
+                    }
+                    _ => self.budget_manager.add_transaction(budget_name, transaction, false),
+                }
+            }
+            FormKind::EditTransaction { budget_name, transaction_id } => {
+                let extra = self.budget_manager.edit_extra_spending_by_id(budget_name, *transaction_id, amount).unwrap_or(0.0);
+                match self.budget_manager.check_overspend(budget_name, extra) {
+                    Ok(OverspendCheck::NeedsConfirmation { remaining_after }) => {
+                        let question = tr!(DashConfirmOverspend, i18n::amount(remaining_after));
+                        let pending = Pending::OverspendEdit {
+                            budget_name: budget_name.clone(),
+                            transaction_id: *transaction_id,
+                            new_name: value(0),
+                            amount,
+                        };
+                        self.mode = Mode::Confirm(pending, question);
+                        return false;
+                    }
+                    _ => self.budget_manager.edit_transaction_by_id(budget_name, *transaction_id, value(0), amount, false).map(|_| ()),
+                }
+            }
+        };
+        self.report(result, tr!(DashSaved))
+    }
+
+    fn handle_confirm_key(&mut self, pending: Pending, question: String, code: KeyCode) -> bool {
+        match code {
+            KeyCode::Char('o' | 'O' | 'y' | 'Y') | KeyCode::Enter => {}
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                self.status = tr!(OperationCancelled).to_string();
+                return false;
+            }
+            _ => {
+                self.mode = Mode::Confirm(pending, question);
+                return false;
+            }
+        }
+
+        let result = match pending {
+            Pending::DeleteBudget { budget_name } => self.budget_manager.remove_budget(&budget_name).map(|_| ()),
+            Pending::DeleteTransaction { budget_name, transaction_id } => {
+                self.budget_manager.remove_transaction_by_id(&budget_name, transaction_id).map(|_| ())
+            }
+            Pending::OverspendAdd { budget_name, transaction } => {
+                self.budget_manager.add_transaction(&budget_name, transaction, true)
+            }
+            Pending::OverspendEdit { budget_name, transaction_id, new_name, amount } => {
+                self.budget_manager.edit_transaction_by_id(&budget_name, transaction_id, new_name, amount, true).map(|_| ())
+            }
+        };
+        self.report(result, tr!(DashDone))
+    }
+
+    fn report(&mut self, result: rusqlite::Result<()>, success: &str) -> bool {
+        match result {
+            Ok(()) => self.status = success.to_string(),
+            Err(err) => self.status = tr!(Error, err),
+        }
+        true
+    }
+}
+
+impl Form {
+    fn new(kind: FormKind, title: &'static str, fields: Vec<(&'static str, &str)>) -> Self {
+        let fields = fields.into_iter().map(|(label, value)| (label, value.to_string())).collect();
+        Self { kind, title, fields, focus: 0 }
+    }
+}
+
+//==================================Drawing====================================
+fn draw<B: Backend>(f: &mut Frame<B>, app: &mut App) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(8), Constraint::Length(6), Constraint::Length(1)])
+        .split(f.size());
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(rows[0]);
+
+    draw_budgets(f, app, columns[0]);
+    draw_transactions(f, app, columns[1]);
+    draw_alerts(f, app, rows[1]);
+    f.render_widget(Paragraph::new(app.status.as_str()).style(Style::default().fg(Color::Cyan)), rows[2]);
+
+    match &app.mode {
+        Mode::Form(form) => draw_form(f, form),
+        Mode::Confirm(_, question) => {
+            let area = centered(f.size(), 60, 3);
+            f.render_widget(Clear, area);
+            let block = Block::default().borders(Borders::ALL).title(tr!(DashConfirmTitle));
+            f.render_widget(Paragraph::new(question.as_str()).block(block), area);
+        }
+        Mode::Normal => {}
+    }
+}
+
+fn panel(title: &str, focused: bool) -> Block<'_> {
+    let style = if focused {
+        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default()
+    };
+    Block::default().borders(Borders::ALL).title(Span::styled(title, style))
+}
+
+// Une barre de progression par budget (dépensé / total)
+fn draw_budgets<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+    let block = panel(tr!(DashBudgets), app.focus == Focus::Budgets);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    if app.budgets.is_empty() {
+        f.render_widget(Paragraph::new(tr!(DashNoBudgets)), inner);
+        return;
+    }
+
+    // Faire défiler la liste pour que le budget sélectionné reste visible
+    let visible = inner.height.max(1) as usize;
+    let first = app.budget_index.saturating_sub(visible - 1);
+    for (row, (i, budget)) in app.budgets.iter().enumerate().skip(first).take(visible).enumerate() {
+        let ratio = if budget.total_amount > 0.0 { budget.spent_amount / budget.total_amount } else { 1.0 };
+        let color = match ratio {
+            r if r >= 1.0 => Color::Red,
+            r if r >= 0.75 => Color::Yellow,
+            _ => Color::Green,
+        };
+        let label_style = if i == app.budget_index {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default()
+        };
+        let label = format!("{} {} / {}", budget.budget_name, i18n::amount(budget.spent_amount), i18n::amount(budget.total_amount));
+        let gauge = LineGauge::default()
+            .gauge_style(Style::default().fg(color))
+            .line_set(symbols::line::THICK)
+            .ratio(ratio.clamp(0.0, 1.0))
+            .label(Spans::from(Span::styled(label, label_style)));
+        f.render_widget(gauge, Rect { y: inner.y + row as u16, height: 1, ..inner });
+    }
+}
+
+fn draw_transactions<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
+    let title = match app.selected_budget() {
+        Some(budget) => format!("{} — {}", tr!(DashTransactions), budget.budget_name),
+        None => tr!(DashTransactions).to_string(),
+    };
+    let rows = app.transactions.iter().map(|t| {
+        Row::new(vec![
+            Cell::from(t.transaction_name.clone()),
+            Cell::from(i18n::amount(t.amount)),
+            Cell::from(t.transaction_date.as_deref().map(i18n::date).unwrap_or_default()),
+            Cell::from(t.payee.clone()),
+        ])
+    });
+    let table = Table::new(rows)
+        .header(Row::new(vec![tr!(DashFieldName), tr!(ColAmount), tr!(ColDate), tr!(ColPayee)]).style(Style::default().add_modifier(Modifier::BOLD)))
+        .block(panel(&title, app.focus == Focus::Transactions))
+        .widths(&TRANSACTION_WIDTHS)
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    f.render_stateful_widget(table, area, &mut app.transaction_state);
+}
+
+fn draw_alerts<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+    let lines: Vec<Spans> = if app.alerts.is_empty() {
+        vec![Spans::from(tr!(DashNoAlerts))]
+    } else {
+        app.alerts.iter().map(|alert| Spans::from(Span::styled(alert.as_str(), Style::default().fg(Color::Red)))).collect()
+    };
+    f.render_widget(Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(tr!(DashAlerts))), area);
+}
+
+fn draw_form<B: Backend>(f: &mut Frame<B>, form: &Form) {
+    let area = centered(f.size(), 60, form.fields.len() as u16 + 4);
+    f.render_widget(Clear, area);
+
+    let mut lines: Vec<Spans> = form
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(i, (label, value))| {
+            let style = if i == form.focus { Style::default().fg(Color::Yellow) } else { Style::default() };
+            Spans::from(vec![Span::styled(format!("{:>14} : ", label), style), Span::raw(value.as_str())])
+        })
+        .collect();
+    lines.push(Spans::from(""));
+    lines.push(Spans::from(tr!(DashFormHelp)));
+    let block = Block::default().borders(Borders::ALL).title(form.title);
+    f.render_widget(Paragraph::new(lines).block(block), area);
+
+    let value = &form.fields[form.focus].1;
+    f.set_cursor(area.x + 1 + 17 + value.chars().count() as u16, area.y + 1 + form.focus as u16);
+}
+
+// Rectangle centré de largeur `percent_x` % et de hauteur fixe
+fn centered(area: Rect, percent_x: u16, height: u16) -> Rect {
+    let width = area.width * percent_x / 100;
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height: height.min(area.height),
+    }
+}
@@ -1,14 +1,137 @@
 use console::Style; 
 use rusqlite::{params, Connection, Result};
 use colored::*;
-use prettytable::{Table, row, cell};
+use serde::Serialize;
+use std::cell::{Cell, RefCell};
+use crate::config;
+use crate::events::{BudgetEvent, EventSubscriber};
+use crate::i18n::{self, tr};
+use crate::output::{self, OutputFormat};
+
+mod accounts;
+mod aliases;
+mod alerts;
+mod categories;
+mod duplicates;
+mod inbox;
+mod overspend;
+mod profiles;
+mod rules;
+mod search;
+mod query;
+pub use accounts::AccountBudget;
+pub use aliases::BudgetAlias;
+pub use alerts::{AlertRule, AlertRuleKind};
+pub use categories::CategoryBudget;
+pub use inbox::UnassignedTransaction;
+pub use overspend::{OverspendCheck, OverspendPolicy};
+pub use profiles::{Encoding, ImportProfile, SignConvention};
+pub use rules::{AssignmentRule, RuleSet, RuleSuggestion};
+
+// Données d'une transaction à enregistrer
+#[derive(Debug, Clone, Default)]
+pub struct NewTransaction {
+    pub transaction_name: String,
+    pub amount: f64,
+    pub notes: String,
+    pub payee: String,
+    pub tags: Vec<String>,
+    // Date ISO (AAAA-MM-JJ) ; la date du jour si absente
+    pub transaction_date: Option<String>,
+    // Compte d'origine d'une transaction importée (relevé bancaire) ; vide sinon
+    pub account: String,
+    // Identifiant attribué par la banque (FITID OFX, référence CAMT.053 ou MT940) ; vide sinon
+    pub external_id: String,
+    // Catégorie du fichier importé (QIF) ou attribuée par une règle ; vide sinon
+    pub category: String,
+}
+
+// Un budget et son solde calculé à partir des transactions
+#[derive(Debug, Clone, Serialize)]
+pub struct Budget {
+    pub id: i32,
+    pub budget_name: String,
+    pub total_amount: f64,
+    pub spent_amount: f64,
+    pub remaining_amount: f64,
+    pub overspend_policy: OverspendPolicy,
+}
+
+impl Budget {
+    const SELECT: &'static str =
+        "SELECT b.id, b.budget_name, b.total_amount, COALESCE(SUM(t.amount), 0), b.overspend_policy
+         FROM budgets b LEFT JOIN transactions t ON t.budget_id = b.id";
+
+    fn from_row(row: &rusqlite::Row) -> Result<Self> {
+        let total_amount: f64 = row.get(2)?;
+        let spent_amount: f64 = row.get(3)?;
+        Ok(Self {
+            id: row.get(0)?,
+            budget_name: row.get(1)?,
+            total_amount,
+            spent_amount,
+            remaining_amount: total_amount - spent_amount,
+            overspend_policy: OverspendPolicy::parse(&row.get::<_, String>(4)?).unwrap_or(OverspendPolicy::Allow),
+        })
+    }
+}
+
+// Une transaction telle qu'elle est lue depuis la base
+#[derive(Debug, Clone, Serialize)]
+pub struct TransactionRecord {
+    pub id: i64,
+    pub budget_name: String,
+    pub transaction_name: String,
+    pub amount: f64,
+    pub transaction_date: Option<String>,
+    pub payee: String,
+    pub notes: String,
+    pub tags: Vec<String>,
+    pub category: String,
+}
+
+impl TransactionRecord {
+    // Colonnes attendues par `from_row`, dans l'ordre
+    pub(crate) const COLUMNS: &'static str =
+        "t.id, b.budget_name, t.transaction_name, t.amount, t.transaction_date, t.payee, t.notes, t.tags, t.category";
+
+    fn from_row(row: &rusqlite::Row) -> Result<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            budget_name: row.get(1)?,
+            transaction_name: row.get(2)?,
+            amount: row.get(3)?,
+            transaction_date: row.get(4)?,
+            payee: row.get(5)?,
+            notes: row.get(6)?,
+            tags: split_tags(&row.get::<_, String>(7)?),
+            category: row.get(8)?,
+        })
+    }
+}
+
+impl NewTransaction {
+    pub fn new(transaction_name: impl Into<String>, amount: f64) -> Self {
+        Self { transaction_name: transaction_name.into(), amount, ..Default::default() }
+    }
+}
+
 pub struct BudgetManager {
     conn: Connection,
+    subscribers: Vec<Box<dyn EventSubscriber>>,
+    // Savepoints ouverts par `atomically` et événements émis pendant ce temps :
+    // ils ne sont diffusés qu'une fois les écritures réellement enregistrées
+    savepoint_depth: Cell<usize>,
+    pending_events: RefCell<Vec<BudgetEvent>>,
 }
 
 impl std::fmt::Debug for BudgetManager {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("BudgetManager").field("conn", &self.conn).finish()
+        f.debug_struct("BudgetManager")
+            .field("conn", &self.conn)
+            .field("subscribers", &self.subscribers.len())
+            .field("savepoint_depth", &self.savepoint_depth.get())
+            .finish()
     }
 }
 
@@ -22,7 +145,8 @@ impl  BudgetManager {
                 id INTEGER PRIMARY KEY AUTOINCREMENT,
                 budget_name TEXT NOT NULL,
                 total_amount REAL NOT NULL,
-                remaining_amount REAL NOT NULL 
+                remaining_amount REAL NOT NULL,
+                overspend_policy TEXT NOT NULL DEFAULT 'allow'
             )",
             [],
         )?;
@@ -33,12 +157,92 @@ impl  BudgetManager {
                 budget_id INTEGER NOT NULL,
                 transaction_name TEXT NOT NULL,
                 amount REAL NOT NULL,
+                transaction_date TEXT,
+                notes TEXT NOT NULL DEFAULT '',
+                payee TEXT NOT NULL DEFAULT '',
+                tags TEXT NOT NULL DEFAULT '',
+                account TEXT NOT NULL DEFAULT '',
+                external_id TEXT NOT NULL DEFAULT '',
+                category TEXT NOT NULL DEFAULT '',
                 FOREIGN KEY(budget_id) REFERENCES budgets(id)
             )",
             [],
         )?;
+        // Bases créées avant l'ajout de ces colonnes
+        ensure_column(&conn, "transactions", "transaction_date", "TEXT")?;
+        ensure_column(&conn, "transactions", "notes", "TEXT NOT NULL DEFAULT ''")?;
+        ensure_column(&conn, "transactions", "payee", "TEXT NOT NULL DEFAULT ''")?;
+        ensure_column(&conn, "transactions", "tags", "TEXT NOT NULL DEFAULT ''")?;
+        ensure_column(&conn, "transactions", "account", "TEXT NOT NULL DEFAULT ''")?;
+        ensure_column(&conn, "transactions", "external_id", "TEXT NOT NULL DEFAULT ''")?;
+        ensure_column(&conn, "transactions", "category", "TEXT NOT NULL DEFAULT ''")?;
+        ensure_column(&conn, "budgets", "overspend_policy", "TEXT NOT NULL DEFAULT 'allow'")?;
+
+        let manager = Self {
+            conn,
+            subscribers: Vec::new(),
+            savepoint_depth: Cell::new(0),
+            pending_events: RefCell::new(Vec::new()),
+        };
+        manager.create_alert_rules_table()?;
+        manager.create_search_index()?;
+        manager.create_import_profiles_table()?;
+        manager.create_account_budgets_table()?;
+        manager.create_category_budgets_table()?;
+        manager.create_assignment_rules_table()?;
+        manager.create_budget_aliases_table()?;
+        manager.create_unassigned_table()?;
+        Ok(manager)
+    }
+
+//==================================Event subscribers====================================
+    // Enregistre un abonné qui recevra tous les événements émis par le gestionnaire
+    pub fn subscribe(&mut self, subscriber: impl EventSubscriber + 'static) {
+        self.subscribers.push(Box::new(subscriber));
+    }
 
-        Ok(Self { conn })
+    // Dans `atomically`, l'événement attend la fin du savepoint le plus externe
+    pub(crate) fn emit(&self, event: BudgetEvent) {
+        if self.savepoint_depth.get() > 0 {
+            self.pending_events.borrow_mut().push(event);
+            return;
+        }
+        for subscriber in &self.subscribers {
+            subscriber.on_event(&event);
+        }
+    }
+
+    // Exécute `f` dans un SAVEPOINT : toutes ses écritures sont annulées si elle échoue.
+    // Les savepoints s'imbriquent, contrairement à BEGIN/COMMIT. Les événements émis
+    // par `f` sont abandonnés avec ses écritures, ou diffusés après le RELEASE du
+    // savepoint le plus externe.
+    pub fn atomically<T, E: From<rusqlite::Error>>(&self, f: impl FnOnce() -> std::result::Result<T, E>) -> std::result::Result<T, E> {
+        self.conn.execute_batch("SAVEPOINT atomic")?;
+        let queued = self.pending_events.borrow().len();
+        self.savepoint_depth.set(self.savepoint_depth.get() + 1);
+        let result = f();
+        self.savepoint_depth.set(self.savepoint_depth.get() - 1);
+
+        match result {
+            Ok(value) => {
+                if let Err(err) = self.conn.execute_batch("RELEASE atomic") {
+                    self.pending_events.borrow_mut().truncate(queued);
+                    return Err(err.into());
+                }
+                if self.savepoint_depth.get() == 0 {
+                    let events = std::mem::take(&mut *self.pending_events.borrow_mut());
+                    for event in events {
+                        self.emit(event);
+                    }
+                }
+                Ok(value)
+            }
+            Err(err) => {
+                self.pending_events.borrow_mut().truncate(queued);
+                self.conn.execute_batch("ROLLBACK TO atomic; RELEASE atomic")?;
+                Err(err)
+            }
+        }
     }
  //==================================Function to add budgets====================================
     pub fn add_budget(&self, budget_name: &str, total_amount: f64) -> Result<()> {
@@ -48,90 +252,138 @@ impl  BudgetManager {
          // Vérification : Le nom du budget ne peut pas être vide
 
         if budget_name.trim().is_empty(){ 
-            println!("{}",error_style.apply_to("Erreur: Le champ nom du budget ne peut pas etre vide."));
-            return Err(rusqlite::Error::InvalidParameterName("Le nom du budget est vide".into()));
+            println!("{}",error_style.apply_to(tr!(Error, tr!(BudgetNameEmpty))));
+            return Err(rusqlite::Error::InvalidParameterName(tr!(BudgetNameEmpty).into()));
         }
     
         // Vérification : Le montant total doit être positif et raisonnable
 
-        if total_amount < 0.0 || total_amount > 1_000_000.0{ 
-            println!("{}",error_style.apply_to("Erreur:  Le montant total doit être compris entre 0 et 1 000 000"));
-            return Err(rusqlite::Error::InvalidParameterName("Le montant total est invalide".into()));
+        let max_total_amount = config::get().budgets.max_total_amount;
+        if !(0.0..=max_total_amount).contains(&total_amount) { 
+            let message = tr!(BudgetAmountOutOfRange, i18n::amount(max_total_amount));
+            println!("{}",error_style.apply_to(tr!(Error, message)));
+            return Err(rusqlite::Error::InvalidParameterName(message));
         }
         // Pour un nouveau budget, le montant restant est égal au montant total
         let remaining_amount = total_amount;
         
-        // Insertion dans la base de données
-        self.conn.execute(
-            "INSERT INTO budgets (budget_name,total_amount,remaining_amount) VALUES (?1,?2,?3)",
-             params! [budget_name,total_amount,remaining_amount],
-        )?;
-        println!("Ajout en cours.....\n");
-        println!("{}", success_style.apply_to(format!(
-            "Budget '{}' ajouté avec succès ! Montant total : {:.2}, Montant restant : {:.2}.",
-            budget_name, total_amount, remaining_amount )));
+        // Insertion dans la base de données, avec la règle d'alerte par défaut :
+        // alerte quand 90 % du budget est dépensé (voir la configuration)
+        self.atomically(|| {
+            self.conn.execute(
+                "INSERT INTO budgets (budget_name,total_amount,remaining_amount) VALUES (?1,?2,?3)",
+                 params! [budget_name,total_amount,remaining_amount],
+            )?;
+            let budget_id = self.conn.last_insert_rowid();
+            self.conn.execute(
+                "INSERT INTO alert_rules (budget_id, kind, threshold) VALUES (?1, ?2, ?3)",
+                params![budget_id, AlertRuleKind::PercentUsed.as_str(), config::get().budgets.default_alert_percent],
+            )
+        })?;
+        println!("{}\n", tr!(AddingBudget));
+        println!("{}", success_style.apply_to(tr!(
+            BudgetAdded,
+            budget_name, i18n::amount(total_amount), i18n::amount(remaining_amount))));
+        self.emit(BudgetEvent::BudgetCreated {
+            budget_name: budget_name.to_string(),
+            total_amount,
+        });
         Ok(())
     }
 
 //==================================Function to remove budgets====================================
-    pub fn remove_budget(&self, budget_name: &str) -> Result<()> {
+    // Retourne false si le budget n'existe pas
+    pub fn remove_budget(&self, budget_name: &str) -> Result<bool> {
         let error_style = Style::new().red();
         let success_style = Style::new().green();
-        
-        let rows_affected = self.conn.execute(
-            "DELETE FROM budgets WHERE budget_name= ?1",
-            params![budget_name],
-        )?;
+
+        let rows_affected = self.atomically(|| {
+            self.conn.execute(
+                "DELETE FROM alert_rules WHERE budget_id IN (SELECT id FROM budgets WHERE budget_name = ?1)",
+                params![budget_name],
+            )?;
+            // Une règle d'affectation qui donne aussi une catégorie ou des tags les garde
+            self.conn.execute(
+                "UPDATE assignment_rules SET budget_id = NULL
+                 WHERE budget_id IN (SELECT id FROM budgets WHERE budget_name = ?1) AND (category <> '' OR tags <> '')",
+                params![budget_name],
+            )?;
+            // Les transactions du budget partent avec lui (index de recherche et empreintes d'import compris)
+            for table in ["transactions", "account_budgets", "category_budgets", "assignment_rules", "budget_aliases"] {
+                self.conn.execute(
+                    &format!("DELETE FROM {} WHERE budget_id IN (SELECT id FROM budgets WHERE budget_name = ?1)", table),
+                    params![budget_name],
+                )?;
+            }
+            self.conn.execute(
+                "DELETE FROM budgets WHERE budget_name= ?1",
+                params![budget_name],
+            )
+        })?;
 
         if rows_affected > 0 {
-            println!("{}", success_style.apply_to(format!("Budget'{}' supprimée avec succès .", budget_name)));
+            println!("{}", success_style.apply_to(tr!(BudgetRemoved, budget_name)));
+            self.emit(BudgetEvent::BudgetDeleted { budget_name: budget_name.to_string() });
         } else {
-            println!("{}", error_style.apply_to(format!("Erreur: Le budget : '{}' n'existe pas", budget_name)));
+            println!("{}", error_style.apply_to(tr!(Error, tr!(BudgetNotFound, budget_name))));
         }
 
-        Ok(())
+        Ok(rows_affected > 0)
     }
 //==================================Function to edit budgets====================================
-    pub fn edit_budget(&self, new_budget_name: String, old_budget_name: String, new_total_amount: f64) -> Result<()> {
+    // Retourne false si le budget n'existe pas
+    // `new_total_amount` : None garde le montant actuel
+    pub fn edit_budget(&self, new_budget_name: String, old_budget_name: String, new_total_amount: Option<f64>) -> Result<bool> {
         let error_style = Style::new().red();
         let success_style = Style::new().green();
 
+        // Même vérification du montant qu'à la création
+        let max_total_amount = config::get().budgets.max_total_amount;
+        if new_total_amount.is_some_and(|amount| !(0.0..=max_total_amount).contains(&amount)) {
+            let message = tr!(BudgetAmountOutOfRange, i18n::amount(max_total_amount));
+            println!("{}", error_style.apply_to(tr!(Error, message)));
+            return Err(rusqlite::Error::InvalidParameterName(message));
+        }
+
         let rows_affected = self.conn.execute(
-            "UPDATE budgets SET budget_name = ?1, total_amount = ?2 WHERE budget_name = ?3",
+            "UPDATE budgets SET budget_name = ?1, total_amount = COALESCE(?2, total_amount) WHERE budget_name = ?3",
             params![new_budget_name, new_total_amount, old_budget_name],
         )?;
 
         if rows_affected > 0 {
-            println!("{}", success_style.apply_to("Budget modifiée avec succès !"));
+            println!("{}", success_style.apply_to(tr!(BudgetEdited)));
+            let total_amount = self.get_budget(&new_budget_name)?.map_or(0.0, |budget| budget.total_amount);
+            self.emit(BudgetEvent::BudgetEdited {
+                old_budget_name: old_budget_name.clone(),
+                new_budget_name: new_budget_name.clone(),
+                total_amount,
+            });
         } else {
-            println!("{}", error_style.apply_to(format!("Erreur:Le budget '{}'n'existe pas ", old_budget_name)));
+            println!("{}", error_style.apply_to(tr!(Error, tr!(BudgetNotFound, old_budget_name))));
         }
 
-        Ok(())
+        Ok(rows_affected > 0)
+    }
+//==================================Function to list budgets====================================
+    pub fn list_budgets(&self) -> Result<Vec<Budget>> {
+        let mut stmt = self.conn.prepare(&format!("{} GROUP BY b.id ORDER BY b.id", Budget::SELECT))?;
+        let budgets = stmt.query_map([], Budget::from_row)?;
+        budgets.collect()
     }
-//==================================Function to display budgets====================================
-    pub fn display_budgets(&self) ->Result<()> {
-        let mut stmt = self.conn.prepare("SELECT id, budget_name, total_amount, remaining_amount FROM budgets")?; // Utilisation de "?" pour extraire Statement
-        let budget_iterator = stmt.query_map([], |row| {
-    Ok((
-        row.get::<_, i32>(0)?,      // id
-        row.get::<_, String>(1)?,  // budget_name
-        row.get::<_, f64>(2)?,     // total_amount
-        row.get::<_, f64>(3)?,     // remaining_amount
-    ))
-    })?;
-    let mut table = Table::new();
-    println!("{}", "Liste des budgets :\n".bold().underline().green());
-    println!("{}", "Création en cours .....\n".green().bold());
-    table.add_row(row!["ID".bold(),"Nom du Budget".bold(),"Total $".bold(),"Restant $".bold()]);
-    
-    for budget in budget_iterator {
-        let (id,name,total,remaining)=budget?;
-        table.add_row(row![id,name,total,remaining]);
 
+    pub fn get_budget(&self, budget_name: &str) -> Result<Option<Budget>> {
+        let mut stmt = self.conn.prepare(&format!("{} WHERE b.budget_name = ?1 GROUP BY b.id", Budget::SELECT))?;
+        let mut budgets = stmt.query_map(params![budget_name], Budget::from_row)?;
+        budgets.next().transpose()
     }
-    table.printstd();
-    Ok(())
+
+//==================================Function to display budgets====================================
+    pub fn display_budgets(&self, format: OutputFormat) -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let budgets = self.list_budgets()?;
+        if format == OutputFormat::Table {
+            println!("{}\n", tr!(BudgetListTitle).bold().underline().green());
+        }
+        output::render(&budgets, format, std::io::stdout())
     }
 
     //==================================function to get budgets'id ===================================
@@ -150,22 +402,27 @@ impl  BudgetManager {
         }
 
     }
+    // Comme get_budget_id, mais un budget absent est une erreur
+    fn require_budget_id(&self, budget_name: &str) -> Result<i32> {
+        self.get_budget_id(budget_name)?
+            .ok_or_else(|| rusqlite::Error::InvalidParameterName(tr!(BudgetNotFound, budget_name)))
+    }
+
     pub fn afficher_alerte(message: &str) {
-        println!("{}", " ALERTE:".red().bold());
+        println!("{}", tr!(AlertTitle).red().bold());
         println!("{}", message.yellow().bold());
     }
 
 //==================================calculate remaining amount===================================
 pub fn calculate_remaining_amount(&self, budget_name: &str, total_amount: f64) -> Result<f64> {
     let error_style = Style::new().red();
-    let success_style = Style::new().green();
 
     // Obtenir l'ID du budget correspondant
     let budget_id = self.get_budget_id(budget_name)?;
 
     if budget_id.is_none() {
-        println!("{}", error_style.apply_to("Erreur : Le budget spécifié n'existe pas"));
-        return Err(rusqlite::Error::InvalidParameterName("Budget non trouvé".into()));
+        println!("{}", error_style.apply_to(tr!(Error, tr!(BudgetNotFound, budget_name))));
+        return Err(rusqlite::Error::InvalidParameterName(tr!(BudgetNotFound, budget_name)));
     }
     let budget_id = budget_id.unwrap();
 
@@ -177,49 +434,74 @@ pub fn calculate_remaining_amount(&self, budget_name: &str, total_amount: f64) -
 
     let remaining_amount = total_amount - total_spent;
 
-    if remaining_amount < total_amount * 0.1 {
-        Self::afficher_alerte(&format!(
-            "Le montant restant est inférieur à 10 % du budget total ({}$ restant)",
-            remaining_amount
-        ));
-    }
-
     Ok(remaining_amount)
 }
 
 //==================================Function to add transactions====================================
     
-    pub fn add_transaction(&self, budget_name: &str, transaction_name: String, total_amount: f64) -> Result<()> {
+    // `overspend_confirmed` : l'utilisateur a accepté un dépassement (politique "warn")
+    pub fn add_transaction(&self, budget_name: &str, transaction: NewTransaction, overspend_confirmed: bool) -> Result<()> {
         let error_style = Style::new().red();
         let success_style = Style::new().green();
 
-        let budget_id = self.get_budget_id(budget_name)?.ok_or_else(|| {
-            println!("{}", error_style.apply_to(format!("Erreur : Le budget '{}' n'existe pas", budget_name)));
-            rusqlite::Error::InvalidParameterName("Budget introuvable".into())
-        })?;
-        
+        if self.get_budget_id(budget_name)?.is_none() {
+            println!("{}", error_style.apply_to(tr!(Error, tr!(BudgetNotFound, budget_name))));
+            return Err(rusqlite::Error::InvalidParameterName(tr!(BudgetNotFound, budget_name)));
+        }
 
-        self.conn.execute(
-            "INSERT INTO transactions (budget_id, transaction_name, amount) VALUES (?1, ?2, ?3)",
-            params![budget_id, transaction_name, total_amount],
-        )?;
+        let amount = transaction.amount;
+        // Comme add_budget, les refus (politique de dépassement) sont affichés ici
+        if let Err(err) = self.insert_transaction(budget_name, transaction, overspend_confirmed) {
+            if let rusqlite::Error::InvalidParameterName(message) = &err {
+                println!("{}", error_style.apply_to(tr!(Error, message)));
+            }
+            return Err(err);
+        }
+        println!("{}", success_style.apply_to(tr!(TransactionAdded)));
+        self.evaluate_alert_rules(budget_name, Some(amount))?;
+        Ok(())
+    }
+
+    // Insertion sans message ni évaluation des alertes, pour les imports : les
+    // alertes sont évaluées une fois par budget à la fin de l'import
+    pub fn insert_transaction(&self, budget_name: &str, transaction: NewTransaction, overspend_confirmed: bool) -> Result<()> {
+        let budget_id = self.require_budget_id(budget_name)?;
+
+        // Vérification de la politique de dépassement et insertion dans une même transaction SQLite
+        self.atomically(|| {
+            self.enforce_overspend_policy(budget_name, transaction.amount, overspend_confirmed)?;
+            self.conn.execute(
+                "INSERT INTO transactions (budget_id, transaction_name, amount, transaction_date, notes, payee, tags, account, external_id, category)
+                 VALUES (?1, ?2, ?3, COALESCE(?4, date('now')), ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    budget_id, transaction.transaction_name, transaction.amount, transaction.transaction_date,
+                    transaction.notes, transaction.payee, join_tags(&transaction.tags), transaction.account,
+                    transaction.external_id, transaction.category
+                ],
+            )
+        })?;
 
-        println!("{}", success_style.apply_to("Transaction ajoutée avec succès !"));
+        self.emit(BudgetEvent::TransactionAdded {
+            budget_name: budget_name.to_string(),
+            transaction_name: transaction.transaction_name,
+            amount: transaction.amount,
+        });
         Ok(())
     }
 
 //==================================Function to remove transactions====================================
 
-    pub fn remove_transaction(&self, budget_name: &str, transaction_name: &str) -> Result<()> {
+    // Retourne false si aucune transaction ne correspond
+    pub fn remove_transaction(&self, budget_name: &str, transaction_name: &str) -> Result<bool> {
         let error_style = Style::new().red();
         let success_style = Style::new().green();
         let warning_style = Style::new().yellow();
 
-        let budget_id = match self.get_budget_id(budget_name) {
-            Ok(id) => id,
-            Err(_) => {
-                println!("{}", error_style.apply_to(format!("Erreur : Le budget '{}' n'existe pas", budget_name)));
-                return Ok(());
+        let budget_id = match self.get_budget_id(budget_name)? {
+            Some(id) => id,
+            None => {
+                println!("{}", error_style.apply_to(tr!(Error, tr!(BudgetNotFound, budget_name))));
+                return Ok(false);
             }
         };
 
@@ -229,48 +511,159 @@ pub fn calculate_remaining_amount(&self, budget_name: &str, total_amount: f64) -
         )?;
 
         if rows_affected > 0 {
-            println!("{}", success_style.apply_to(format!("Transaction '{}' supprimée avec succès dans le budget '{}'.", transaction_name, budget_name)));
+            println!("{}", success_style.apply_to(tr!(TransactionRemoved, transaction_name, budget_name)));
+            self.emit(BudgetEvent::TransactionRemoved {
+                budget_name: budget_name.to_string(),
+                transaction_name: transaction_name.to_string(),
+            });
         } else {
-            println!("{}", warning_style.apply_to(format!("Aucune transaction correspondant à '{}' trouvée dans le budget '{}'", transaction_name, budget_name)));
+            println!("{}", warning_style.apply_to(tr!(TransactionNotFound, transaction_name, budget_name)));
         }
 
-        Ok(())
+        Ok(rows_affected > 0)
     }
 
 //==================================Function to edit transactions====================================
-    pub fn edit_transaction(&self, budget_name: &str, old_name: String, new_name: String, new_amount: f64) -> Result<()> {
+    // Retourne false si aucune transaction ne correspond
+    // `new_amount` : None garde le montant des transactions
+    pub fn edit_transaction(&self, budget_name: &str, old_name: String, new_name: String, new_amount: Option<f64>, overspend_confirmed: bool) -> Result<bool> {
         let error_style = Style::new().red();
         let success_style = Style::new().green();
         let warning_style = Style::new().yellow();
 
-        let budget_id = match self.get_budget_id(budget_name) {
-            Ok(id) => id,
-            Err(_) => {
-                println!("{}", error_style.apply_to(format!("Erreur : Le budget '{}' n'existe pas", budget_name)));
-                return Ok(());
+        let budget_id = match self.get_budget_id(budget_name)? {
+            Some(id) => id,
+            None => {
+                println!("{}", error_style.apply_to(tr!(Error, tr!(BudgetNotFound, budget_name))));
+                return Ok(false);
             }
         };
 
+        // Montant annoncé par l'événement : inchangé, celui des transactions modifiées
+        let amount = match new_amount {
+            Some(amount) => amount,
+            None => self.conn.query_row(
+                "SELECT COALESCE(MAX(amount), 0) FROM transactions WHERE budget_id = ?1 AND transaction_name = ?2",
+                params![budget_id, old_name],
+                |row| row.get(0),
+            )?,
+        };
+
+        let rows_affected = self.atomically(|| {
+            if let Some(new_amount) = new_amount {
+                self.enforce_overspend_policy(budget_name, self.edit_extra_spending(budget_name, &old_name, new_amount)?, overspend_confirmed)?;
+            }
+            self.conn.execute(
+                "UPDATE transactions SET transaction_name = ?1, amount = COALESCE(?2, amount) WHERE budget_id = ?3 AND transaction_name = ?4",
+                params![new_name, new_amount, budget_id, old_name],
+            )
+        })?;
+
+        if rows_affected > 0 {
+            println!("{}", success_style.apply_to(tr!(TransactionEdited)));
+            self.emit(BudgetEvent::TransactionEdited {
+                budget_name: budget_name.to_string(),
+                old_name,
+                new_name,
+                amount,
+            });
+            self.evaluate_alert_rules(budget_name, new_amount)?;
+        } else {
+            println!("{}", warning_style.apply_to(tr!(TransactionNotFound, old_name, budget_name)));
+        }
+        Ok(rows_affected > 0)
+    }
+    // Dépense supplémentaire causée par la modification des transactions `old_name`
+    pub fn edit_extra_spending(&self, budget_name: &str, old_name: &str, new_amount: f64) -> Result<f64> {
+        let (count, old_total): (i64, f64) = self.conn.query_row(
+            "SELECT COUNT(*), COALESCE(SUM(t.amount), 0)
+             FROM transactions t JOIN budgets b ON b.id = t.budget_id
+             WHERE b.budget_name = ?1 AND t.transaction_name = ?2",
+            params![budget_name, old_name],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        Ok(new_amount * count as f64 - old_total)
+    }
+
+//==================================Function to remove or edit one transaction====================================
+    // Variantes par identifiant, pour le menu et le tableau de bord : seule la
+    // transaction choisie est touchée, même si d'autres portent le même nom.
+    // Retournent false si la transaction n'existe pas dans ce budget.
+    pub fn remove_transaction_by_id(&self, budget_name: &str, transaction_id: i64) -> Result<bool> {
+        let success_style = Style::new().green();
+        let warning_style = Style::new().yellow();
+
+        let Some((budget_id, transaction_name, _)) = self.find_transaction(budget_name, transaction_id)? else {
+            println!("{}", warning_style.apply_to(tr!(TransactionNotFound, format!("#{}", transaction_id), budget_name)));
+            return Ok(false);
+        };
+
         let rows_affected = self.conn.execute(
-            "UPDATE transactions SET transaction_name = ?1, amount = ?2 WHERE budget_id = ?3 AND transaction_name = ?4",
-            params![new_name, new_amount, budget_id, old_name],
+            "DELETE FROM transactions WHERE id = ?1 AND budget_id = ?2",
+            params![transaction_id, budget_id],
         )?;
 
         if rows_affected > 0 {
-            println!("{}", success_style.apply_to("Transaction modifiée avec succès !"));
-        } else {
-            println!("{}", warning_style.apply_to(format!("Aucune transaction correspondant à '{}' trouvée dans le budget '{}'", old_name, budget_name)));
+            println!("{}", success_style.apply_to(tr!(TransactionRemoved, transaction_name, budget_name)));
+            self.emit(BudgetEvent::TransactionRemoved { budget_name: budget_name.to_string(), transaction_name });
         }
-        Ok(())
+        Ok(rows_affected > 0)
+    }
+
+    pub fn edit_transaction_by_id(&self, budget_name: &str, transaction_id: i64, new_name: String, new_amount: f64, overspend_confirmed: bool) -> Result<bool> {
+        let success_style = Style::new().green();
+        let warning_style = Style::new().yellow();
+
+        let Some((budget_id, old_name, old_amount)) = self.find_transaction(budget_name, transaction_id)? else {
+            println!("{}", warning_style.apply_to(tr!(TransactionNotFound, format!("#{}", transaction_id), budget_name)));
+            return Ok(false);
+        };
+
+        let rows_affected = self.atomically(|| {
+            self.enforce_overspend_policy(budget_name, new_amount - old_amount, overspend_confirmed)?;
+            self.conn.execute(
+                "UPDATE transactions SET transaction_name = ?1, amount = ?2 WHERE id = ?3 AND budget_id = ?4",
+                params![new_name, new_amount, transaction_id, budget_id],
+            )
+        })?;
+
+        if rows_affected > 0 {
+            println!("{}", success_style.apply_to(tr!(TransactionEdited)));
+            self.emit(BudgetEvent::TransactionEdited {
+                budget_name: budget_name.to_string(),
+                old_name,
+                new_name,
+                amount: new_amount,
+            });
+            self.evaluate_alert_rules(budget_name, Some(new_amount))?;
+        }
+        Ok(rows_affected > 0)
+    }
+
+    // Dépense supplémentaire causée par la modification de la transaction `transaction_id`
+    pub fn edit_extra_spending_by_id(&self, budget_name: &str, transaction_id: i64, new_amount: f64) -> Result<f64> {
+        Ok(self.find_transaction(budget_name, transaction_id)?.map_or(0.0, |(_, _, old_amount)| new_amount - old_amount))
+    }
+
+    // (id du budget, nom, montant) de la transaction `transaction_id` du budget
+    fn find_transaction(&self, budget_name: &str, transaction_id: i64) -> Result<Option<(i64, String, f64)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT b.id, t.transaction_name, t.amount
+             FROM transactions t JOIN budgets b ON b.id = t.budget_id
+             WHERE t.id = ?1 AND b.budget_name = ?2",
+        )?;
+        let mut rows = stmt.query_map(params![transaction_id, budget_name], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?;
+        rows.next().transpose()
     }
 //==================================Function to show remaining amount ====================================
-    pub fn show_remaining_amount(&self, budget_name: &str) -> Result<()> {
+    // Retourne false si le budget n'existe pas
+    pub fn show_remaining_amount(&self, budget_name: &str) -> Result<bool> {
         let success_style = Style::new().green();
         let warning_style = Style::new().red();
         let title_style = Style::new().blue().bold();
     
         // Log pour vérifier le nom du budget
-        println!("{}", format!("Recherche du budget : {} .....\n", budget_name).green());
+        println!("{}\n", tr!(SearchingBudget, budget_name).green());
 
     
         let budget = match self.conn.query_row(
@@ -279,25 +672,23 @@ pub fn calculate_remaining_amount(&self, budget_name: &str, total_amount: f64) -
             |row| Ok((row.get::<_, f64>(0)?, row.get::<_, f64>(1)?)),
         ) {
             Ok(budget) => {
-                println!("Budget trouvé : ID = {}, Montant total = {}", budget.0, budget.1);
+                println!("{}", tr!(BudgetFound, budget.0, i18n::amount(budget.1)));
                 budget
             },
             Err(_) => {
-                println!("{}", warning_style.apply_to(format!("Erreur : Le budget '{}' n'existe pas", budget_name)));
-                return Ok(()); 
+                println!("{}", warning_style.apply_to(tr!(Error, tr!(BudgetNotFound, budget_name))));
+                return Ok(false);
             }
         };
     
-        let total_spent: f64 = match self
+        let total_spent: f64 = self
             .conn
             .query_row(
                 "SELECT COALESCE(SUM(amount), 0) FROM transactions WHERE budget_id = ?1",
                 params![budget.0],
                 |row| row.get(0),
-            ) {
-                Ok(value) => value,
-                Err(_) => 0.0, // Default to 0 if an error occurs
-            };
+            )
+            .unwrap_or(0.0); // Default to 0 if an error occurs
     
         let remaining_amount = budget.1 - total_spent;
         let amount_style = if remaining_amount >= 0.0 {
@@ -308,11 +699,11 @@ pub fn calculate_remaining_amount(&self, budget_name: &str, total_amount: f64) -
     
         println!(
             "{} : {}",
-            title_style.apply_to(format!("Solde restant pour '{}' :", budget_name)),
-            amount_style.apply_to(remaining_amount)
+            title_style.apply_to(tr!(RemainingFor, budget_name)),
+            amount_style.apply_to(i18n::amount(remaining_amount))
         );
-       
-        Ok(())
+
+        Ok(true)
     }
 }
     
@@ -325,3 +716,105 @@ pub fn calculate_remaining_amount(&self, budget_name: &str, total_amount: f64) -
 
    
 
+// Ajoute une colonne à une table existante si elle n'y est pas encore
+fn ensure_column(conn: &Connection, table: &str, column: &str, definition: &str) -> Result<()> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    let exists = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<Result<Vec<_>>>()?
+        .iter()
+        .any(|name| name == column);
+    if !exists {
+        conn.execute(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition), [])?;
+    }
+    Ok(())
+}
+
+// Les tags sont stockés en minuscules, sans '#', séparés par des virgules
+pub fn normalize_tag(tag: &str) -> String {
+    tag.trim().trim_start_matches('#').to_lowercase()
+}
+
+fn join_tags(tags: &[String]) -> String {
+    tags.iter()
+        .map(|tag| normalize_tag(tag))
+        .filter(|tag| !tag.is_empty())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn split_tags(tags: &str) -> Vec<String> {
+    tags.split(',').filter(|tag| !tag.is_empty()).map(String::from).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    // Gestionnaire en mémoire et liste des événements reçus par un abonné
+    fn manager() -> (BudgetManager, Rc<RefCell<Vec<BudgetEvent>>>) {
+        let mut manager = BudgetManager::new(":memory:").unwrap();
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let received = Rc::clone(&events);
+        manager.subscribe(move |event: &BudgetEvent| received.borrow_mut().push(event.clone()));
+        (manager, events)
+    }
+
+    #[test]
+    fn events_wait_for_the_outermost_release() {
+        let (manager, events) = manager();
+        manager
+            .atomically(|| {
+                manager.add_budget("Courses", 100.0)?;
+                manager.atomically(|| manager.add_budget("Loisirs", 50.0))?;
+                assert!(events.borrow().is_empty());
+                Ok::<_, rusqlite::Error>(())
+            })
+            .unwrap();
+        assert_eq!(events.borrow().len(), 2);
+    }
+
+    #[test]
+    fn events_are_dropped_on_rollback() {
+        let (manager, events) = manager();
+        let result = manager.atomically(|| {
+            manager.add_budget("Courses", 100.0)?;
+            // Le savepoint interne est annulé, l'externe est enregistré
+            let _ = manager.atomically(|| {
+                manager.add_budget("Loisirs", 50.0)?;
+                Err::<(), _>(rusqlite::Error::InvalidParameterName("annulé".into()))
+            });
+            Ok::<_, rusqlite::Error>(())
+        });
+        assert!(result.is_ok());
+        assert_eq!(*events.borrow(), vec![BudgetEvent::BudgetCreated { budget_name: "Courses".into(), total_amount: 100.0 }]);
+
+        let result = manager.atomically(|| {
+            manager.add_budget("Loisirs", 50.0)?;
+            Err::<(), _>(rusqlite::Error::InvalidParameterName("annulé".into()))
+        });
+        assert!(result.is_err());
+        assert_eq!(events.borrow().len(), 1);
+        assert!(manager.get_budget_id("Loisirs").unwrap().is_none());
+    }
+
+    #[test]
+    fn removing_a_budget_keeps_rules_with_other_actions() {
+        let (manager, _) = manager();
+        manager.add_budget("Courses", 100.0).unwrap();
+        let rule = |category: &str| AssignmentRule {
+            contains: "carrefour".into(),
+            budget_name: "Courses".into(),
+            category: category.into(),
+            ..Default::default()
+        };
+        manager.add_assignment_rule(&rule("")).unwrap();
+        let kept = manager.add_assignment_rule(&rule("Alimentation")).unwrap();
+
+        assert!(manager.remove_budget("Courses").unwrap());
+        let rules = manager.list_assignment_rules().unwrap();
+        assert_eq!(rules.len(), 1);
+        assert_eq!((rules[0].id, rules[0].budget_name.as_str(), rules[0].category.as_str()), (kept, "", "Alimentation"));
+    }
+}
@@ -0,0 +1,59 @@
+use rusqlite::{params, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use crate::i18n::tr;
+use super::BudgetManager;
+
+// Compte bancaire (identifiant du relevé, ex : ACCTID d'un fichier OFX) et le
+// budget qui reçoit ses transactions importées
+#[derive(Debug, Clone, Serialize)]
+pub struct AccountBudget {
+    pub account: String,
+    pub budget_name: String,
+}
+
+impl BudgetManager {
+    pub(super) fn create_account_budgets_table(&self) -> Result<()> {
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS account_budgets (
+                account TEXT PRIMARY KEY,
+                budget_id INTEGER NOT NULL,
+                FOREIGN KEY(budget_id) REFERENCES budgets(id)
+            )",
+            [],
+        )?;
+        Ok(())
+    }
+
+//==================================Function to map an account to a budget====================================
+    pub fn set_account_budget(&self, account: &str, budget_name: &str) -> Result<()> {
+        let budget_id = self
+            .get_budget_id(budget_name)?
+            .ok_or_else(|| rusqlite::Error::InvalidParameterName(tr!(BudgetNotFound, budget_name)))?;
+        self.conn.execute(
+            "INSERT INTO account_budgets (account, budget_id) VALUES (?1, ?2)
+             ON CONFLICT(account) DO UPDATE SET budget_id = excluded.budget_id",
+            params![account.trim(), budget_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn remove_account_budget(&self, account: &str) -> Result<bool> {
+        let rows_affected = self.conn.execute("DELETE FROM account_budgets WHERE account = ?1", params![account])?;
+        Ok(rows_affected > 0)
+    }
+
+//==================================Function to list account mappings====================================
+    pub fn list_account_budgets(&self) -> Result<Vec<AccountBudget>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT a.account, b.budget_name FROM account_budgets a JOIN budgets b ON b.id = a.budget_id ORDER BY a.account",
+        )?;
+        let mappings = stmt.query_map([], |row| Ok(AccountBudget { account: row.get(0)?, budget_name: row.get(1)? }))?;
+        mappings.collect()
+    }
+
+    // Compte => nom du budget, pour l'import
+    pub fn account_budgets(&self) -> Result<HashMap<String, String>> {
+        Ok(self.list_account_budgets()?.into_iter().map(|mapping| (mapping.account, mapping.budget_name)).collect())
+    }
+}
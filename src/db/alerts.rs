@@ -0,0 +1,336 @@
+use rusqlite::{params, Result};
+use serde::Serialize;
+use std::collections::HashSet;
+use crate::config;
+use crate::events::BudgetEvent;
+use crate::i18n::{self, tr};
+use super::BudgetManager;
+
+// Types de règles d'alerte disponibles pour un budget
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertRuleKind {
+    // Pourcentage du budget déjà dépensé (ex : 90 => alerte à 90 % consommé)
+    PercentUsed,
+    // Montant restant inférieur à une valeur absolue
+    RemainingBelow,
+    // Dépense projetée en fin de mois supérieure à X % du budget
+    ProjectedOverspend,
+    // Une seule transaction dépasse un montant donné
+    LargeTransaction,
+}
+
+impl AlertRuleKind {
+    pub const ALL: [AlertRuleKind; 4] = [
+        AlertRuleKind::PercentUsed,
+        AlertRuleKind::RemainingBelow,
+        AlertRuleKind::ProjectedOverspend,
+        AlertRuleKind::LargeTransaction,
+    ];
+
+    // Valeur stockée dans la colonne `kind` de la table alert_rules
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlertRuleKind::PercentUsed => "percent_used",
+            AlertRuleKind::RemainingBelow => "remaining_below",
+            AlertRuleKind::ProjectedOverspend => "projected_overspend",
+            AlertRuleKind::LargeTransaction => "large_transaction",
+        }
+    }
+
+    pub fn parse(kind: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|k| k.as_str() == kind)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AlertRuleKind::PercentUsed => tr!(AlertKindPercentUsed),
+            AlertRuleKind::RemainingBelow => tr!(AlertKindRemainingBelow),
+            AlertRuleKind::ProjectedOverspend => tr!(AlertKindProjectedOverspend),
+            AlertRuleKind::LargeTransaction => tr!(AlertKindLargeTransaction),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AlertRule {
+    pub id: i32,
+    pub kind: AlertRuleKind,
+    pub threshold: f64,
+}
+
+// (montant total, montant restant, règles déclenchées et leur message)
+type TriggeredAlerts = (f64, f64, Vec<(AlertRule, String)>);
+
+impl BudgetManager {
+    pub(super) fn create_alert_rules_table(&self) -> Result<()> {
+        let exists: bool = self.conn.query_row(
+            "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type = 'table' AND name = 'alert_rules'",
+            [],
+            |row| row.get(0),
+        )?;
+
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS alert_rules (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                budget_id INTEGER NOT NULL,
+                kind TEXT NOT NULL,
+                threshold REAL NOT NULL,
+                fired INTEGER NOT NULL DEFAULT 0,
+                FOREIGN KEY(budget_id) REFERENCES budgets(id)
+            )",
+            [],
+        )?;
+        // Règle déclenchée lors de la dernière évaluation (bases créées avant la colonne)
+        super::ensure_column(&self.conn, "alert_rules", "fired", "INTEGER NOT NULL DEFAULT 0")?;
+
+        // Première création : les budgets existants reçoivent la règle par défaut
+        if !exists {
+            self.conn.execute(
+                "INSERT INTO alert_rules (budget_id, kind, threshold) SELECT id, ?1, ?2 FROM budgets",
+                params![AlertRuleKind::PercentUsed.as_str(), config::get().budgets.default_alert_percent],
+            )?;
+        }
+        Ok(())
+    }
+
+//==================================Function to list alert rules====================================
+    pub fn list_alert_rules(&self, budget_name: &str) -> Result<Vec<AlertRule>> {
+        let budget_id = self.require_budget_id(budget_name)?;
+        let mut stmt = self.conn.prepare(
+            "SELECT id, kind, threshold FROM alert_rules WHERE budget_id = ?1 ORDER BY id",
+        )?;
+        let rules = stmt.query_map(params![budget_id], |row| {
+            Ok((row.get::<_, i32>(0)?, row.get::<_, String>(1)?, row.get::<_, f64>(2)?))
+        })?;
+
+        let mut result = Vec::new();
+        for rule in rules {
+            let (id, kind, threshold) = rule?;
+            // Les types inconnus (base modifiée à la main) sont ignorés
+            if let Some(kind) = AlertRuleKind::parse(&kind) {
+                result.push(AlertRule { id, kind, threshold });
+            }
+        }
+        Ok(result)
+    }
+
+//==================================Function to add alert rules====================================
+    pub fn add_alert_rule(&self, budget_name: &str, kind: AlertRuleKind, threshold: f64) -> Result<()> {
+        let budget_id = self.require_budget_id(budget_name)?;
+        validate_threshold(kind, threshold)?;
+        self.conn.execute(
+            "INSERT INTO alert_rules (budget_id, kind, threshold) VALUES (?1, ?2, ?3)",
+            params![budget_id, kind.as_str(), threshold],
+        )?;
+        Ok(())
+    }
+
+//==================================Function to edit alert rules====================================
+    pub fn edit_alert_rule(&self, rule_id: i32, kind: AlertRuleKind, threshold: f64) -> Result<bool> {
+        validate_threshold(kind, threshold)?;
+        let rows_affected = self.conn.execute(
+            "UPDATE alert_rules SET kind = ?1, threshold = ?2, fired = 0 WHERE id = ?3",
+            params![kind.as_str(), threshold, rule_id],
+        )?;
+        Ok(rows_affected > 0)
+    }
+
+//==================================Function to remove alert rules====================================
+    pub fn remove_alert_rule(&self, rule_id: i32) -> Result<bool> {
+        let rows_affected = self.conn.execute("DELETE FROM alert_rules WHERE id = ?1", params![rule_id])?;
+        Ok(rows_affected > 0)
+    }
+
+//==================================Evaluate alert rules====================================
+    // Évalue toutes les règles du budget après une transaction et émet un
+    // événement `ThresholdCrossed` pour chaque règle qui vient de se déclencher :
+    // une règle qui l'était déjà à l'évaluation précédente reste silencieuse
+    // jusqu'à ce qu'elle cesse de l'être. Les règles "transaction unique"
+    // signalent chaque transaction trop grosse.
+    pub fn evaluate_alert_rules(&self, budget_name: &str, last_amount: Option<f64>) -> Result<()> {
+        let budget_id = self.require_budget_id(budget_name)?;
+        let (total_amount, remaining_amount, triggered) = self.triggered_alerts(budget_name, last_amount)?;
+        let already_fired = self.fired_alert_rules(budget_id)?;
+        self.conn.execute("UPDATE alert_rules SET fired = 0 WHERE budget_id = ?1", params![budget_id])?;
+
+        for (rule, message) in triggered {
+            if rule.kind != AlertRuleKind::LargeTransaction {
+                self.conn.execute("UPDATE alert_rules SET fired = 1 WHERE id = ?1", params![rule.id])?;
+                if already_fired.contains(&rule.id) {
+                    continue;
+                }
+            }
+            self.emit(BudgetEvent::ThresholdCrossed {
+                budget_name: budget_name.to_string(),
+                total_amount,
+                remaining_amount,
+                message,
+            });
+        }
+        Ok(())
+    }
+
+    fn fired_alert_rules(&self, budget_id: i32) -> Result<HashSet<i32>> {
+        let mut stmt = self.conn.prepare("SELECT id FROM alert_rules WHERE budget_id = ?1 AND fired = 1")?;
+        let ids = stmt.query_map(params![budget_id], |row| row.get(0))?;
+        ids.collect()
+    }
+
+    // Messages des règles actuellement déclenchées, sans émettre d'événement
+    // (les règles "transaction unique" ne s'appliquent qu'après une transaction)
+    pub fn current_alerts(&self, budget_name: &str) -> Result<Vec<String>> {
+        Ok(self.triggered_alerts(budget_name, None)?.2.into_iter().map(|(_, message)| message).collect())
+    }
+
+    fn triggered_alerts(&self, budget_name: &str, last_amount: Option<f64>) -> Result<TriggeredAlerts> {
+        let (budget_id, total_amount): (i32, f64) = self.conn.query_row(
+            "SELECT id, total_amount FROM budgets WHERE budget_name = ?1",
+            params![budget_name],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        let remaining_amount = self.calculate_remaining_amount(budget_name, total_amount)?;
+        let spent = total_amount - remaining_amount;
+        let mut triggered = Vec::new();
+
+        for rule in self.list_alert_rules(budget_name)? {
+            let message = match rule.kind {
+                AlertRuleKind::PercentUsed => {
+                    if total_amount > 0.0 && spent / total_amount * 100.0 >= rule.threshold {
+                        Some(tr!(
+                            AlertPercentUsed,
+                            i18n::number(spent / total_amount * 100.0, 0), budget_name,
+                            i18n::number(rule.threshold, 0), i18n::amount(remaining_amount)
+                        ))
+                    } else {
+                        None
+                    }
+                }
+                AlertRuleKind::RemainingBelow => {
+                    if remaining_amount < rule.threshold {
+                        Some(tr!(
+                            AlertRemainingBelow,
+                            budget_name, i18n::amount(remaining_amount), i18n::amount(rule.threshold)
+                        ))
+                    } else {
+                        None
+                    }
+                }
+                AlertRuleKind::ProjectedOverspend => {
+                    let projected = self.projected_month_spending(budget_id)?;
+                    if projected > total_amount * rule.threshold / 100.0 {
+                        Some(tr!(
+                            AlertProjectedOverspend,
+                            budget_name, i18n::amount(projected), i18n::amount(total_amount)
+                        ))
+                    } else {
+                        None
+                    }
+                }
+                AlertRuleKind::LargeTransaction => match last_amount {
+                    Some(amount) if amount > rule.threshold => Some(tr!(
+                        AlertLargeTransaction,
+                        i18n::amount(amount), budget_name, i18n::amount(rule.threshold)
+                    )),
+                    _ => None,
+                },
+            };
+
+            if let Some(message) = message {
+                triggered.push((rule, message));
+            }
+        }
+        Ok((total_amount, remaining_amount, triggered))
+    }
+
+    // Extrapole les dépenses du mois en cours jusqu'à la fin du mois
+    fn projected_month_spending(&self, budget_id: i32) -> Result<f64> {
+        self.conn.query_row(
+            "SELECT COALESCE(SUM(amount), 0)
+                    * CAST(strftime('%d', date('now', 'start of month', '+1 month', '-1 day')) AS REAL)
+                    / CAST(strftime('%d', 'now') AS REAL)
+             FROM transactions
+             WHERE budget_id = ?1 AND transaction_date >= date('now', 'start of month')",
+            params![budget_id],
+            |row| row.get(0),
+        )
+    }
+}
+
+fn validate_threshold(kind: AlertRuleKind, threshold: f64) -> Result<()> {
+    let valid = match kind {
+        AlertRuleKind::PercentUsed | AlertRuleKind::ProjectedOverspend => threshold > 0.0,
+        AlertRuleKind::RemainingBelow | AlertRuleKind::LargeTransaction => threshold >= 0.0,
+    };
+    if !valid || !threshold.is_finite() {
+        return Err(rusqlite::Error::InvalidParameterName(tr!(InvalidThreshold).into()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use crate::db::NewTransaction;
+
+    // Budget "Courses" de 100 avec la règle par défaut (90 % dépensés) ;
+    // retourne aussi les messages d'alerte émis
+    fn courses() -> (BudgetManager, Rc<RefCell<Vec<String>>>) {
+        let mut manager = BudgetManager::new(":memory:").unwrap();
+        manager.add_budget("Courses", 100.0).unwrap();
+        let alerts = Rc::new(RefCell::new(Vec::new()));
+        let received = Rc::clone(&alerts);
+        manager.subscribe(move |event: &BudgetEvent| {
+            if let BudgetEvent::ThresholdCrossed { message, .. } = event {
+                received.borrow_mut().push(message.clone());
+            }
+        });
+        (manager, alerts)
+    }
+
+    fn spend(manager: &BudgetManager, name: &str, amount: f64) {
+        manager.add_transaction("Courses", NewTransaction::new(name.to_string(), amount), false).unwrap();
+    }
+
+    #[test]
+    fn alerts_fire_once_when_the_threshold_is_crossed() {
+        let (manager, alerts) = courses();
+        spend(&manager, "Marché", 50.0);
+        assert!(alerts.borrow().is_empty());
+        spend(&manager, "Boucherie", 45.0);
+        assert_eq!(alerts.borrow().len(), 1);
+        spend(&manager, "Pain", 1.0);
+        assert_eq!(alerts.borrow().len(), 1);
+        assert_eq!(manager.current_alerts("Courses").unwrap().len(), 1);
+
+        // Repassée sous le seuil, la règle se déclenche de nouveau au prochain franchissement
+        let id: i64 = manager
+            .conn
+            .query_row("SELECT id FROM transactions WHERE transaction_name = 'Boucherie'", [], |row| row.get(0))
+            .unwrap();
+        manager.edit_transaction_by_id("Courses", id, "Boucherie".to_string(), 5.0, false).unwrap();
+        assert_eq!(alerts.borrow().len(), 1);
+        spend(&manager, "Traiteur", 40.0);
+        assert_eq!(alerts.borrow().len(), 2);
+    }
+
+    #[test]
+    fn each_large_transaction_is_reported() {
+        let (manager, alerts) = courses();
+        manager.add_alert_rule("Courses", AlertRuleKind::LargeTransaction, 20.0).unwrap();
+        spend(&manager, "Marché", 30.0);
+        spend(&manager, "Boucherie", 25.0);
+        spend(&manager, "Pain", 2.0);
+        assert_eq!(alerts.borrow().len(), 2);
+
+        // Un seuil modifié repart de zéro
+        let rule = manager.list_alert_rules("Courses").unwrap()[0].clone();
+        spend(&manager, "Traiteur", 35.0);
+        assert_eq!(alerts.borrow().len(), 4);
+        manager.edit_alert_rule(rule.id, AlertRuleKind::PercentUsed, 80.0).unwrap();
+        spend(&manager, "Fromager", 1.0);
+        assert_eq!(alerts.borrow().len(), 5);
+    }
+}
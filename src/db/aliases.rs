@@ -0,0 +1,59 @@
+use rusqlite::{params, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use crate::i18n::tr;
+use super::BudgetManager;
+
+// Nom de budget d'un fichier importé qui n'existe pas dans la base (ex :
+// "Course" dans un CSV) et le budget existant qui reçoit ses transactions
+#[derive(Debug, Clone, Serialize)]
+pub struct BudgetAlias {
+    pub alias: String,
+    pub budget_name: String,
+}
+
+impl BudgetManager {
+    pub(super) fn create_budget_aliases_table(&self) -> Result<()> {
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS budget_aliases (
+                alias TEXT PRIMARY KEY,
+                budget_id INTEGER NOT NULL,
+                FOREIGN KEY(budget_id) REFERENCES budgets(id)
+            )",
+            [],
+        )?;
+        Ok(())
+    }
+
+//==================================Function to map a budget alias====================================
+    pub fn set_budget_alias(&self, alias: &str, budget_name: &str) -> Result<()> {
+        let budget_id = self
+            .get_budget_id(budget_name)?
+            .ok_or_else(|| rusqlite::Error::InvalidParameterName(tr!(BudgetNotFound, budget_name)))?;
+        self.conn.execute(
+            "INSERT INTO budget_aliases (alias, budget_id) VALUES (?1, ?2)
+             ON CONFLICT(alias) DO UPDATE SET budget_id = excluded.budget_id",
+            params![alias.trim(), budget_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn remove_budget_alias(&self, alias: &str) -> Result<bool> {
+        let rows_affected = self.conn.execute("DELETE FROM budget_aliases WHERE alias = ?1", params![alias])?;
+        Ok(rows_affected > 0)
+    }
+
+//==================================Function to list budget aliases====================================
+    pub fn list_budget_aliases(&self) -> Result<Vec<BudgetAlias>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT a.alias, b.budget_name FROM budget_aliases a JOIN budgets b ON b.id = a.budget_id ORDER BY a.alias",
+        )?;
+        let aliases = stmt.query_map([], |row| Ok(BudgetAlias { alias: row.get(0)?, budget_name: row.get(1)? }))?;
+        aliases.collect()
+    }
+
+    // Alias => nom du budget, pour l'import
+    pub fn budget_aliases(&self) -> Result<HashMap<String, String>> {
+        Ok(self.list_budget_aliases()?.into_iter().map(|alias| (alias.alias, alias.budget_name)).collect())
+    }
+}
@@ -0,0 +1,59 @@
+use rusqlite::{params, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use crate::i18n::tr;
+use super::BudgetManager;
+
+// Catégorie d'un fichier importé (ex : "Alimentation:Courses" dans un QIF) et le
+// budget qui reçoit ses transactions
+#[derive(Debug, Clone, Serialize)]
+pub struct CategoryBudget {
+    pub category: String,
+    pub budget_name: String,
+}
+
+impl BudgetManager {
+    pub(super) fn create_category_budgets_table(&self) -> Result<()> {
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS category_budgets (
+                category TEXT PRIMARY KEY,
+                budget_id INTEGER NOT NULL,
+                FOREIGN KEY(budget_id) REFERENCES budgets(id)
+            )",
+            [],
+        )?;
+        Ok(())
+    }
+
+//==================================Function to map a category to a budget====================================
+    pub fn set_category_budget(&self, category: &str, budget_name: &str) -> Result<()> {
+        let budget_id = self
+            .get_budget_id(budget_name)?
+            .ok_or_else(|| rusqlite::Error::InvalidParameterName(tr!(BudgetNotFound, budget_name)))?;
+        self.conn.execute(
+            "INSERT INTO category_budgets (category, budget_id) VALUES (?1, ?2)
+             ON CONFLICT(category) DO UPDATE SET budget_id = excluded.budget_id",
+            params![category.trim(), budget_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn remove_category_budget(&self, category: &str) -> Result<bool> {
+        let rows_affected = self.conn.execute("DELETE FROM category_budgets WHERE category = ?1", params![category])?;
+        Ok(rows_affected > 0)
+    }
+
+//==================================Function to list category mappings====================================
+    pub fn list_category_budgets(&self) -> Result<Vec<CategoryBudget>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT c.category, b.budget_name FROM category_budgets c JOIN budgets b ON b.id = c.budget_id ORDER BY c.category",
+        )?;
+        let mappings = stmt.query_map([], |row| Ok(CategoryBudget { category: row.get(0)?, budget_name: row.get(1)? }))?;
+        mappings.collect()
+    }
+
+    // Catégorie => nom du budget, pour l'import
+    pub fn category_budgets(&self) -> Result<HashMap<String, String>> {
+        Ok(self.list_category_budgets()?.into_iter().map(|mapping| (mapping.category, mapping.budget_name)).collect())
+    }
+}
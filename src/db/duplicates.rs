@@ -0,0 +1,105 @@
+use chrono::Utc;
+use rusqlite::Result;
+use std::collections::HashSet;
+use crate::parse;
+use super::{BudgetManager, NewTransaction};
+
+// Empreinte d'une transaction pour reconnaître une ligne déjà importée :
+// l'identifiant de la banque s'il existe, sinon date, montant, libellé
+// normalisé et compte d'origine. Le budget n'en fait pas partie : une ligne
+// réimportée peut avoir été classée ailleurs.
+fn fingerprint(date: &str, amount: f64, description: &str, account: &str, external_id: &str) -> String {
+    let account = parse::fold(account.trim());
+    if external_id.is_empty() {
+        format!("{}|{:.2}|{}|{}", date, amount, normalize_description(description), account)
+    } else {
+        format!("id|{}|{}", account, external_id.trim())
+    }
+}
+
+// "CB  Café-Élysée 15/03" et "cb cafe elysee 15 03" donnent le même libellé
+pub(super) fn normalize_description(description: &str) -> String {
+    parse::fold(description)
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+impl NewTransaction {
+    // Sans date, la transaction sera datée du jour (`date('now')`, en UTC)
+    pub fn fingerprint(&self) -> String {
+        let today = Utc::now().date_naive().to_string();
+        let date = self.transaction_date.as_deref().unwrap_or(&today);
+        fingerprint(date, self.amount, &self.transaction_name, &self.account, &self.external_id)
+    }
+}
+
+impl BudgetManager {
+//==================================Function to list transaction fingerprints====================================
+    // Les transactions importées en attente de classement comptent aussi
+    pub fn transaction_fingerprints(&self) -> Result<HashSet<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT COALESCE(transaction_date, ''), amount, transaction_name, account, external_id FROM transactions
+             UNION ALL
+             SELECT COALESCE(transaction_date, ''), amount, transaction_name, account, external_id FROM unassigned_transactions",
+        )?;
+        let fingerprints = stmt.query_map([], |row| {
+            let text = |index: usize| row.get::<_, String>(index);
+            Ok(fingerprint(&text(0)?, row.get(1)?, &text(2)?, &text(3)?, &text(4)?))
+        })?;
+        fingerprints.collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn descriptions_ignore_case_accents_and_punctuation() {
+        assert_eq!(normalize_description("CB  Café-Élysée 15/03"), "cb cafe elysee 15 03");
+        assert_eq!(fingerprint("2026-03-15", 3.5, "Café", " Livret ", ""), fingerprint("2026-03-15", 3.499, "CAFE", "livret", ""));
+        assert_ne!(fingerprint("2026-03-15", 3.5, "Café", "", ""), fingerprint("2026-03-15", 3.5, "Café", "Livret", ""));
+        assert_ne!(fingerprint("2026-03-15", 3.5, "Café", "", ""), fingerprint("2026-03-16", 3.5, "Café", "", ""));
+    }
+
+    #[test]
+    fn bank_identifiers_replace_the_other_fields() {
+        assert_eq!(fingerprint("2026-03-15", 3.5, "Café", "Livret", "T42"), fingerprint("2026-03-16", 9.0, "Pain", "livret", "T42"));
+        assert_ne!(fingerprint("2026-03-15", 3.5, "Café", "Livret", "T42"), fingerprint("2026-03-15", 3.5, "Café", "Livret", "T43"));
+        assert_ne!(fingerprint("2026-03-15", 3.5, "Café", "Livret", "T42"), fingerprint("2026-03-15", 3.5, "Café", "Courant", "T42"));
+    }
+
+    #[test]
+    fn recorded_transactions_have_the_fingerprint_of_their_line() {
+        let manager = BudgetManager::new(":memory:").unwrap();
+        manager.add_budget("Courses", 100.0).unwrap();
+        let transaction = NewTransaction {
+            transaction_date: Some("2026-03-15".to_string()),
+            account: "Livret".to_string(),
+            ..NewTransaction::new("Café".to_string(), 3.5)
+        };
+        manager.add_transaction("Courses", transaction.clone(), false).unwrap();
+        // Sans date : celle du jour
+        let undated = NewTransaction::new("Pain".to_string(), 1.2);
+        manager.add_transaction("Courses", undated.clone(), false).unwrap();
+
+        let fingerprints = manager.transaction_fingerprints().unwrap();
+        assert_eq!(fingerprints, HashSet::from([transaction.fingerprint(), undated.fingerprint()]));
+    }
+
+    #[test]
+    fn removed_budgets_leave_no_fingerprint() {
+        let manager = BudgetManager::new(":memory:").unwrap();
+        manager.add_budget("Courses", 100.0).unwrap();
+        manager.add_budget("Loisirs", 100.0).unwrap();
+        let cafe = NewTransaction::new("Café".to_string(), 3.5);
+        manager.add_transaction("Courses", cafe.clone(), false).unwrap();
+        manager.add_transaction("Loisirs", NewTransaction::new("Cinéma".to_string(), 9.0), false).unwrap();
+
+        assert!(manager.remove_budget("Courses").unwrap());
+        assert!(!manager.transaction_fingerprints().unwrap().contains(&cafe.fingerprint()));
+        assert_eq!(manager.transaction_fingerprints().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,131 @@
+use rusqlite::{params, Result};
+use serde::Serialize;
+use super::{join_tags, split_tags, BudgetManager, NewTransaction};
+
+// Transaction importée dont le budget n'existe pas, mise de côté (politique
+// d'import "inbox") en attente d'être rangée dans un budget
+#[derive(Debug, Clone, Serialize)]
+pub struct UnassignedTransaction {
+    pub id: i64,
+    // Budget indiqué par le fichier importé, vide si la ligne n'en a pas
+    pub budget_name: String,
+    pub transaction_name: String,
+    pub amount: f64,
+    pub transaction_date: Option<String>,
+    pub payee: String,
+    pub notes: String,
+    pub tags: Vec<String>,
+    pub category: String,
+    pub account: String,
+    #[serde(skip)]
+    pub external_id: String,
+}
+
+impl UnassignedTransaction {
+    const COLUMNS: &'static str =
+        "id, budget_name, transaction_name, amount, transaction_date, payee, notes, tags, category, account, external_id";
+
+    fn from_row(row: &rusqlite::Row) -> Result<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            budget_name: row.get(1)?,
+            transaction_name: row.get(2)?,
+            amount: row.get(3)?,
+            transaction_date: row.get(4)?,
+            payee: row.get(5)?,
+            notes: row.get(6)?,
+            tags: split_tags(&row.get::<_, String>(7)?),
+            category: row.get(8)?,
+            account: row.get(9)?,
+            external_id: row.get(10)?,
+        })
+    }
+
+    pub fn transaction(&self) -> NewTransaction {
+        NewTransaction {
+            payee: self.payee.clone(),
+            notes: self.notes.clone(),
+            tags: self.tags.clone(),
+            transaction_date: self.transaction_date.clone(),
+            account: self.account.clone(),
+            external_id: self.external_id.clone(),
+            category: self.category.clone(),
+            ..NewTransaction::new(self.transaction_name.clone(), self.amount)
+        }
+    }
+}
+
+impl BudgetManager {
+    pub(super) fn create_unassigned_table(&self) -> Result<()> {
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS unassigned_transactions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                budget_name TEXT NOT NULL,
+                transaction_name TEXT NOT NULL,
+                amount REAL NOT NULL,
+                transaction_date TEXT,
+                payee TEXT NOT NULL DEFAULT '',
+                notes TEXT NOT NULL DEFAULT '',
+                tags TEXT NOT NULL DEFAULT '',
+                category TEXT NOT NULL DEFAULT '',
+                account TEXT NOT NULL DEFAULT '',
+                external_id TEXT NOT NULL DEFAULT ''
+            )",
+            [],
+        )?;
+        Ok(())
+    }
+
+//==================================Function to park an imported transaction====================================
+    pub fn park_transaction(&self, budget_name: &str, transaction: &NewTransaction) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO unassigned_transactions
+                (budget_name, transaction_name, amount, transaction_date, payee, notes, tags, category, account, external_id)
+             VALUES (?1, ?2, ?3, COALESCE(?4, date('now')), ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                budget_name.trim(), transaction.transaction_name, transaction.amount, transaction.transaction_date,
+                transaction.payee, transaction.notes, join_tags(&transaction.tags), transaction.category,
+                transaction.account, transaction.external_id
+            ],
+        )?;
+        Ok(())
+    }
+
+//==================================Function to list transactions to triage====================================
+    pub fn list_unassigned(&self) -> Result<Vec<UnassignedTransaction>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM unassigned_transactions ORDER BY budget_name, transaction_date, id",
+            UnassignedTransaction::COLUMNS
+        ))?;
+        let transactions = stmt.query_map([], UnassignedTransaction::from_row)?;
+        transactions.collect()
+    }
+
+    pub fn count_unassigned(&self) -> Result<usize> {
+        self.conn.query_row("SELECT COUNT(*) FROM unassigned_transactions", [], |row| row.get(0))
+    }
+
+    fn get_unassigned(&self, id: i64) -> Result<Option<UnassignedTransaction>> {
+        let mut stmt =
+            self.conn.prepare(&format!("SELECT {} FROM unassigned_transactions WHERE id = ?1", UnassignedTransaction::COLUMNS))?;
+        let mut transactions = stmt.query_map(params![id], UnassignedTransaction::from_row)?;
+        transactions.next().transpose()
+    }
+
+//==================================Function to assign a transaction to triage====================================
+    // La transaction passe dans le budget (politique de dépassement comprise) et
+    // quitte la liste. Retourne false si elle n'existe pas.
+    pub fn assign_unassigned(&self, id: i64, budget_name: &str, overspend_confirmed: bool) -> Result<bool> {
+        let Some(unassigned) = self.get_unassigned(id)? else { return Ok(false) };
+        self.atomically(|| {
+            self.add_transaction(budget_name, unassigned.transaction(), overspend_confirmed)?;
+            self.conn.execute("DELETE FROM unassigned_transactions WHERE id = ?1", params![id])
+        })?;
+        Ok(true)
+    }
+
+    pub fn remove_unassigned(&self, id: i64) -> Result<bool> {
+        let rows_affected = self.conn.execute("DELETE FROM unassigned_transactions WHERE id = ?1", params![id])?;
+        Ok(rows_affected > 0)
+    }
+}
@@ -0,0 +1,187 @@
+use rusqlite::{params, Result};
+use serde::Serialize;
+use crate::i18n::{self, tr};
+use super::BudgetManager;
+
+// Comportement d'un budget lorsqu'une transaction le rend négatif
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OverspendPolicy {
+    // Le dépassement est enregistré sans rien demander
+    Allow,
+    // Le dépassement doit être confirmé par l'utilisateur
+    Warn,
+    // Le dépassement est refusé
+    Block,
+}
+
+impl OverspendPolicy {
+    pub const ALL: [OverspendPolicy; 3] = [OverspendPolicy::Allow, OverspendPolicy::Warn, OverspendPolicy::Block];
+
+    // Valeur stockée dans la colonne `overspend_policy` de la table budgets
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OverspendPolicy::Allow => "allow",
+            OverspendPolicy::Warn => "warn",
+            OverspendPolicy::Block => "block",
+        }
+    }
+
+    pub fn parse(policy: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|p| p.as_str() == policy)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            OverspendPolicy::Allow => tr!(PolicyAllow),
+            OverspendPolicy::Warn => tr!(PolicyWarn),
+            OverspendPolicy::Block => tr!(PolicyBlock),
+        }
+    }
+}
+
+// Résultat de la vérification d'une dépense par rapport à la politique du budget
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OverspendCheck {
+    Allowed,
+    NeedsConfirmation { remaining_after: f64 },
+    Blocked { remaining_after: f64 },
+}
+
+impl BudgetManager {
+//==================================Function to set the overspend policy====================================
+    pub fn set_overspend_policy(&self, budget_name: &str, policy: OverspendPolicy) -> Result<()> {
+        let rows_affected = self.conn.execute(
+            "UPDATE budgets SET overspend_policy = ?1 WHERE budget_name = ?2",
+            params![policy.as_str(), budget_name],
+        )?;
+        if rows_affected == 0 {
+            return Err(rusqlite::Error::InvalidParameterName(tr!(BudgetNotFound, budget_name)));
+        }
+        Ok(())
+    }
+
+    pub fn overspend_policy(&self, budget_name: &str) -> Result<OverspendPolicy> {
+        let policy: String = self.conn.query_row(
+            "SELECT overspend_policy FROM budgets WHERE budget_name = ?1",
+            params![budget_name],
+            |row| row.get(0),
+        )?;
+        Ok(OverspendPolicy::parse(&policy).unwrap_or(OverspendPolicy::Allow))
+    }
+
+//==================================Check a spending against the policy====================================
+    // `extra_spending` est la dépense supplémentaire (montant d'une nouvelle
+    // transaction, ou différence entre nouveau et ancien montant lors d'une modification)
+    pub fn check_overspend(&self, budget_name: &str, extra_spending: f64) -> Result<OverspendCheck> {
+        let budget_id = self.require_budget_id(budget_name)?;
+        let (total_amount, total_spent): (f64, f64) = self.conn.query_row(
+            "SELECT b.total_amount, COALESCE(SUM(t.amount), 0)
+             FROM budgets b LEFT JOIN transactions t ON t.budget_id = b.id
+             WHERE b.id = ?1",
+            params![budget_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        let remaining_after = total_amount - total_spent - extra_spending;
+
+        // Seules les dépenses qui rendent le budget négatif sont concernées
+        if extra_spending <= 0.0 || remaining_after >= 0.0 {
+            return Ok(OverspendCheck::Allowed);
+        }
+        Ok(match self.overspend_policy(budget_name)? {
+            OverspendPolicy::Allow => OverspendCheck::Allowed,
+            OverspendPolicy::Warn => OverspendCheck::NeedsConfirmation { remaining_after },
+            OverspendPolicy::Block => OverspendCheck::Blocked { remaining_after },
+        })
+    }
+
+    // Vérification faite dans le chemin d'écriture (à appeler dans `atomically`)
+    pub(super) fn enforce_overspend_policy(&self, budget_name: &str, extra_spending: f64, confirmed: bool) -> Result<()> {
+        match self.check_overspend(budget_name, extra_spending)? {
+            OverspendCheck::Allowed => Ok(()),
+            OverspendCheck::NeedsConfirmation { .. } if confirmed => Ok(()),
+            OverspendCheck::NeedsConfirmation { remaining_after } => Err(rusqlite::Error::InvalidParameterName(
+                tr!(OverspendNeedsConfirmation, budget_name, i18n::amount(remaining_after)),
+            )),
+            OverspendCheck::Blocked { remaining_after } => Err(rusqlite::Error::InvalidParameterName(
+                tr!(OverspendRefused, budget_name, i18n::amount(remaining_after)),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::NewTransaction;
+
+    // Budget "Courses" de 100 dont 80 sont déjà dépensés
+    fn courses(policy: OverspendPolicy) -> BudgetManager {
+        let manager = BudgetManager::new(":memory:").unwrap();
+        manager.add_budget("Courses", 100.0).unwrap();
+        spend(&manager, "Marché", 80.0, false).unwrap();
+        manager.set_overspend_policy("Courses", policy).unwrap();
+        manager
+    }
+
+    fn spend(manager: &BudgetManager, name: &str, amount: f64, confirmed: bool) -> Result<()> {
+        manager.add_transaction("Courses", NewTransaction::new(name, amount), confirmed)
+    }
+
+    fn count(manager: &BudgetManager) -> i64 {
+        manager.conn.query_row("SELECT COUNT(*) FROM transactions", [], |row| row.get(0)).unwrap()
+    }
+
+    #[test]
+    fn policies_are_stored_by_name() {
+        for policy in OverspendPolicy::ALL {
+            assert_eq!(OverspendPolicy::parse(policy.as_str()), Some(policy));
+        }
+        assert_eq!(OverspendPolicy::parse("never"), None);
+
+        let manager = BudgetManager::new(":memory:").unwrap();
+        manager.add_budget("Courses", 100.0).unwrap();
+        assert_eq!(manager.overspend_policy("Courses").unwrap(), OverspendPolicy::Allow);
+        assert!(manager.set_overspend_policy("Inconnu", OverspendPolicy::Block).is_err());
+    }
+
+    #[test]
+    fn only_spending_past_zero_is_checked() {
+        for policy in OverspendPolicy::ALL {
+            let manager = courses(policy);
+            assert_eq!(manager.check_overspend("Courses", 20.0).unwrap(), OverspendCheck::Allowed);
+            assert_eq!(manager.check_overspend("Courses", -50.0).unwrap(), OverspendCheck::Allowed);
+        }
+        let check = |policy| courses(policy).check_overspend("Courses", 30.0).unwrap();
+        assert_eq!(check(OverspendPolicy::Allow), OverspendCheck::Allowed);
+        assert_eq!(check(OverspendPolicy::Warn), OverspendCheck::NeedsConfirmation { remaining_after: -10.0 });
+        assert_eq!(check(OverspendPolicy::Block), OverspendCheck::Blocked { remaining_after: -10.0 });
+    }
+
+    #[test]
+    fn warn_needs_a_confirmation_and_block_refuses() {
+        let manager = courses(OverspendPolicy::Allow);
+        assert!(spend(&manager, "Traiteur", 30.0, false).is_ok());
+
+        let manager = courses(OverspendPolicy::Warn);
+        assert!(spend(&manager, "Traiteur", 30.0, false).is_err());
+        assert_eq!(count(&manager), 1);
+        assert!(spend(&manager, "Traiteur", 30.0, true).is_ok());
+        assert_eq!(count(&manager), 2);
+
+        let manager = courses(OverspendPolicy::Block);
+        assert!(spend(&manager, "Traiteur", 30.0, true).is_err());
+        assert_eq!(count(&manager), 1);
+    }
+
+    #[test]
+    fn edits_check_only_the_extra_spending() {
+        let manager = courses(OverspendPolicy::Block);
+        assert!(manager.edit_transaction("Courses", "Marché".into(), "Marché".into(), Some(110.0), true).is_err());
+        assert!(manager.edit_transaction("Courses", "Marché".into(), "Marché".into(), Some(100.0), false).is_ok());
+        // Renommer sans changer le montant ne demande rien
+        assert!(manager.edit_transaction("Courses", "Marché".into(), "Primeur".into(), None, false).unwrap());
+        let spent: f64 = manager.conn.query_row("SELECT SUM(amount) FROM transactions", [], |row| row.get(0)).unwrap();
+        assert_eq!(spent, 100.0);
+    }
+}
@@ -0,0 +1,304 @@
+use rusqlite::{params, Result};
+use serde::Serialize;
+use crate::i18n::tr;
+use super::BudgetManager;
+
+// Profil d'import : décrit le CSV exporté par une banque (séparateur, encodage,
+// en-tête, format des dates et des montants, colonnes utilisées)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ImportProfile {
+    pub id: i32,
+    pub name: String,
+    pub delimiter: char,
+    pub encoding: Encoding,
+    // Numéro de la ligne d'en-tête (1 = première ligne) ; 0 : pas d'en-tête
+    pub header_row: usize,
+    // Format chrono des dates (ex : %d/%m/%Y) ; vide : lecture souple (`parse::date`)
+    pub date_format: String,
+    pub decimal_separator: char,
+    pub sign: SignConvention,
+    // Colonnes : nom dans l'en-tête ou numéro (1 = première colonne) ; vide : absente
+    pub date_column: String,
+    pub name_column: String,
+    pub amount_column: String,
+    pub debit_column: String,
+    pub credit_column: String,
+    pub payee_column: String,
+    pub notes_column: String,
+    pub budget_column: String,
+    // Budget des lignes sans colonne budget (ou avec une case vide)
+    pub default_budget: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Encoding {
+    #[serde(rename = "utf-8")]
+    Utf8,
+    // Latin-1 étendu, courant dans les exports bancaires sous Windows
+    #[serde(rename = "windows-1252")]
+    Windows1252,
+}
+
+impl Encoding {
+    pub const ALL: [Encoding; 2] = [Encoding::Utf8, Encoding::Windows1252];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Encoding::Utf8 => "utf-8",
+            Encoding::Windows1252 => "windows-1252",
+        }
+    }
+
+    pub fn parse(encoding: &str) -> Option<Self> {
+        match encoding.to_lowercase().as_str() {
+            "utf-8" | "utf8" => Some(Encoding::Utf8),
+            "windows-1252" | "cp1252" | "latin1" | "latin-1" | "iso-8859-1" => Some(Encoding::Windows1252),
+            _ => None,
+        }
+    }
+}
+
+// Sens des montants dans le fichier. Les dépenses sont enregistrées en positif ;
+// les entrées d'argent (salaire, remboursement) ne sont pas importées.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignConvention {
+    // Une colonne montant, dépenses négatives (relevés bancaires)
+    ExpensesNegative,
+    // Une colonne montant, dépenses positives
+    ExpensesPositive,
+    // Colonnes débit (dépenses) et crédit (entrées) séparées
+    DebitCredit,
+}
+
+impl SignConvention {
+    pub const ALL: [SignConvention; 3] =
+        [SignConvention::ExpensesNegative, SignConvention::ExpensesPositive, SignConvention::DebitCredit];
+
+    // Valeur stockée dans la colonne `sign` de la table import_profiles
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SignConvention::ExpensesNegative => "expenses_negative",
+            SignConvention::ExpensesPositive => "expenses_positive",
+            SignConvention::DebitCredit => "debit_credit",
+        }
+    }
+
+    pub fn parse(sign: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|s| s.as_str() == sign)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SignConvention::ExpensesNegative => tr!(SignExpensesNegative),
+            SignConvention::ExpensesPositive => tr!(SignExpensesPositive),
+            SignConvention::DebitCredit => tr!(SignDebitCredit),
+        }
+    }
+}
+
+impl ImportProfile {
+    const COLUMNS: &'static str = "id, name, delimiter, encoding, header_row, date_format, decimal_separator, sign,
+         date_column, name_column, amount_column, debit_column, credit_column, payee_column, notes_column,
+         budget_column, default_budget";
+
+    fn from_row(row: &rusqlite::Row) -> Result<Self> {
+        let first_char = |index: usize| -> Result<char> { Ok(row.get::<_, String>(index)?.chars().next().unwrap_or(',')) };
+        Ok(Self {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            delimiter: first_char(2)?,
+            encoding: Encoding::parse(&row.get::<_, String>(3)?).unwrap_or(Encoding::Utf8),
+            header_row: row.get(4)?,
+            date_format: row.get(5)?,
+            decimal_separator: first_char(6)?,
+            sign: SignConvention::parse(&row.get::<_, String>(7)?).unwrap_or(SignConvention::ExpensesNegative),
+            date_column: row.get(8)?,
+            name_column: row.get(9)?,
+            amount_column: row.get(10)?,
+            debit_column: row.get(11)?,
+            credit_column: row.get(12)?,
+            payee_column: row.get(13)?,
+            notes_column: row.get(14)?,
+            budget_column: row.get(15)?,
+            default_budget: row.get(16)?,
+        })
+    }
+
+    // Résumé des colonnes utilisées, pour l'affichage
+    pub fn columns_summary(&self) -> String {
+        [
+            ("date", &self.date_column),
+            ("name", &self.name_column),
+            ("amount", &self.amount_column),
+            ("debit", &self.debit_column),
+            ("credit", &self.credit_column),
+            ("payee", &self.payee_column),
+            ("notes", &self.notes_column),
+            ("budget", &self.budget_column),
+        ]
+        .iter()
+        .filter(|(_, column)| !column.is_empty())
+        .map(|(field, column)| format!("{}={}", field, column))
+        .collect::<Vec<_>>()
+        .join(", ")
+    }
+
+    fn validate(&self) -> Result<()> {
+        let invalid = |message: String| Err(rusqlite::Error::InvalidParameterName(message));
+        if self.name.trim().is_empty() {
+            return invalid(tr!(ProfileNameEmpty).to_string());
+        }
+        if !self.delimiter.is_ascii() {
+            return invalid(tr!(ProfileBadDelimiter).to_string());
+        }
+        if self.delimiter == self.decimal_separator {
+            return invalid(tr!(ProfileSameSeparators).to_string());
+        }
+        if self.name_column.is_empty() {
+            return invalid(tr!(ProfileMissingColumn, "name"));
+        }
+        match self.sign {
+            SignConvention::DebitCredit if self.debit_column.is_empty() => invalid(tr!(ProfileMissingColumn, "debit")),
+            SignConvention::DebitCredit => Ok(()),
+            _ if self.amount_column.is_empty() => invalid(tr!(ProfileMissingColumn, "amount")),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl BudgetManager {
+    pub(super) fn create_import_profiles_table(&self) -> Result<()> {
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS import_profiles (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL UNIQUE,
+                delimiter TEXT NOT NULL,
+                encoding TEXT NOT NULL,
+                header_row INTEGER NOT NULL,
+                date_format TEXT NOT NULL DEFAULT '',
+                decimal_separator TEXT NOT NULL,
+                sign TEXT NOT NULL,
+                date_column TEXT NOT NULL DEFAULT '',
+                name_column TEXT NOT NULL,
+                amount_column TEXT NOT NULL DEFAULT '',
+                debit_column TEXT NOT NULL DEFAULT '',
+                credit_column TEXT NOT NULL DEFAULT '',
+                payee_column TEXT NOT NULL DEFAULT '',
+                notes_column TEXT NOT NULL DEFAULT '',
+                budget_column TEXT NOT NULL DEFAULT '',
+                default_budget TEXT NOT NULL DEFAULT ''
+            )",
+            [],
+        )?;
+        Ok(())
+    }
+
+//==================================Function to save import profiles====================================
+    // Crée le profil, ou remplace celui qui porte le même nom
+    pub fn save_import_profile(&self, profile: &ImportProfile) -> Result<()> {
+        profile.validate()?;
+        self.conn.execute(
+            "INSERT INTO import_profiles (name, delimiter, encoding, header_row, date_format, decimal_separator, sign,
+                 date_column, name_column, amount_column, debit_column, credit_column, payee_column, notes_column,
+                 budget_column, default_budget)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)
+             ON CONFLICT(name) DO UPDATE SET
+                 delimiter = excluded.delimiter, encoding = excluded.encoding, header_row = excluded.header_row,
+                 date_format = excluded.date_format, decimal_separator = excluded.decimal_separator, sign = excluded.sign,
+                 date_column = excluded.date_column, name_column = excluded.name_column,
+                 amount_column = excluded.amount_column, debit_column = excluded.debit_column,
+                 credit_column = excluded.credit_column, payee_column = excluded.payee_column,
+                 notes_column = excluded.notes_column, budget_column = excluded.budget_column,
+                 default_budget = excluded.default_budget",
+            params![
+                profile.name.trim(), profile.delimiter.to_string(), profile.encoding.as_str(), profile.header_row,
+                profile.date_format, profile.decimal_separator.to_string(), profile.sign.as_str(),
+                profile.date_column, profile.name_column, profile.amount_column, profile.debit_column,
+                profile.credit_column, profile.payee_column, profile.notes_column, profile.budget_column,
+                profile.default_budget
+            ],
+        )?;
+        Ok(())
+    }
+
+//==================================Function to list import profiles====================================
+    pub fn list_import_profiles(&self) -> Result<Vec<ImportProfile>> {
+        let mut stmt = self.conn.prepare(&format!("SELECT {} FROM import_profiles ORDER BY name", ImportProfile::COLUMNS))?;
+        let profiles = stmt.query_map([], ImportProfile::from_row)?;
+        profiles.collect()
+    }
+
+    pub fn get_import_profile(&self, name: &str) -> Result<Option<ImportProfile>> {
+        let mut stmt = self.conn.prepare(&format!("SELECT {} FROM import_profiles WHERE name = ?1", ImportProfile::COLUMNS))?;
+        let mut profiles = stmt.query_map(params![name], ImportProfile::from_row)?;
+        profiles.next().transpose()
+    }
+
+//==================================Function to remove import profiles====================================
+    pub fn remove_import_profile(&self, name: &str) -> Result<bool> {
+        let rows_affected = self.conn.execute("DELETE FROM import_profiles WHERE name = ?1", params![name])?;
+        Ok(rows_affected > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(name: &str) -> ImportProfile {
+        ImportProfile {
+            id: 0,
+            name: name.to_string(),
+            delimiter: ';',
+            encoding: Encoding::Windows1252,
+            header_row: 1,
+            date_format: "%d/%m/%Y".to_string(),
+            decimal_separator: ',',
+            sign: SignConvention::ExpensesNegative,
+            date_column: "Date".to_string(),
+            name_column: "Libellé".to_string(),
+            amount_column: "Montant".to_string(),
+            debit_column: String::new(),
+            credit_column: String::new(),
+            payee_column: String::new(),
+            notes_column: String::new(),
+            budget_column: String::new(),
+            default_budget: "Courses".to_string(),
+        }
+    }
+
+    fn is_refused(result: Result<()>) -> bool {
+        matches!(result, Err(rusqlite::Error::InvalidParameterName(_)))
+    }
+
+    #[test]
+    fn saving_twice_replaces_the_profile() {
+        let manager = BudgetManager::new(":memory:").unwrap();
+        manager.save_import_profile(&profile("Banque")).unwrap();
+        let updated = ImportProfile { header_row: 3, default_budget: "Loisirs".to_string(), ..profile("Banque") };
+        manager.save_import_profile(&updated).unwrap();
+
+        let profiles = manager.list_import_profiles().unwrap();
+        assert_eq!(profiles.len(), 1);
+        assert_eq!(profiles[0], ImportProfile { id: profiles[0].id, ..updated });
+        assert!(manager.remove_import_profile("Banque").unwrap());
+        assert_eq!(manager.get_import_profile("Banque").unwrap(), None);
+    }
+
+    #[test]
+    fn incomplete_profiles_are_refused() {
+        let manager = BudgetManager::new(":memory:").unwrap();
+        assert!(is_refused(manager.save_import_profile(&profile(" "))));
+        assert!(is_refused(manager.save_import_profile(&ImportProfile { delimiter: ',', ..profile("Banque") })));
+        assert!(is_refused(manager.save_import_profile(&ImportProfile { name_column: String::new(), ..profile("Banque") })));
+        assert!(is_refused(manager.save_import_profile(&ImportProfile { amount_column: String::new(), ..profile("Banque") })));
+
+        // Débit/crédit : la colonne montant n'est plus utilisée, la colonne débit l'est
+        let debit_credit = ImportProfile { sign: SignConvention::DebitCredit, amount_column: String::new(), ..profile("Banque") };
+        assert!(is_refused(manager.save_import_profile(&debit_credit)));
+        let debit_credit = ImportProfile { debit_column: "Débit".to_string(), ..debit_credit };
+        manager.save_import_profile(&debit_credit).unwrap();
+        assert!(manager.list_import_profiles().unwrap().len() == 1);
+    }
+}
@@ -0,0 +1,20 @@
+use rusqlite::{params_from_iter, Result};
+use crate::query::Query;
+use super::{BudgetManager, TransactionRecord};
+
+impl BudgetManager {
+//==================================Run a query====================================
+    // Exécute une requête du mini-langage (voir `crate::query`) sur les transactions
+    pub fn query_transactions(&self, query: &Query) -> Result<Vec<TransactionRecord>> {
+        let (where_clause, values) = query.to_sql();
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {}
+             FROM transactions t JOIN budgets b ON b.id = t.budget_id
+             WHERE {}
+             ORDER BY t.transaction_date, t.id",
+            TransactionRecord::COLUMNS, where_clause
+        ))?;
+        let records = stmt.query_map(params_from_iter(values), TransactionRecord::from_row)?;
+        records.collect()
+    }
+}
@@ -0,0 +1,456 @@
+use regex::{Regex, RegexBuilder};
+use rusqlite::{params, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use crate::i18n::{self, tr};
+use crate::parse;
+use super::duplicates::normalize_description;
+use super::{join_tags, split_tags, BudgetManager, NewTransaction, TransactionRecord};
+
+// Règle d'affectation : des conditions sur la transaction (toutes facultatives,
+// toutes doivent être remplies) et ce qu'elle lui attribue. Les règles servent
+// à l'import, pour les lignes qui n'indiquent pas leur budget, et à la saisie.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AssignmentRule {
+    pub id: i32,
+    // Les règles de plus forte priorité passent d'abord
+    pub priority: i32,
+    // Texte contenu dans le libellé ou la note, casse et accents ignorés
+    pub contains: String,
+    // Expression régulière sur le libellé ou la note, casse ignorée
+    pub regex: String,
+    // Texte contenu dans le bénéficiaire (contrepartie du relevé)
+    pub payee: String,
+    pub min_amount: Option<f64>,
+    pub max_amount: Option<f64>,
+    pub budget_name: String,
+    pub category: String,
+    pub tags: Vec<String>,
+}
+
+impl AssignmentRule {
+    fn validate(&self) -> Result<()> {
+        let invalid = |message: String| Err(rusqlite::Error::InvalidParameterName(message));
+        let no_condition = self.contains.trim().is_empty()
+            && self.regex.is_empty()
+            && self.payee.trim().is_empty()
+            && self.min_amount.is_none()
+            && self.max_amount.is_none();
+        if no_condition {
+            return invalid(tr!(AssignRuleNoCondition).to_string());
+        }
+        if self.budget_name.trim().is_empty() && self.category.trim().is_empty() && self.tags.is_empty() {
+            return invalid(tr!(AssignRuleNoAction).to_string());
+        }
+        if let (Some(min), Some(max)) = (self.min_amount, self.max_amount) {
+            if min > max {
+                return invalid(tr!(AssignRuleBadRange).to_string());
+            }
+        }
+        if !self.regex.is_empty() {
+            compile(&self.regex).map_err(|err| rusqlite::Error::InvalidParameterName(tr!(AssignRuleBadRegex, err)))?;
+        }
+        Ok(())
+    }
+
+    // Conditions lisibles : libellé contient "carrefour", montant ≤ 50...
+    pub fn conditions(&self) -> String {
+        let mut conditions = Vec::new();
+        if !self.contains.is_empty() {
+            conditions.push(tr!(CondContains, self.contains));
+        }
+        if !self.regex.is_empty() {
+            conditions.push(tr!(CondRegex, self.regex));
+        }
+        if !self.payee.is_empty() {
+            conditions.push(tr!(CondPayee, self.payee));
+        }
+        if let Some(min) = self.min_amount {
+            conditions.push(tr!(CondAmountMin, i18n::number(min, 2)));
+        }
+        if let Some(max) = self.max_amount {
+            conditions.push(tr!(CondAmountMax, i18n::number(max, 2)));
+        }
+        conditions.join(", ")
+    }
+
+    fn matches(&self, regex: Option<&Regex>, transaction: &NewTransaction) -> bool {
+        let texts = [transaction.transaction_name.as_str(), transaction.notes.as_str()];
+        let contains = |text: &str, pattern: &str| parse::fold(text).contains(&parse::fold(pattern.trim()));
+        (self.contains.trim().is_empty() || texts.iter().any(|text| contains(text, &self.contains)))
+            && regex.is_none_or(|regex| texts.iter().any(|text| regex.is_match(text)))
+            && (self.payee.trim().is_empty() || contains(&transaction.payee, &self.payee))
+            && self.min_amount.is_none_or(|min| transaction.amount >= min)
+            && self.max_amount.is_none_or(|max| transaction.amount <= max)
+    }
+}
+
+// Champ de la règle suggérée ("contains" ou "payee") et son motif
+type SuggestionKey = (&'static str, String);
+
+fn compile(pattern: &str) -> std::result::Result<Regex, regex::Error> {
+    RegexBuilder::new(pattern).case_insensitive(true).build()
+}
+
+// Ce que les règles attribuent à une transaction. Chaque règle qui s'applique
+// complète ce qui manque : le budget et la catégorie viennent de la première
+// règle (par priorité) qui en donne un, les tags de toutes les règles.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Assignment {
+    pub budget_name: Option<String>,
+    pub category: Option<String>,
+    pub tags: Vec<String>,
+}
+
+// Règles enregistrées, expressions régulières compilées, par priorité
+pub struct RuleSet {
+    rules: Vec<(AssignmentRule, Option<Regex>)>,
+}
+
+impl RuleSet {
+    pub fn matching(&self, transaction: &NewTransaction) -> Vec<&AssignmentRule> {
+        self.rules
+            .iter()
+            .filter(|(rule, regex)| rule.matches(regex.as_ref(), transaction))
+            .map(|(rule, _)| rule)
+            .collect()
+    }
+
+    pub fn assign(&self, transaction: &NewTransaction) -> Assignment {
+        let mut assignment = Assignment::default();
+        for rule in self.matching(transaction) {
+            if assignment.budget_name.is_none() && !rule.budget_name.is_empty() {
+                assignment.budget_name = Some(rule.budget_name.clone());
+            }
+            if assignment.category.is_none() && !rule.category.is_empty() {
+                assignment.category = Some(rule.category.clone());
+            }
+            for tag in &rule.tags {
+                if !assignment.tags.contains(tag) {
+                    assignment.tags.push(tag.clone());
+                }
+            }
+        }
+        assignment
+    }
+
+    // Complète la catégorie (si elle est vide) et les tags de la transaction ;
+    // retourne le budget attribué, que l'appelant utilise ou non
+    pub fn apply(&self, transaction: &mut NewTransaction) -> Option<String> {
+        let assignment = self.assign(transaction);
+        if transaction.category.is_empty() {
+            transaction.category = assignment.category.unwrap_or_default();
+        }
+        for tag in assignment.tags {
+            if !transaction.tags.iter().any(|known| super::normalize_tag(known) == tag) {
+                transaction.tags.push(tag);
+            }
+        }
+        assignment.budget_name
+    }
+}
+
+// Règle proposée à partir des transactions déjà classées
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleSuggestion {
+    // Condition proposée : `contains` (mot du libellé) ou `payee`
+    pub field: &'static str,
+    pub pattern: String,
+    pub budget_name: String,
+    // Transactions de ce budget parmi celles qui remplissent la condition
+    pub matches: usize,
+    pub total: usize,
+}
+
+impl RuleSuggestion {
+    pub fn rule(&self) -> AssignmentRule {
+        let mut rule = AssignmentRule { budget_name: self.budget_name.clone(), ..Default::default() };
+        match self.field {
+            "payee" => rule.payee = self.pattern.clone(),
+            _ => rule.contains = self.pattern.clone(),
+        }
+        rule
+    }
+}
+
+// Mots trop courants dans les libellés bancaires pour identifier un marchand
+const GENERIC_WORDS: &[&str] = &[
+    "cb", "carte", "prlv", "prelevement", "sepa", "vir", "virement", "paiement", "achat", "retrait", "facture",
+    "dab", "chez", "pour", "par", "les", "des", "the", "and", "card", "payment", "purchase", "transfer", "direct",
+    "debit", "pos",
+];
+
+// Premier mot significatif du libellé : "CB CARREFOUR 12/03" donne "carrefour"
+fn keyword(description: &str) -> Option<String> {
+    normalize_description(description)
+        .split(' ')
+        .find(|word| word.chars().count() >= 3 && word.chars().all(char::is_alphabetic) && !GENERIC_WORDS.contains(word))
+        .map(String::from)
+}
+
+// Part minimale des transactions d'un mot-clé qui doivent être dans le même budget
+const SUGGESTION_AGREEMENT: f64 = 0.8;
+
+impl BudgetManager {
+    pub(super) fn create_assignment_rules_table(&self) -> Result<()> {
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS assignment_rules (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                priority INTEGER NOT NULL DEFAULT 0,
+                contains TEXT NOT NULL DEFAULT '',
+                regex TEXT NOT NULL DEFAULT '',
+                payee TEXT NOT NULL DEFAULT '',
+                min_amount REAL,
+                max_amount REAL,
+                budget_id INTEGER,
+                category TEXT NOT NULL DEFAULT '',
+                tags TEXT NOT NULL DEFAULT '',
+                FOREIGN KEY(budget_id) REFERENCES budgets(id)
+            )",
+            [],
+        )?;
+        Ok(())
+    }
+
+//==================================Function to add assignment rules====================================
+    // Retourne l'identifiant de la règle
+    pub fn add_assignment_rule(&self, rule: &AssignmentRule) -> Result<i32> {
+        rule.validate()?;
+        let budget_id = match rule.budget_name.trim() {
+            "" => None,
+            budget_name => Some(
+                self.get_budget_id(budget_name)?
+                    .ok_or_else(|| rusqlite::Error::InvalidParameterName(tr!(BudgetNotFound, budget_name)))?,
+            ),
+        };
+        self.conn.execute(
+            "INSERT INTO assignment_rules (priority, contains, regex, payee, min_amount, max_amount, budget_id, category, tags)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                rule.priority, rule.contains.trim(), rule.regex, rule.payee.trim(), rule.min_amount, rule.max_amount,
+                budget_id, rule.category.trim(), join_tags(&rule.tags)
+            ],
+        )?;
+        Ok(self.conn.last_insert_rowid() as i32)
+    }
+
+    pub fn remove_assignment_rule(&self, id: i32) -> Result<bool> {
+        let rows_affected = self.conn.execute("DELETE FROM assignment_rules WHERE id = ?1", params![id])?;
+        Ok(rows_affected > 0)
+    }
+
+//==================================Function to list assignment rules====================================
+    // Dans l'ordre d'application : priorité décroissante, puis ordre de création
+    pub fn list_assignment_rules(&self) -> Result<Vec<AssignmentRule>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT r.id, r.priority, r.contains, r.regex, r.payee, r.min_amount, r.max_amount,
+                    COALESCE(b.budget_name, ''), r.category, r.tags
+             FROM assignment_rules r LEFT JOIN budgets b ON b.id = r.budget_id
+             ORDER BY r.priority DESC, r.id",
+        )?;
+        let rules = stmt.query_map([], |row| {
+            Ok(AssignmentRule {
+                id: row.get(0)?,
+                priority: row.get(1)?,
+                contains: row.get(2)?,
+                regex: row.get(3)?,
+                payee: row.get(4)?,
+                min_amount: row.get(5)?,
+                max_amount: row.get(6)?,
+                budget_name: row.get(7)?,
+                category: row.get(8)?,
+                tags: split_tags(&row.get::<_, String>(9)?),
+            })
+        })?;
+        rules.collect()
+    }
+
+    pub fn assignment_rules(&self) -> Result<RuleSet> {
+        let rules = self
+            .list_assignment_rules()?
+            .into_iter()
+            .map(|rule| {
+                // Expression vérifiée à l'enregistrement
+                let regex = Some(&rule.regex).filter(|regex| !regex.is_empty()).and_then(|regex| compile(regex).ok());
+                (rule, regex)
+            })
+            .collect();
+        Ok(RuleSet { rules })
+    }
+
+    // Pour la saisie manuelle : catégorie et tags des règles ; retourne le budget attribué
+    pub fn apply_assignment_rules(&self, transaction: &mut NewTransaction) -> Result<Option<String>> {
+        Ok(self.assignment_rules()?.apply(transaction))
+    }
+
+//==================================Function to suggest assignment rules====================================
+    // Regroupe les transactions enregistrées par bénéficiaire, ou à défaut par le
+    // premier mot significatif du libellé, et propose une règle pour chaque groupe
+    // d'au moins `min_count` transactions presque toutes dans le même budget.
+    // Les transactions déjà classées par une règle sont ignorées.
+    pub fn suggest_assignment_rules(&self, min_count: usize) -> Result<Vec<RuleSuggestion>> {
+        let rules = self.assignment_rules()?;
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM transactions t JOIN budgets b ON b.id = t.budget_id ORDER BY t.id",
+            TransactionRecord::COLUMNS
+        ))?;
+        let records = stmt.query_map([], TransactionRecord::from_row)?.collect::<Result<Vec<_>>>()?;
+
+        // (champ, motif) => nombre de transactions par budget, dans l'ordre d'apparition
+        let mut groups: Vec<(SuggestionKey, Vec<(String, usize)>)> = Vec::new();
+        let mut index: HashMap<SuggestionKey, usize> = HashMap::new();
+        for record in records {
+            let transaction = NewTransaction {
+                payee: record.payee.clone(),
+                notes: record.notes.clone(),
+                ..NewTransaction::new(record.transaction_name.clone(), record.amount)
+            };
+            if rules.assign(&transaction).budget_name.is_some() {
+                continue;
+            }
+            let key = match record.payee.trim() {
+                "" => match keyword(&record.transaction_name) {
+                    Some(word) => ("contains", word),
+                    None => continue,
+                },
+                payee => ("payee", payee.to_string()),
+            };
+            let folded = (key.0, parse::fold(&key.1));
+            let position = *index.entry(folded).or_insert_with(|| {
+                groups.push((key, Vec::new()));
+                groups.len() - 1
+            });
+            let budgets = &mut groups[position].1;
+            match budgets.iter_mut().find(|(name, _)| *name == record.budget_name) {
+                Some((_, count)) => *count += 1,
+                None => budgets.push((record.budget_name, 1)),
+            }
+        }
+
+        let mut suggestions: Vec<RuleSuggestion> = groups
+            .into_iter()
+            .filter_map(|((field, pattern), budgets)| {
+                let total: usize = budgets.iter().map(|(_, count)| count).sum();
+                let (budget_name, matches) = budgets.into_iter().max_by_key(|(_, count)| *count)?;
+                (total >= min_count && matches as f64 >= total as f64 * SUGGESTION_AGREEMENT)
+                    .then_some(RuleSuggestion { field, pattern, budget_name, matches, total })
+            })
+            .collect();
+        suggestions.sort_by_key(|suggestion| std::cmp::Reverse(suggestion.matches));
+        Ok(suggestions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn purchase(name: &str, amount: f64) -> NewTransaction {
+        NewTransaction { transaction_name: name.into(), amount, ..Default::default() }
+    }
+
+    // Trois règles sur "carrefour" : la plus prioritaire donne le budget,
+    // la suivante la catégorie, toutes les tags
+    fn manager() -> BudgetManager {
+        let manager = BudgetManager::new(":memory:").unwrap();
+        manager.add_budget("Courses", 300.0).unwrap();
+        manager.add_budget("Maison", 200.0).unwrap();
+        let rules = [
+            AssignmentRule {
+                priority: 0,
+                contains: "carrefour".into(),
+                budget_name: "Maison".into(),
+                category: "Divers".into(),
+                tags: vec!["magasin".into()],
+                ..Default::default()
+            },
+            AssignmentRule {
+                priority: 10,
+                contains: "CARREFOUR".into(),
+                budget_name: "Courses".into(),
+                tags: vec!["#Fixe".into()],
+                ..Default::default()
+            },
+            AssignmentRule {
+                priority: 5,
+                regex: "^carrefour (city|market)".into(),
+                category: "Alimentation".into(),
+                tags: vec!["fixe".into(), "magasin".into()],
+                ..Default::default()
+            },
+            AssignmentRule {
+                priority: 20,
+                contains: "carrefour".into(),
+                min_amount: Some(100.0),
+                tags: vec!["gros".into()],
+                ..Default::default()
+            },
+        ];
+        for rule in &rules {
+            manager.add_assignment_rule(rule).unwrap();
+        }
+        manager
+    }
+
+    #[test]
+    fn rules_are_listed_by_priority() {
+        let priorities = manager().list_assignment_rules().unwrap().iter().map(|rule| rule.priority).collect::<Vec<_>>();
+        assert_eq!(priorities, vec![20, 10, 5, 0]);
+    }
+
+    #[test]
+    fn first_rule_wins_and_tags_are_merged() {
+        let rules = manager().assignment_rules().unwrap();
+        let assignment = rules.assign(&purchase("Carrefour City Lyon", 42.0));
+        assert_eq!(
+            assignment,
+            Assignment {
+                budget_name: Some("Courses".into()),
+                category: Some("Alimentation".into()),
+                tags: vec!["fixe".into(), "magasin".into()],
+            }
+        );
+
+        // La règle sur le montant passe en tête sans rien changer d'autre que les tags
+        let assignment = rules.assign(&purchase("carrefour market", 120.0));
+        assert_eq!(assignment.budget_name.as_deref(), Some("Courses"));
+        assert_eq!(assignment.tags, vec!["gros", "fixe", "magasin"]);
+
+        // Sans la règle à expression, la catégorie vient de la moins prioritaire
+        let assignment = rules.assign(&purchase("Carrefour Drive", 42.0));
+        assert_eq!(assignment.category.as_deref(), Some("Divers"));
+        assert_eq!(rules.assign(&purchase("Boulangerie", 42.0)), Assignment::default());
+    }
+
+    #[test]
+    fn apply_keeps_what_the_transaction_already_has() {
+        let rules = manager().assignment_rules().unwrap();
+        let mut transaction = purchase("Carrefour City", 42.0);
+        transaction.category = "Repas".into();
+        transaction.tags = vec!["#Fixe".into()];
+        assert_eq!(rules.apply(&mut transaction).as_deref(), Some("Courses"));
+        assert_eq!(transaction.category, "Repas");
+        assert_eq!(transaction.tags, vec!["#Fixe", "magasin"]);
+    }
+
+    #[test]
+    fn invalid_rules_are_refused() {
+        let manager = manager();
+        let invalid = [
+            AssignmentRule { budget_name: "Courses".into(), ..Default::default() },
+            AssignmentRule { contains: "carrefour".into(), ..Default::default() },
+            AssignmentRule {
+                min_amount: Some(50.0),
+                max_amount: Some(10.0),
+                category: "Divers".into(),
+                ..Default::default()
+            },
+            AssignmentRule { regex: "(".into(), category: "Divers".into(), ..Default::default() },
+            AssignmentRule { contains: "carrefour".into(), budget_name: "Inconnu".into(), ..Default::default() },
+        ];
+        for rule in &invalid {
+            assert!(matches!(manager.add_assignment_rule(rule), Err(rusqlite::Error::InvalidParameterName(_))), "{rule:?}");
+        }
+        assert_eq!(manager.list_assignment_rules().unwrap().len(), 4);
+    }
+}
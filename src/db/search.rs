@@ -0,0 +1,194 @@
+use rusqlite::{params, Result};
+use super::{BudgetManager, TransactionRecord};
+
+impl BudgetManager {
+    // Index FTS5 sur les noms, notes et bénéficiaires des transactions.
+    // Les triggers le maintiennent à jour à chaque insertion, modification ou suppression.
+    pub(super) fn create_search_index(&self) -> Result<()> {
+        let exists: bool = self.conn.query_row(
+            "SELECT COUNT(*) > 0 FROM sqlite_master WHERE name = 'transactions_fts'",
+            [],
+            |row| row.get(0),
+        )?;
+
+        self.conn.execute_batch(
+            "CREATE VIRTUAL TABLE IF NOT EXISTS transactions_fts USING fts5(
+                transaction_name, notes, payee,
+                content = 'transactions', content_rowid = 'id',
+                tokenize = 'unicode61 remove_diacritics 2'
+            );
+            CREATE TRIGGER IF NOT EXISTS transactions_fts_insert AFTER INSERT ON transactions BEGIN
+                INSERT INTO transactions_fts (rowid, transaction_name, notes, payee)
+                VALUES (new.id, new.transaction_name, new.notes, new.payee);
+            END;
+            CREATE TRIGGER IF NOT EXISTS transactions_fts_delete AFTER DELETE ON transactions BEGIN
+                INSERT INTO transactions_fts (transactions_fts, rowid, transaction_name, notes, payee)
+                VALUES ('delete', old.id, old.transaction_name, old.notes, old.payee);
+            END;
+            CREATE TRIGGER IF NOT EXISTS transactions_fts_update AFTER UPDATE ON transactions BEGIN
+                INSERT INTO transactions_fts (transactions_fts, rowid, transaction_name, notes, payee)
+                VALUES ('delete', old.id, old.transaction_name, old.notes, old.payee);
+                INSERT INTO transactions_fts (rowid, transaction_name, notes, payee)
+                VALUES (new.id, new.transaction_name, new.notes, new.payee);
+            END;",
+        )?;
+
+        // Première création : indexer les transactions déjà présentes
+        if !exists {
+            self.conn.execute("INSERT INTO transactions_fts (transactions_fts) VALUES ('rebuild')", [])?;
+        }
+        Ok(())
+    }
+
+//==================================Full-text search====================================
+    // Recherche plein texte. Syntaxe acceptée :
+    //   amazon            mot exact (sans tenir compte des accents ni de la casse)
+    //   amaz*             préfixe
+    //   "carte bleue"     expression exacte
+    // Les résultats sont triés du plus pertinent au moins pertinent.
+    pub fn search_transactions(&self, query: &str) -> Result<Vec<TransactionRecord>> {
+        let Some(fts_query) = build_fts_query(query) else {
+            return Ok(Vec::new());
+        };
+
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {}
+             FROM transactions_fts
+             JOIN transactions t ON t.id = transactions_fts.rowid
+             JOIN budgets b ON b.id = t.budget_id
+             WHERE transactions_fts MATCH ?1
+             ORDER BY transactions_fts.rank",
+            TransactionRecord::COLUMNS
+        ))?;
+        let hits = stmt.query_map(params![fts_query], TransactionRecord::from_row)?;
+        hits.collect()
+    }
+}
+
+// Convertit la saisie de l'utilisateur en requête FTS5 sûre : chaque mot ou
+// expression est mis entre guillemets pour que les caractères spéciaux
+// (tirets, deux-points...) ne provoquent pas d'erreur de syntaxe.
+fn build_fts_query(query: &str) -> Option<String> {
+    let mut terms = Vec::new();
+    let mut chars = query.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '"' {
+            chars.next();
+            let phrase: String = chars.by_ref().take_while(|&c| c != '"').collect();
+            if !phrase.trim().is_empty() {
+                terms.push(quote(phrase.trim()));
+            }
+        } else {
+            let mut word = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() || c == '"' {
+                    break;
+                }
+                word.push(c);
+                chars.next();
+            }
+            match word.strip_suffix('*') {
+                Some(prefix) if !prefix.is_empty() => terms.push(format!("{}*", quote(prefix))),
+                Some(_) => {}
+                None => terms.push(quote(&word)),
+            }
+        }
+    }
+
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
+}
+
+fn quote(term: &str) -> String {
+    format!("\"{}\"", term.replace('"', "\"\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Transactions écrites directement en SQL : ce sont les triggers qui tiennent l'index à jour
+    fn manager() -> BudgetManager {
+        let manager = BudgetManager::new(":memory:").unwrap();
+        manager.add_budget("Courses", 500.0).unwrap();
+        for (name, notes, payee) in [
+            ("Amazon Prime", "abonnement annuel", "Amazon EU"),
+            ("Café de la gare", "", ""),
+            ("Retrait carte bleue", "distributeur", ""),
+        ] {
+            insert(&manager, name, notes, payee);
+        }
+        manager
+    }
+
+    fn insert(manager: &BudgetManager, name: &str, notes: &str, payee: &str) {
+        manager
+            .conn
+            .execute(
+                "INSERT INTO transactions (budget_id, transaction_name, amount, notes, payee) VALUES (1, ?1, 10, ?2, ?3)",
+                params![name, notes, payee],
+            )
+            .unwrap();
+    }
+
+    fn names(manager: &BudgetManager, query: &str) -> Vec<String> {
+        let mut names: Vec<String> =
+            manager.search_transactions(query).unwrap().into_iter().map(|hit| hit.transaction_name).collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn matches_words_prefixes_and_phrases() {
+        let manager = manager();
+        assert_eq!(names(&manager, "CAFE"), ["Café de la gare"]);
+        assert_eq!(names(&manager, "amaz*"), ["Amazon Prime"]);
+        assert_eq!(names(&manager, "abonnement"), ["Amazon Prime"]);
+        assert_eq!(names(&manager, "\"carte bleue\""), ["Retrait carte bleue"]);
+        assert!(names(&manager, "\"bleue carte\"").is_empty());
+        assert_eq!(names(&manager, "bleue carte"), ["Retrait carte bleue"]);
+        // Caractères spéciaux de FTS5 pris comme du texte
+        assert!(names(&manager, "c-b: (x OR").is_empty());
+        assert!(names(&manager, "  * ").is_empty());
+    }
+
+    #[test]
+    fn index_follows_updates_and_deletes() {
+        let manager = manager();
+        manager.conn.execute("UPDATE transactions SET transaction_name = 'Boulangerie' WHERE id = 2", []).unwrap();
+        assert!(names(&manager, "cafe").is_empty());
+        assert_eq!(names(&manager, "boulangerie"), ["Boulangerie"]);
+
+        manager.conn.execute("DELETE FROM transactions WHERE id = 1", []).unwrap();
+        assert!(names(&manager, "amazon").is_empty());
+    }
+
+    #[test]
+    fn existing_transactions_are_indexed_on_creation() {
+        let manager = manager();
+        manager
+            .conn
+            .execute_batch(
+                "DROP TRIGGER transactions_fts_insert; DROP TRIGGER transactions_fts_delete;
+                 DROP TRIGGER transactions_fts_update; DROP TABLE transactions_fts;",
+            )
+            .unwrap();
+        insert(&manager, "Pharmacie", "", "");
+        manager.create_search_index().unwrap();
+        assert_eq!(names(&manager, "pharmacie"), ["Pharmacie"]);
+        assert_eq!(names(&manager, "amazon"), ["Amazon Prime"]);
+    }
+
+    #[test]
+    fn quotes_every_term() {
+        assert_eq!(build_fts_query(r#"amaz* "carte bleue" a"b"#).as_deref(), Some(r#""amaz"* "carte bleue" "a" "b""#));
+        assert_eq!(build_fts_query("l'été -x").as_deref(), Some(r#""l'été" "-x""#));
+        assert_eq!(build_fts_query(" * \"\" "), None);
+    }
+}
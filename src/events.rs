@@ -0,0 +1,71 @@
+use crate::db::BudgetManager;
+
+// Événements émis par le BudgetManager après chaque modification réussie.
+// Les abonnés (alertes, journal, notifications...) sont branchés via
+// `BudgetManager::subscribe` sans toucher aux fonctions de la base.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BudgetEvent {
+    BudgetCreated {
+        budget_name: String,
+        total_amount: f64,
+    },
+    BudgetEdited {
+        old_budget_name: String,
+        new_budget_name: String,
+        total_amount: f64,
+    },
+    BudgetDeleted {
+        budget_name: String,
+    },
+    TransactionAdded {
+        budget_name: String,
+        transaction_name: String,
+        amount: f64,
+    },
+    TransactionEdited {
+        budget_name: String,
+        old_name: String,
+        new_name: String,
+        amount: f64,
+    },
+    TransactionRemoved {
+        budget_name: String,
+        transaction_name: String,
+    },
+    // Une règle d'alerte du budget s'est déclenchée
+    ThresholdCrossed {
+        budget_name: String,
+        total_amount: f64,
+        remaining_amount: f64,
+        message: String,
+    },
+    // Fin d'un import de transactions (nombre de lignes importées / ignorées / en erreur)
+    ImportFinished {
+        imported: usize,
+        skipped: usize,
+        failed: usize,
+    },
+}
+
+pub trait EventSubscriber {
+    fn on_event(&self, event: &BudgetEvent);
+}
+
+// Une simple closure peut servir d'abonné
+impl<F: Fn(&BudgetEvent)> EventSubscriber for F {
+    fn on_event(&self, event: &BudgetEvent) {
+        self(event)
+    }
+}
+
+//==================================Alert subscriber====================================
+// Affiche une alerte dans le terminal quand une règle se déclenche
+pub struct AlertPrinter;
+
+impl EventSubscriber for AlertPrinter {
+    fn on_event(&self, event: &BudgetEvent) {
+        if let BudgetEvent::ThresholdCrossed { message, .. } = event {
+            BudgetManager::afficher_alerte(message);
+        }
+    }
+}
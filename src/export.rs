@@ -0,0 +1,27 @@
+use std::error::Error;
+use std::io::Write;
+use crate::db::TransactionRecord;
+
+// En-têtes du format CSV d'échange (relu par `import::import_csv`)
+pub const CSV_HEADERS: [&str; 8] =
+    ["budget_name", "transaction_name", "amount", "transaction_date", "payee", "notes", "tags", "category"];
+
+//==================================Export transactions to CSV====================================
+pub fn export_csv(records: &[TransactionRecord], writer: impl Write) -> Result<(), Box<dyn Error>> {
+    let mut csv_writer = csv::Writer::from_writer(writer);
+    csv_writer.write_record(CSV_HEADERS)?;
+    for record in records {
+        csv_writer.write_record([
+            record.budget_name.as_str(),
+            record.transaction_name.as_str(),
+            &record.amount.to_string(),
+            record.transaction_date.as_deref().unwrap_or(""),
+            record.payee.as_str(),
+            record.notes.as_str(),
+            &record.tags.join(" "),
+            record.category.as_str(),
+        ])?;
+    }
+    csv_writer.flush()?;
+    Ok(())
+}
@@ -0,0 +1,1477 @@
+use chrono::NaiveDate;
+use serde::Deserialize;
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+// Traduction des messages affichés à l'utilisateur et formatage des nombres,
+// montants et dates selon la langue choisie. Chaque message est une variante
+// de `Msg` : un oubli dans l'un des catalogues est une erreur de compilation.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Lang {
+    Fr,
+    En,
+}
+
+impl Lang {
+    // Accepte les codes courts ("fr", "en") et les locales POSIX ("en_US.UTF-8")
+    pub fn parse(code: &str) -> Option<Self> {
+        let code = code.trim().to_lowercase();
+        match code.get(..2)? {
+            "fr" => Some(Lang::Fr),
+            "en" => Some(Lang::En),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Lang::Fr => "fr",
+            Lang::En => "en",
+        }
+    }
+
+    // BUDGET_LANG en priorité, puis les variables de locale habituelles ; français par défaut
+    pub fn from_env() -> Self {
+        ["BUDGET_LANG", "LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find_map(|value| Lang::parse(&value))
+            .unwrap_or(Lang::Fr)
+    }
+}
+
+static LANG: OnceLock<Lang> = OnceLock::new();
+
+// Fixe la langue de l'interface ; à appeler une seule fois au démarrage
+pub fn set_lang(lang: Lang) {
+    let _ = LANG.set(lang);
+}
+
+pub fn lang() -> Lang {
+    *LANG.get_or_init(Lang::from_env)
+}
+
+// Remplace chaque `{}` du modèle par l'argument suivant
+pub fn fill(template: &str, args: &[&dyn Display]) -> String {
+    let mut result = String::with_capacity(template.len());
+    let mut args = args.iter();
+    let mut parts = template.split("{}");
+    if let Some(first) = parts.next() {
+        result.push_str(first);
+    }
+    for part in parts {
+        if let Some(arg) = args.next() {
+            result.push_str(&arg.to_string());
+        }
+        result.push_str(part);
+    }
+    result
+}
+
+// tr!(BudgetAdded, nom, montant) : message traduit avec ses arguments
+macro_rules! tr {
+    ($msg:ident) => {
+        $crate::i18n::Msg::$msg.text()
+    };
+    ($msg:ident, $($arg:expr),+ $(,)?) => {
+        $crate::i18n::fill($crate::i18n::Msg::$msg.text(), &[$(&$arg as &dyn std::fmt::Display),+])
+    };
+}
+pub(crate) use tr;
+
+//==================================Number, amount and date formatting====================================
+// Nombre avec séparateur de milliers et de décimales selon la langue
+pub fn number(value: f64, decimals: usize) -> String {
+    let (thousands, decimal) = match lang() {
+        Lang::Fr => (' ', ','),
+        Lang::En => (',', '.'),
+    };
+    let formatted = format!("{:.*}", decimals, value.abs());
+    let (int_part, frac_part) = formatted.split_once('.').unwrap_or((&formatted, ""));
+
+    let mut grouped = String::new();
+    for (i, digit) in int_part.chars().enumerate() {
+        if i > 0 && (int_part.len() - i) % 3 == 0 {
+            grouped.push(thousands);
+        }
+        grouped.push(digit);
+    }
+    let sign = if value < 0.0 && formatted.chars().any(|c| c.is_ascii_digit() && c != '0') { "-" } else { "" };
+    if frac_part.is_empty() {
+        format!("{}{}", sign, grouped)
+    } else {
+        format!("{}{}{}{}", sign, grouped, decimal, frac_part)
+    }
+}
+
+// Montant avec deux décimales et symbole monétaire ("1 234,50 $" ou "$1,234.50")
+pub fn amount(value: f64) -> String {
+    let currency = &crate::config::get().currency;
+    let formatted = number(value, 2);
+    match lang() {
+        Lang::Fr => format!("{} {}", formatted, currency),
+        Lang::En => match formatted.strip_prefix('-') {
+            Some(positive) => format!("-{}{}", currency, positive),
+            None => format!("{}{}", currency, formatted),
+        },
+    }
+}
+
+// Date ISO (AAAA-MM-JJ) affichée au format local ; les dates illisibles sont laissées telles quelles
+pub fn date(iso: &str) -> String {
+    let pattern = match lang() {
+        Lang::Fr => "%d/%m/%Y",
+        Lang::En => "%m/%d/%Y",
+    };
+    match NaiveDate::parse_from_str(iso, "%Y-%m-%d") {
+        Ok(date) => date.format(pattern).to_string(),
+        Err(_) => iso.to_string(),
+    }
+}
+
+//==================================Message catalogs====================================
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Msg {
+    // Commun
+    Error,
+    BudgetNotFound,
+    DeletionCancelled,
+    OperationCancelled,
+    TransactionCancelled,
+    Goodbye,
+
+    // Budgets et transactions
+    BudgetNameEmpty,
+    BudgetAmountOutOfRange,
+    AddingBudget,
+    BudgetAdded,
+    BudgetRemoved,
+    BudgetEdited,
+    BudgetListTitle,
+    AlertTitle,
+    TransactionAdded,
+    TransactionRemoved,
+    TransactionNotFound,
+    TransactionEdited,
+    SearchingBudget,
+    BudgetFound,
+    RemainingFor,
+
+    // Règles d'alerte
+    AlertKindPercentUsed,
+    AlertKindRemainingBelow,
+    AlertKindProjectedOverspend,
+    AlertKindLargeTransaction,
+    AlertPercentUsed,
+    AlertRemainingBelow,
+    AlertProjectedOverspend,
+    AlertLargeTransaction,
+    InvalidThreshold,
+
+    // Politique de dépassement
+    PolicyAllow,
+    PolicyWarn,
+    PolicyBlock,
+    OverspendNeedsConfirmation,
+    OverspendRefused,
+
+    // En-têtes des tableaux
+    ColId,
+    ColBudgetName,
+    ColTotal,
+    ColSpent,
+    ColRemaining,
+    ColPolicy,
+    ColBudget,
+    ColTransaction,
+    ColAmount,
+    ColDate,
+    ColPayee,
+    ColNotes,
+    ColTags,
+    ColRuleKind,
+    ColThreshold,
+    ColLine,
+    ColCommand,
+    ColResult,
+    NoTransactionFound,
+    TransactionsTotal,
+
+    // Requêtes
+    InvalidQuery,
+    QueryMissingValue,
+    QueryUnknownKey,
+    QueryUnclosedQuote,
+
+    // Configuration
+    ConfigInvalid,
+    ConfigCurrency,
+    ConfigPositive,
+    ConfigPercent,
+    ConfigCreateNeedsAmount,
+    ConfigBadValue,
+    ConfigUnknownKey,
+    ConfigFile,
+    ConfigSaved,
+    ConfigFixHint,
+    ConfigRewritten,
+    PromptConfigKey,
+    PromptConfigValue,
+
+    // Import
+    ImportLineSkipped,
+    ImportSummary,
+    ImportInvalidXml,
+    ImportBadDebitCredit,
+    ImportPendingSkipped,
+    ImportBalanceMismatch,
+    ImportBalanceMissing,
+    ImportStatementUnbalanced,
+    AssignRuleNoCondition,
+    AssignRuleNoAction,
+    AssignRuleBadRange,
+    AssignRuleBadRegex,
+    AssignRuleAdded,
+    AssignRuleRemoved,
+    AssignRuleNotFound,
+    NoAssignRules,
+    CondContains,
+    CondRegex,
+    CondPayee,
+    CondAmountMin,
+    CondAmountMax,
+    AssignRuleTestNone,
+    AssignRuleTestResult,
+    NoRuleSuggestions,
+    ConfirmRuleSuggestion,
+    RuleSuggestionsCreated,
+    ColPriority,
+    ColConditions,
+    ColMatches,
+    MenuAssignRules,
+    AssignRulesAdd,
+    AssignRulesRemove,
+    AssignRulesTest,
+    AssignRulesSuggest,
+    PromptRuleContains,
+    PromptRuleRegex,
+    PromptRulePayee,
+    PromptRuleMinAmount,
+    PromptRuleMaxAmount,
+    PromptRuleBudget,
+    PromptRuleCategory,
+    PromptRuleTags,
+    PromptRulePriority,
+    PromptAssignRuleToRemove,
+    ConfirmRemoveAssignRule,
+    ImportBudgetUnmapped,
+    ImportStatusNewBudget,
+    ImportStatusUnassigned,
+    ImportPreviewUnassigned,
+    ImportUnassignedSummary,
+    PromptAliasBudget,
+    AliasMapped,
+    AliasUnmapped,
+    AliasNotMapped,
+    NoAliases,
+    ColAlias,
+    ColRequestedBudget,
+    MenuInbox,
+    NoUnassigned,
+    InboxAssign,
+    InboxRemove,
+    PromptUnassignedToAssign,
+    PromptUnassignedToRemove,
+    UnassignedAssigned,
+    UnassignedRemoved,
+    UnassignedNotFound,
+    ConfirmAssignSameBudget,
+    ConfirmAssignSameCategory,
+    ConfirmAssignSameAccount,
+    ConfirmRemoveUnassigned,
+    ImportTransferSkipped,
+    ImportCategoryUnmapped,
+    PromptCategoryBudget,
+    CategoryMapped,
+    CategoryUnmapped,
+    CategoryNotMapped,
+    NoCategories,
+    ColCategory,
+    ImportAccountUnmapped,
+    PromptAccountBudget,
+    AccountMapped,
+    AccountUnmapped,
+    AccountNotMapped,
+    NoAccounts,
+    ColAccount,
+    ImportStatusDuplicate,
+    ImportStatusDuplicateForced,
+    ConfirmImportDuplicates,
+    ImportStatusSkipped,
+    ImportStatusFailed,
+    ImportPreviewSummary,
+    ImportDryRun,
+    ConfirmImport,
+    ConfirmImportOverspend,
+    ImportEmptyName,
+    ImportEmptyAmount,
+    ImportIncomeSkipped,
+    ImportNoBudget,
+    ImportChooseFormat,
+    ImportStandardFormat,
+    ImportNewProfile,
+    ColProfile,
+    ColDelimiter,
+    ColEncoding,
+    ColHeaderRow,
+    ColDateFormat,
+    ColDecimal,
+    ColSign,
+    ColColumns,
+    SignExpensesNegative,
+    SignExpensesPositive,
+    SignDebitCredit,
+    ProfileNameEmpty,
+    ProfileSameSeparators,
+    ProfileBadDelimiter,
+    ProfileMissingColumn,
+    ProfileColumnNotFound,
+    ProfileNotFound,
+    ProfileSaved,
+    ProfileRemoved,
+    NoProfiles,
+    ProfileSample,
+    ProfilePreview,
+    ProfileNoColumn,
+    PromptEncoding,
+    PromptDelimiter,
+    PromptHeaderRow,
+    PromptSign,
+    PromptDateColumn,
+    PromptNameColumn,
+    PromptAmountColumn,
+    PromptDebitColumn,
+    PromptCreditColumn,
+    PromptPayeeColumn,
+    PromptNotesColumn,
+    PromptBudgetColumn,
+    PromptDefaultBudget,
+    PromptDecimalSeparator,
+    PromptDateFormat,
+    PromptProfileName,
+    ConfirmSaveProfile,
+
+    // Menu
+    MenuTitle,
+    MenuAddBudget,
+    MenuRemoveBudget,
+    MenuEditBudget,
+    MenuListBudgets,
+    MenuAddTransaction,
+    MenuRemoveTransaction,
+    MenuEditTransaction,
+    MenuShowRemaining,
+    MenuAlerts,
+    MenuPolicy,
+    MenuSearch,
+    MenuQuery,
+    MenuDashboard,
+    MenuConfig,
+    MenuRepl,
+    MenuQuickAdd,
+    MenuImport,
+    MenuQuit,
+    MenuChoose,
+    MenuInvalidOption,
+    PromptBudgetName,
+    PromptBudgetTotal,
+    PromptNewBudgetName,
+    PromptNewBudgetTotal,
+    PromptBudget,
+    PromptBudgetToRemove,
+    PromptBudgetToEdit,
+    PromptTransactionName,
+    PromptTransactionAmount,
+    PromptNewTransactionName,
+    PromptNewTransactionAmount,
+    PromptTransactionToRemove,
+    PromptTransactionToEdit,
+    PromptPayee,
+    PromptNotes,
+    PromptTags,
+    PromptDate,
+    PromptQuickAdd,
+    PromptImportFile,
+    PromptPolicy,
+    PromptSearch,
+    PromptQuery,
+    PromptThreshold,
+    PromptRuleKind,
+    PromptRuleToEdit,
+    PromptRuleToRemove,
+    FilterHint,
+    SearchHelp,
+    FieldRequired,
+    NotANumber,
+    InvalidDate,
+    InvalidAmount,
+    PossibleValues,
+    InvalidThresholdRetry,
+    ConfirmRemoveBudget,
+    ConfirmRemoveTransaction,
+    ConfirmRemoveRule,
+    ConfirmOverspend,
+    BudgetAddedMenu,
+    PolicySet,
+    NoBudgets,
+    NoTransactions,
+    NoDate,
+    OverspendWarning,
+    OverspendBlocked,
+    AlertRulesOf,
+    RuleAdd,
+    RuleEdit,
+    RuleRemove,
+    Back,
+    RuleAdded,
+    RuleEdited,
+    RuleRemoved,
+    NoRules,
+
+    // Tableau de bord
+    DashHelp,
+    DashNewBudget,
+    DashEditBudget,
+    DashNewTransaction,
+    DashEditTransaction,
+    DashFieldName,
+    DashFieldTotal,
+    DashFieldAmount,
+    DashInputCancelled,
+    DashSaved,
+    DashDone,
+    DashConfirmTitle,
+    DashConfirmRemoveBudget,
+    DashConfirmRemoveTransaction,
+    DashConfirmOverspend,
+    DashNoBudgets,
+    DashBudgets,
+    DashTransactions,
+    DashAlerts,
+    DashNoAlerts,
+    DashFormHelp,
+
+    // Mode commande
+    ReplWelcome,
+    ReplNested,
+    ReplUnclosedQuote,
+    ReplHistoryError,
+
+    // Fichiers de commandes
+    BatchLineInvalid,
+    BatchInvalid,
+    BatchNotAllowed,
+    BatchNeedsYes,
+    BatchExportOutput,
+    BatchCommandFailed,
+    BatchDone,
+    BatchFailed,
+    BatchUndone,
+    BatchNotRun,
+    BatchCommitted,
+    BatchRolledBack,
+
+    // Ajout rapide
+    QuickAddNoAmount,
+    QuickAddNoName,
+    QuickAddNoBudget,
+    QuickAddPreview,
+    QuickAddConfirm,
+
+    // Aide de la ligne de commande
+    HelpApp,
+    HelpDb,
+    HelpConfigFile,
+    HelpFormat,
+    HelpLang,
+    HelpAdd,
+    HelpAmount,
+    HelpDate,
+    HelpTag,
+    HelpConfirmOverspend,
+    HelpQuick,
+    HelpQuickYes,
+    HelpBudget,
+    HelpTx,
+    HelpAlert,
+    HelpBalance,
+    HelpSearch,
+    HelpQuery,
+    HelpImport,
+    HelpImportProfile,
+    HelpImportAccount,
+    HelpImportDryRun,
+    HelpImportForce,
+    HelpImportYes,
+    HelpImportUnknownBudget,
+    HelpProfile,
+    HelpAccount,
+    HelpCategory,
+    HelpRule,
+    HelpAlias,
+    HelpInbox,
+    HelpExport,
+    HelpExportOutput,
+    HelpExportQuery,
+    HelpDashboard,
+    HelpConfig,
+    HelpRepl,
+    HelpBatch,
+    HelpReplFormat,
+    HelpConfigShow,
+    HelpConfigSet,
+    HelpBudgetAdd,
+    HelpBudgetList,
+    HelpBudgetEdit,
+    HelpNewName,
+    HelpNewTotal,
+    HelpBudgetRm,
+    HelpBudgetPolicy,
+    HelpTxAdd,
+    HelpTxList,
+    HelpTxListBudget,
+    HelpTxEdit,
+    HelpNewAmount,
+    HelpTxRm,
+    HelpAlertList,
+    HelpAlertAdd,
+    HelpRuleEdit,
+    HelpRuleRm,
+    HelpProfileList,
+    HelpProfileCreate,
+    HelpProfileRm,
+    HelpAccountList,
+    HelpAccountMap,
+    HelpAccountUnmap,
+    HelpCategoryList,
+    HelpCategoryMap,
+    HelpCategoryUnmap,
+    HelpAliasList,
+    HelpAliasMap,
+    HelpAliasUnmap,
+    HelpInboxList,
+    HelpInboxAssign,
+    HelpInboxRm,
+    HelpRuleList,
+    HelpRuleAdd,
+    HelpRuleContains,
+    HelpRuleRegex,
+    HelpRulePayee,
+    HelpRuleMin,
+    HelpRuleMax,
+    HelpRulePriority,
+    HelpRuleTest,
+    HelpRuleSuggest,
+    HelpRuleMinCount,
+    HelpRuleApply,
+
+    // Commentaires du fichier de configuration
+    ConfigDocTitle,
+    ConfigDocLanguage,
+    ConfigDocCurrency,
+    ConfigDocDatabase,
+    ConfigDocColors,
+    ConfigDocMaxTotal,
+    ConfigDocAlertPercent,
+    ConfigDocUnknownBudget,
+    ConfigDocNewBudgetAmount,
+}
+
+impl Msg {
+    pub fn text(self) -> &'static str {
+        match lang() {
+            Lang::Fr => self.fr(),
+            Lang::En => self.en(),
+        }
+    }
+
+    fn fr(self) -> &'static str {
+        use Msg::*;
+        match self {
+            Error => "Erreur : {}",
+            BudgetNotFound => "Le budget '{}' n'existe pas",
+            DeletionCancelled => "Suppression annulée.",
+            OperationCancelled => "Opération annulée.",
+            TransactionCancelled => "Transaction annulée.",
+            Goodbye => "Au revoir !!!",
+
+            BudgetNameEmpty => "Le champ nom du budget ne peut pas être vide.",
+            BudgetAmountOutOfRange => "Le montant total doit être compris entre 0 et {}",
+            AddingBudget => "Ajout en cours.....",
+            BudgetAdded => "Budget '{}' ajouté avec succès ! Montant total : {}, Montant restant : {}.",
+            BudgetRemoved => "Budget '{}' supprimé avec succès.",
+            BudgetEdited => "Budget modifié avec succès !",
+            BudgetListTitle => "Liste des budgets :",
+            AlertTitle => " ALERTE:",
+            TransactionAdded => "Transaction ajoutée avec succès !",
+            TransactionRemoved => "Transaction '{}' supprimée avec succès dans le budget '{}'.",
+            TransactionNotFound => "Aucune transaction correspondant à '{}' trouvée dans le budget '{}'",
+            TransactionEdited => "Transaction modifiée avec succès !",
+            SearchingBudget => "Recherche du budget : {} .....",
+            BudgetFound => "Budget trouvé : ID = {}, Montant total = {}",
+            RemainingFor => "Solde restant pour '{}' :",
+
+            AlertKindPercentUsed => "Pourcentage dépensé (%)",
+            AlertKindRemainingBelow => "Montant restant inférieur à",
+            AlertKindProjectedOverspend => "Dépense projetée en fin de mois (%)",
+            AlertKindLargeTransaction => "Transaction unique supérieure à",
+            AlertPercentUsed => "{} % du budget '{}' est dépensé (seuil : {} %, {} restant)",
+            AlertRemainingBelow => "Le montant restant du budget '{}' ({}) est inférieur à {}",
+            AlertProjectedOverspend => "Au rythme actuel, le budget '{}' atteindra {} de dépenses en fin de mois (budget : {})",
+            AlertLargeTransaction => "Transaction importante de {} dans le budget '{}' (seuil : {})",
+            InvalidThreshold => "Seuil d'alerte invalide",
+
+            PolicyAllow => "Autoriser",
+            PolicyWarn => "Demander confirmation",
+            PolicyBlock => "Refuser",
+            OverspendNeedsConfirmation => "Dépassement du budget '{}' ({} restant après la transaction) : confirmation requise",
+            OverspendRefused => "Dépassement du budget '{}' refusé ({} restant après la transaction)",
+
+            ColId => "ID",
+            ColBudgetName => "Nom du Budget",
+            ColTotal => "Total",
+            ColSpent => "Dépensé",
+            ColRemaining => "Restant",
+            ColPolicy => "Dépassement",
+            ColBudget => "Budget",
+            ColTransaction => "Transaction",
+            ColAmount => "Montant",
+            ColDate => "Date",
+            ColPayee => "Bénéficiaire",
+            ColNotes => "Notes",
+            ColTags => "Tags",
+            ColRuleKind => "Type de règle",
+            ColThreshold => "Seuil",
+            ColLine => "Ligne",
+            ColCommand => "Commande",
+            ColResult => "Résultat",
+            NoTransactionFound => "Aucune transaction trouvée.",
+            TransactionsTotal => "{} transaction(s), total : {}",
+
+            InvalidQuery => "Requête invalide : {}",
+            QueryMissingValue => "valeur manquante après '{}:'",
+            QueryUnknownKey => "clé inconnue '{}' (clés : budget, amount, after, before, tag, payee)",
+            QueryUnclosedQuote => "guillemet non fermé",
+
+            ConfigInvalid => "Configuration invalide : {}",
+            ConfigCurrency => "le symbole monétaire doit contenir 1 à 5 caractères",
+            ConfigPositive => "la valeur doit être un nombre supérieur à 0",
+            ConfigPercent => "la valeur doit être un pourcentage supérieur à 0 et au plus égal à 100",
+            ConfigCreateNeedsAmount => "la valeur doit être supérieure à 0 quand import.unknown_budget vaut \"create\"",
+            ConfigBadValue => "valeur invalide '{}'",
+            ConfigUnknownKey => "clé inconnue '{}' (clés : {})",
+            ConfigFile => "Fichier de configuration : {}",
+            ConfigSaved => "Configuration enregistrée. Elle s'appliquera au prochain lancement.",
+            ConfigFixHint => "Corrigez le fichier, ou remplacez la valeur avec : config set <clé> <valeur>",
+            ConfigRewritten => "Le fichier invalide est remplacé par les valeurs par défaut et cette modification.",
+            PromptConfigKey => "Paramètre à modifier",
+            PromptConfigValue => "Nouvelle valeur",
+
+            ImportLineSkipped => "Ligne {} ignorée : {}",
+            ImportSummary => "Import terminé : {} transaction(s) importée(s), {} ligne(s) ignorée(s) dont {} doublon(s), {} ligne(s) en erreur.",
+            ImportInvalidXml => "Fichier XML illisible : {}",
+            ImportBadDebitCredit => "sens de l'opération inconnu '{}' (CRDT ou DBIT attendu)",
+            ImportPendingSkipped => "opération en attente '{}' non importée",
+            ImportBalanceMismatch => "relevé '{}' : solde final {} au lieu de {} (solde initial {} + opérations {})",
+            ImportBalanceMissing => "relevé '{}' : solde initial ou final absent, opérations non vérifiables",
+            ImportStatementUnbalanced => "relevé '{}' incohérent, opération non importée",
+            AssignRuleNoCondition => "Une règle d'affectation a besoin d'au moins une condition (texte, expression, bénéficiaire ou montant)",
+            AssignRuleNoAction => "Une règle d'affectation doit attribuer un budget, une catégorie ou des tags",
+            AssignRuleBadRange => "Le montant minimal dépasse le montant maximal",
+            AssignRuleBadRegex => "Expression régulière invalide : {}",
+            AssignRuleAdded => "Règle d'affectation {} ajoutée.",
+            AssignRuleRemoved => "Règle d'affectation {} supprimée.",
+            AssignRuleNotFound => "Aucune règle d'affectation n°{}",
+            NoAssignRules => "Aucune règle d'affectation.",
+            CondContains => "libellé contient \"{}\"",
+            CondRegex => "libellé correspond à /{}/",
+            CondPayee => "bénéficiaire contient \"{}\"",
+            CondAmountMin => "montant ≥ {}",
+            CondAmountMax => "montant ≤ {}",
+            AssignRuleTestNone => "Aucune règle ne s'applique.",
+            AssignRuleTestResult => "Résultat : budget {}, catégorie {}, tags {}",
+            NoRuleSuggestions => "Aucune règle à proposer : pas assez de transactions classées de la même façon.",
+            ConfirmRuleSuggestion => "Créer la règle « {} » → {} ({} transaction(s) sur {}) ?",
+            RuleSuggestionsCreated => "{} règle(s) créée(s).",
+            ColPriority => "Priorité",
+            ColConditions => "Conditions",
+            ColMatches => "Transactions",
+            MenuAssignRules => "18.Règles d'affectation automatique",
+            AssignRulesAdd => "Ajouter une règle",
+            AssignRulesRemove => "Supprimer une règle",
+            AssignRulesTest => "Tester les règles sur une transaction",
+            AssignRulesSuggest => "Proposer des règles d'après les transactions classées",
+            PromptRuleContains => "Texte contenu dans le libellé (facultatif)",
+            PromptRuleRegex => "Expression régulière sur le libellé (facultatif)",
+            PromptRulePayee => "Texte contenu dans le bénéficiaire (facultatif)",
+            PromptRuleMinAmount => "Montant minimal (facultatif)",
+            PromptRuleMaxAmount => "Montant maximal (facultatif)",
+            PromptRuleBudget => "Budget attribué (Échap : aucun)",
+            PromptRuleCategory => "Catégorie attribuée (facultatif)",
+            PromptRuleTags => "Tags attribués, séparés par des espaces (facultatif)",
+            PromptRulePriority => "Priorité (la plus forte passe d'abord)",
+            PromptAssignRuleToRemove => "Règle d'affectation à supprimer",
+            ConfirmRemoveAssignRule => "Supprimer la règle n°{} ({}) ?",
+            ImportBudgetUnmapped => "budget '{}' inconnu, sans alias (voir `alias map`)",
+            ImportStatusNewBudget => "OK (budget créé)",
+            ImportStatusUnassigned => "à classer",
+            ImportPreviewUnassigned => "{} ligne(s) sans budget existant mise(s) de côté, à classer ensuite (option 19 du menu, `inbox list`).",
+            ImportUnassignedSummary => "{} ligne(s) mise(s) de côté parmi les transactions à classer.",
+            PromptAliasBudget => "Budget existant pour les lignes du budget inconnu '{}'",
+            AliasMapped => "Le budget '{}' des fichiers importés va dans le budget '{}'.",
+            AliasUnmapped => "L'alias '{}' est supprimé.",
+            AliasNotMapped => "Aucun alias '{}'.",
+            NoAliases => "Aucun alias de budget.",
+            ColAlias => "Alias",
+            ColRequestedBudget => "Budget demandé",
+            MenuInbox => "19.Transactions à classer ({})",
+            NoUnassigned => "Aucune transaction à classer.",
+            InboxAssign => "Ranger une transaction dans un budget",
+            InboxRemove => "Supprimer une transaction",
+            PromptUnassignedToAssign => "Transaction à ranger",
+            PromptUnassignedToRemove => "Transaction à supprimer",
+            UnassignedAssigned => "Transaction n°{} rangée dans le budget '{}'.",
+            UnassignedRemoved => "Transaction n°{} supprimée.",
+            UnassignedNotFound => "Aucune transaction à classer n°{}.",
+            ConfirmAssignSameBudget => "Ranger aussi les {} autre(s) transaction(s) du budget '{}' dans '{}', et retenir cet alias pour les prochains imports ?",
+            ConfirmAssignSameCategory => "Ranger aussi les {} autre(s) transaction(s) de la catégorie '{}' dans '{}', et retenir cette association pour les prochains imports ?",
+            ConfirmAssignSameAccount => "Ranger aussi les {} autre(s) transaction(s) du compte '{}' dans '{}', et retenir cette association pour les prochains imports ?",
+            ConfirmRemoveUnassigned => "Supprimer la transaction '{}' ({}) ?",
+            ImportTransferSkipped => "virement '{}' non importé",
+            ImportCategoryUnmapped => "catégorie '{}' sans budget associé (voir `category map`)",
+            PromptCategoryBudget => "Budget des opérations de la catégorie '{}'",
+            CategoryMapped => "Catégorie '{}' associée au budget '{}'.",
+            CategoryUnmapped => "La catégorie '{}' n'est plus associée à un budget.",
+            CategoryNotMapped => "La catégorie '{}' n'est associée à aucun budget.",
+            NoCategories => "Aucune catégorie associée à un budget.",
+            ColCategory => "Catégorie",
+            ImportAccountUnmapped => "compte '{}' sans budget associé (voir `account map`)",
+            PromptAccountBudget => "Budget des opérations du compte '{}'",
+            AccountMapped => "Compte '{}' associé au budget '{}'.",
+            AccountUnmapped => "Le compte '{}' n'est plus associé à un budget.",
+            AccountNotMapped => "Le compte '{}' n'est associé à aucun budget.",
+            NoAccounts => "Aucun compte associé à un budget.",
+            ColAccount => "Compte",
+            ImportStatusDuplicate => "doublon : ignorée",
+            ImportStatusDuplicateForced => "doublon : importée",
+            ImportStatusSkipped => "ignorée : {}",
+            ImportStatusFailed => "erreur : {}",
+            ImportPreviewSummary => "Aperçu : {} transaction(s) à importer, {} doublon(s), {} ligne(s) ignorée(s), {} ligne(s) en erreur.",
+            ConfirmImportDuplicates => "Importer aussi les {} doublon(s) ?",
+            ImportDryRun => "Simulation : rien n'a été enregistré.",
+            ConfirmImport => "Importer ces transactions ?",
+            ConfirmImportOverspend => "L'import dépasse les budgets suivants (mode \"warn\") : {}. Importer quand même leurs lignes ?",
+            ImportEmptyName => "nom de transaction vide",
+            ImportEmptyAmount => "montant absent",
+            ImportIncomeSkipped => "entrée d'argent '{}' non importée",
+            ImportNoBudget => "aucun budget (ni colonne budget, ni règle, ni budget par défaut)",
+            ImportChooseFormat => "Format du fichier",
+            ImportStandardFormat => "Reconnu automatiquement (format standard, OFX, CAMT.053, MT940, QIF)",
+            ImportNewProfile => "Nouveau profil depuis ce fichier…",
+            ColProfile => "Profil",
+            ColDelimiter => "Séparateur",
+            ColEncoding => "Encodage",
+            ColHeaderRow => "En-tête",
+            ColDateFormat => "Format de date",
+            ColDecimal => "Décimales",
+            ColSign => "Signe",
+            ColColumns => "Colonnes",
+            SignExpensesNegative => "Un montant, dépenses négatives",
+            SignExpensesPositive => "Un montant, dépenses positives",
+            SignDebitCredit => "Colonnes débit et crédit",
+            ProfileNameEmpty => "Le nom du profil ne peut pas être vide.",
+            ProfileSameSeparators => "Le séparateur de champs et le séparateur décimal doivent être différents.",
+            ProfileBadDelimiter => "Le séparateur de champs doit être un caractère ASCII.",
+            ProfileMissingColumn => "Colonne '{}' requise.",
+            ProfileColumnNotFound => "colonne '{}' absente de l'en-tête du fichier",
+            ProfileNotFound => "Le profil d'import '{}' n'existe pas.",
+            ProfileSaved => "Profil d'import '{}' enregistré.",
+            ProfileRemoved => "Profil d'import '{}' supprimé.",
+            NoProfiles => "Aucun profil d'import.",
+            ProfileSample => "Premières lignes du fichier :",
+            ProfilePreview => "Aperçu de l'import :",
+            ProfileNoColumn => "(aucune)",
+            PromptEncoding => "Encodage du fichier",
+            PromptDelimiter => "Séparateur de champs",
+            PromptHeaderRow => "Ligne d'en-tête (0 : aucune)",
+            PromptSign => "Montants",
+            PromptDateColumn => "Colonne de la date",
+            PromptNameColumn => "Colonne du libellé",
+            PromptAmountColumn => "Colonne du montant",
+            PromptDebitColumn => "Colonne des débits",
+            PromptCreditColumn => "Colonne des crédits",
+            PromptPayeeColumn => "Colonne du bénéficiaire",
+            PromptNotesColumn => "Colonne des notes",
+            PromptBudgetColumn => "Colonne du budget",
+            PromptDefaultBudget => "Budget par défaut",
+            PromptDecimalSeparator => "Séparateur décimal",
+            PromptDateFormat => "Format des dates (ex : %d/%m/%Y ; vide : détection souple)",
+            PromptProfileName => "Nom du profil",
+            ConfirmSaveProfile => "Enregistrer ce profil ?",
+
+            MenuTitle => "=== Menu Gestionnaire Budget ===",
+            MenuAddBudget => "1.Ajouter un budget",
+            MenuRemoveBudget => "2.Supprimer un budget",
+            MenuEditBudget => "3.Modifier un budget",
+            MenuListBudgets => "4.Afficher tous les budgets",
+            MenuAddTransaction => "5.Ajouter une transaction",
+            MenuRemoveTransaction => "6.Supprimer une transaction",
+            MenuEditTransaction => "7.Modifier une transaction",
+            MenuShowRemaining => "8.Afficher le solde restant d'un budget",
+            MenuAlerts => "9.Gérer les alertes d'un budget",
+            MenuPolicy => "10.Définir la politique de dépassement d'un budget",
+            MenuSearch => "11.Rechercher des transactions",
+            MenuQuery => "12.Interroger les transactions (ex : budget:Courses amount>50 after:2026-01-01)",
+            MenuDashboard => "13.Tableau de bord",
+            MenuConfig => "14.Configuration",
+            MenuRepl => "15.Mode commande (historique, complétion avec Tab)",
+            MenuQuickAdd => "16.Ajout rapide (ex : café 3,50 Loisirs hier #travail)",
+            MenuImport => "17.Importer des transactions (CSV)",
+            MenuQuit => "0.Quitter",
+            MenuChoose => "Choisissez une option",
+            MenuInvalidOption => "Option invalide, veuillez réessayer",
+            PromptBudgetName => "Nom du budget",
+            PromptBudgetTotal => "Montant total du budget",
+            PromptNewBudgetName => "Nouveau nom du budget",
+            PromptNewBudgetTotal => "Nouveau montant total du budget",
+            PromptBudget => "Budget",
+            PromptBudgetToRemove => "Budget à supprimer",
+            PromptBudgetToEdit => "Budget à modifier",
+            PromptTransactionName => "Nom de la transaction",
+            PromptTransactionAmount => "Montant de la transaction",
+            PromptNewTransactionName => "Nouveau nom de la transaction",
+            PromptNewTransactionAmount => "Nouveau montant de la transaction",
+            PromptTransactionToRemove => "Transaction à supprimer",
+            PromptTransactionToEdit => "Transaction à modifier",
+            PromptPayee => "Bénéficiaire (optionnel)",
+            PromptNotes => "Notes (optionnel)",
+            PromptTags => "Tags séparés par des espaces (optionnel)",
+            PromptDate => "Date (optionnel, aujourd'hui par défaut ; ex : hier, 15/03)",
+            PromptQuickAdd => "Transaction (nom, montant, budget, date, #tags)",
+            PromptImportFile => "Chemin du fichier CSV (colonnes budget_name, transaction_name, amount...)",
+            PromptPolicy => "Politique en cas de dépassement",
+            PromptSearch => "Rechercher",
+            PromptQuery => "Requête",
+            PromptThreshold => "Seuil",
+            PromptRuleKind => "Type de règle",
+            PromptRuleToEdit => "Règle à modifier",
+            PromptRuleToRemove => "Règle à supprimer",
+            FilterHint => "{} (tapez pour filtrer, Échap pour annuler)",
+            SearchHelp => "Recherche : mots, préfixes (amaz*) ou expressions entre guillemets (\"carte bleue\")",
+            FieldRequired => "Ce champ ne peut pas être vide",
+            NotANumber => "'{}' n'est pas un nombre valide",
+            InvalidDate => "date invalide '{}' (ex : 2026-03-15, 15/03/2026, 15/03, hier)",
+            InvalidAmount => "Montant invalide. Réessayez",
+            PossibleValues => "valeurs possibles : {}",
+            InvalidThresholdRetry => "Seuil invalide.",
+            ConfirmRemoveBudget => "Supprimer le budget '{}' et toutes ses transactions ?",
+            ConfirmRemoveTransaction => "Supprimer la transaction '{}' ({}) ?",
+            ConfirmRemoveRule => "Supprimer la règle « {} ({}) » ?",
+            ConfirmOverspend => "Confirmer le dépassement ?",
+            BudgetAddedMenu => "Le budget '{}' a été ajouté avec succès !",
+            PolicySet => "Politique du budget '{}' : {}",
+            NoBudgets => "Aucun budget enregistré. Ajoutez d'abord un budget (option 1).",
+            NoTransactions => "Le budget '{}' n'a aucune transaction.",
+            NoDate => "sans date",
+            OverspendWarning => "Attention : cette transaction dépasse le budget '{}' ({} restant après).",
+            OverspendBlocked => "Erreur : le budget '{}' n'autorise pas les dépassements ({} restant après).",
+            AlertRulesOf => "Règles d'alerte du budget '{}' :",
+            RuleAdd => "Ajouter une règle",
+            RuleEdit => "Modifier une règle",
+            RuleRemove => "Supprimer une règle",
+            Back => "Retour",
+            RuleAdded => "Règle ajoutée avec succès !",
+            RuleEdited => "Règle modifiée avec succès !",
+            RuleRemoved => "Règle supprimée avec succès !",
+            NoRules => "Aucune règle pour ce budget.",
+
+            DashHelp => "Tab : changer de panneau  a : ajouter  e : modifier  d : supprimer  q : quitter",
+            DashNewBudget => "Nouveau budget",
+            DashEditBudget => "Modifier le budget",
+            DashNewTransaction => "Nouvelle transaction",
+            DashEditTransaction => "Modifier la transaction",
+            DashFieldName => "Nom",
+            DashFieldTotal => "Montant total",
+            DashFieldAmount => "Montant",
+            DashInputCancelled => "Saisie annulée.",
+            DashSaved => "Enregistré.",
+            DashDone => "Opération effectuée.",
+            DashConfirmTitle => "Confirmation",
+            DashConfirmRemoveBudget => "Supprimer le budget '{}' et toutes ses transactions ? (o/n)",
+            DashConfirmRemoveTransaction => "Supprimer la transaction '{}' ? (o/n)",
+            DashConfirmOverspend => "Dépassement du budget ({} restant après). Confirmer ? (o/n)",
+            DashNoBudgets => "Aucun budget. Appuyez sur 'a' pour en créer un.",
+            DashBudgets => "Budgets",
+            DashTransactions => "Transactions",
+            DashAlerts => "Alertes",
+            DashNoAlerts => "Aucune alerte.",
+            DashFormHelp => "Entrée : valider   Tab : champ suivant   Échap : annuler",
+            ReplWelcome => "Mode commande : tapez 'help' pour la liste des commandes, 'help <commande>' pour le détail, 'exit' pour quitter.",
+            ReplNested => "Vous êtes déjà en mode commande.",
+            ReplUnclosedQuote => "Guillemet non fermé",
+            ReplHistoryError => "Historique non enregistré ({}) : {}",
+            BatchLineInvalid => "Ligne {} : {}",
+            BatchInvalid => "{} ligne(s) invalide(s) : aucune commande n'a été exécutée.",
+            BatchNotAllowed => "commande '{}' non autorisée dans un fichier de commandes",
+            BatchNeedsYes => "la commande '{}' demande l'option --yes dans un fichier de commandes",
+            BatchExportOutput => "export --output non autorisé dans un fichier de commandes : l'annulation n'effacerait pas le fichier écrit",
+            BatchCommandFailed => "la commande a échoué",
+            BatchDone => "OK",
+            BatchFailed => "Échec : {}",
+            BatchUndone => "Annulée",
+            BatchNotRun => "Non exécutée",
+            BatchCommitted => "{} commande(s) exécutée(s), modifications enregistrées.",
+            BatchRolledBack => "Échec à la ligne {} : toutes les modifications ont été annulées.",
+            QuickAddNoAmount => "montant manquant (ex : café 3,50 Loisirs)",
+            QuickAddNoName => "nom de la transaction manquant",
+            QuickAddNoBudget => "aucun budget reconnu (ou plusieurs possibles) dans '{}'",
+            QuickAddPreview => "Aperçu :",
+            QuickAddConfirm => "Ajouter cette transaction ?",
+            HelpApp => "Gestionnaire de budgets personnels",
+            HelpDb => "Chemin de la base de données SQLite (par défaut : clé `database` de la configuration)",
+            HelpConfigFile => "Fichier de configuration TOML",
+            HelpFormat => "Format de sortie des listes et rapports",
+            HelpLang => "Langue de l'interface (fr, en) ; sinon BUDGET_LANG ou LANG",
+            HelpAdd => "Ajout rapide d'une transaction : add BUDGET NOM MONTANT",
+            HelpAmount => "Montant (12.50, 12,50, \"45 €\", 12.5+3.2...)",
+            HelpDate => "Date (2026-03-15, 15/03, hier... ; aujourd'hui par défaut)",
+            HelpTag => "Tag (option répétable)",
+            HelpConfirmOverspend => "Confirmer un dépassement pour un budget en mode \"warn\"",
+            HelpQuick => "Ajout rapide en une saisie (ex : quick café 3,50 Loisirs hier #travail)",
+            HelpQuickYes => "Enregistrer sans aperçu à confirmer (dépassement accepté en mode \"warn\")",
+            HelpBudget => "Gérer les budgets",
+            HelpTx => "Gérer les transactions",
+            HelpAlert => "Gérer les règles d'alerte d'un budget",
+            HelpBalance => "Afficher le solde restant d'un budget",
+            HelpSearch => "Recherche plein texte (mots, préfixes amaz*, \"expressions\")",
+            HelpQuery => "Requête sur les transactions (ex : budget:Courses amount>50 after:2026-01-01)",
+            HelpImport => "Importer des transactions depuis un fichier CSV",
+            HelpImportProfile => "Profil d'import à utiliser pour un export bancaire (voir `profile list`)",
+            HelpImportAccount => "Compte d'origine des lignes, pour reconnaître les doublons (par défaut : le nom du profil)",
+            HelpImportDryRun => "Afficher l'aperçu sans rien enregistrer",
+            HelpImportForce => "Importer aussi les lignes signalées comme doublons",
+            HelpImportYes => "Importer sans demander de confirmation après l'aperçu (dépassements des budgets en mode \"warn\" acceptés)",
+            HelpImportUnknownBudget => "Lignes au budget inconnu : skip, create, map ou inbox (par défaut : configuration)",
+            HelpProfile => "Gérer les profils d'import des exports bancaires",
+            HelpAccount => "Associer les comptes des relevés bancaires (OFX, CAMT.053, MT940) à des budgets",
+            HelpCategory => "Associer les catégories des fichiers importés (QIF) à des budgets",
+            HelpRule => "Règles d'affectation automatique (budget, catégorie, tags) des transactions",
+            HelpAlias => "Associer les noms de budget inconnus des fichiers importés à des budgets existants",
+            HelpInbox => "Transactions importées dont le budget n'existe pas, à ranger dans un budget",
+            HelpExport => "Exporter les transactions au format CSV",
+            HelpExportOutput => "Fichier de sortie (sortie standard par défaut)",
+            HelpExportQuery => "N'exporter que les transactions correspondant à cette requête",
+            HelpDashboard => "Ouvrir le tableau de bord plein écran",
+            HelpConfig => "Afficher ou modifier la configuration",
+            HelpRepl => "Mode commande interactif (historique, complétion avec Tab)",
+            HelpBatch => "Exécuter un fichier de commandes (une par ligne) dans une seule transaction : tout ou rien",
+            HelpReplFormat => "Format de sortie pour cette commande",
+            HelpConfigShow => "Afficher le fichier de configuration et les valeurs actives",
+            HelpConfigSet => "Modifier une clé (language, currency, database, colors, budgets.max_total_amount, budgets.default_alert_percent, import.unknown_budget, import.new_budget_amount)",
+            HelpBudgetAdd => "Ajouter un budget",
+            HelpBudgetList => "Afficher tous les budgets",
+            HelpBudgetEdit => "Modifier un budget",
+            HelpNewName => "Nouveau nom (inchangé par défaut)",
+            HelpNewTotal => "Nouveau montant total (inchangé par défaut)",
+            HelpBudgetRm => "Supprimer un budget",
+            HelpBudgetPolicy => "Définir la politique de dépassement (allow, warn, block)",
+            HelpTxAdd => "Ajouter une transaction",
+            HelpTxList => "Lister les transactions",
+            HelpTxListBudget => "Limiter à un budget",
+            HelpTxEdit => "Modifier une transaction",
+            HelpNewAmount => "Nouveau montant (inchangé par défaut)",
+            HelpTxRm => "Supprimer une transaction",
+            HelpAlertList => "Lister les règles d'un budget",
+            HelpAlertAdd => "Ajouter une règle (percent_used, remaining_below, projected_overspend, large_transaction)",
+            HelpRuleEdit => "Modifier une règle",
+            HelpRuleRm => "Supprimer une règle",
+            HelpProfileList => "Lister les profils d'import",
+            HelpProfileCreate => "Créer un profil à partir d'un fichier exemple (assistant interactif)",
+            HelpProfileRm => "Supprimer un profil",
+            HelpAccountList => "Lister les comptes associés à un budget",
+            HelpAccountMap => "Associer un compte (identifiant du relevé) à un budget",
+            HelpAccountUnmap => "Supprimer l'association d'un compte",
+            HelpCategoryList => "Lister les catégories associées à un budget",
+            HelpCategoryMap => "Associer une catégorie (ex : \"Alimentation:Courses\") à un budget",
+            HelpCategoryUnmap => "Supprimer l'association d'une catégorie",
+            HelpAliasList => "Lister les alias de budget",
+            HelpAliasMap => "Ranger les lignes importées du budget NAME (inexistant) dans un budget existant",
+            HelpAliasUnmap => "Supprimer un alias",
+            HelpInboxList => "Lister les transactions à classer",
+            HelpInboxAssign => "Ranger une transaction dans un budget",
+            HelpInboxRm => "Supprimer une transaction à classer",
+            HelpRuleList => "Lister les règles, dans l'ordre d'application",
+            HelpRuleAdd => "Ajouter une règle : au moins une condition, et un budget, une catégorie ou des tags",
+            HelpRuleContains => "Texte contenu dans le libellé ou la note (casse et accents ignorés)",
+            HelpRuleRegex => "Expression régulière sur le libellé ou la note (casse ignorée)",
+            HelpRulePayee => "Texte contenu dans le bénéficiaire",
+            HelpRuleMin => "Montant minimal",
+            HelpRuleMax => "Montant maximal",
+            HelpRulePriority => "Les règles de plus forte priorité passent d'abord",
+            HelpRuleTest => "Montrer les règles qui s'appliquent à une transaction, et leur résultat",
+            HelpRuleSuggest => "Proposer des règles d'après les transactions déjà classées",
+            HelpRuleMinCount => "Nombre minimal de transactions semblables",
+            HelpRuleApply => "Créer toutes les règles proposées",
+            ConfigDocTitle => "Configuration du gestionnaire de budgets",
+            ConfigDocLanguage => "Langue de l'interface : \"fr\" ou \"en\" (absente : variables BUDGET_LANG / LANG)",
+            ConfigDocCurrency => "Symbole monétaire affiché avec les montants (5 caractères au plus)",
+            ConfigDocDatabase => "Fichier de base de données SQLite (l'option --db est prioritaire)",
+            ConfigDocColors => "Couleurs : \"auto\" (terminal uniquement), \"always\" ou \"never\"",
+            ConfigDocMaxTotal => "Montant total maximal d'un budget",
+            ConfigDocAlertPercent => "Seuil de la règle d'alerte créée avec chaque nouveau budget (% dépensé, au plus 100)",
+            ConfigDocUnknownBudget => "Lignes dont le budget n'existe pas : \"skip\" (ignorées), \"create\" (budget créé),\n\"map\" (budget existant demandé à l'import) ou \"inbox\" (mises de côté, à classer)",
+            ConfigDocNewBudgetAmount => "Montant total des budgets créés par l'import (positif, requis avec unknown_budget = \"create\")",
+        }
+    }
+
+    fn en(self) -> &'static str {
+        use Msg::*;
+        match self {
+            Error => "Error: {}",
+            BudgetNotFound => "Budget '{}' does not exist",
+            DeletionCancelled => "Deletion cancelled.",
+            OperationCancelled => "Operation cancelled.",
+            TransactionCancelled => "Transaction cancelled.",
+            Goodbye => "Goodbye!",
+
+            BudgetNameEmpty => "The budget name cannot be empty.",
+            BudgetAmountOutOfRange => "The total amount must be between 0 and {}",
+            AddingBudget => "Adding.....",
+            BudgetAdded => "Budget '{}' added successfully! Total amount: {}, remaining amount: {}.",
+            BudgetRemoved => "Budget '{}' removed successfully.",
+            BudgetEdited => "Budget updated successfully!",
+            BudgetListTitle => "Budgets:",
+            AlertTitle => " ALERT:",
+            TransactionAdded => "Transaction added successfully!",
+            TransactionRemoved => "Transaction '{}' removed from budget '{}'.",
+            TransactionNotFound => "No transaction matching '{}' found in budget '{}'",
+            TransactionEdited => "Transaction updated successfully!",
+            SearchingBudget => "Looking up budget: {} .....",
+            BudgetFound => "Budget found: ID = {}, total amount = {}",
+            RemainingFor => "Remaining balance for '{}':",
+
+            AlertKindPercentUsed => "Percentage spent (%)",
+            AlertKindRemainingBelow => "Remaining amount below",
+            AlertKindProjectedOverspend => "Projected month-end spending (%)",
+            AlertKindLargeTransaction => "Single transaction above",
+            AlertPercentUsed => "{}% of budget '{}' is spent (threshold: {}%, {} left)",
+            AlertRemainingBelow => "The remaining amount of budget '{}' ({}) is below {}",
+            AlertProjectedOverspend => "At the current pace, budget '{}' will reach {} of spending by month end (budget: {})",
+            AlertLargeTransaction => "Large transaction of {} in budget '{}' (threshold: {})",
+            InvalidThreshold => "Invalid alert threshold",
+
+            PolicyAllow => "Allow",
+            PolicyWarn => "Ask for confirmation",
+            PolicyBlock => "Refuse",
+            OverspendNeedsConfirmation => "Budget '{}' overspent ({} left after the transaction): confirmation required",
+            OverspendRefused => "Overspending budget '{}' refused ({} left after the transaction)",
+
+            ColId => "ID",
+            ColBudgetName => "Budget name",
+            ColTotal => "Total",
+            ColSpent => "Spent",
+            ColRemaining => "Remaining",
+            ColPolicy => "Overspending",
+            ColBudget => "Budget",
+            ColTransaction => "Transaction",
+            ColAmount => "Amount",
+            ColDate => "Date",
+            ColPayee => "Payee",
+            ColNotes => "Notes",
+            ColTags => "Tags",
+            ColRuleKind => "Rule type",
+            ColThreshold => "Threshold",
+            ColLine => "Line",
+            ColCommand => "Command",
+            ColResult => "Result",
+            NoTransactionFound => "No transaction found.",
+            TransactionsTotal => "{} transaction(s), total: {}",
+
+            InvalidQuery => "Invalid query: {}",
+            QueryMissingValue => "missing value after '{}:'",
+            QueryUnknownKey => "unknown key '{}' (keys: budget, amount, after, before, tag, payee)",
+            QueryUnclosedQuote => "unclosed quote",
+
+            ConfigInvalid => "Invalid configuration: {}",
+            ConfigCurrency => "the currency symbol must have 1 to 5 characters",
+            ConfigPositive => "the value must be a number greater than 0",
+            ConfigPercent => "the value must be a percentage greater than 0 and at most 100",
+            ConfigCreateNeedsAmount => "the value must be greater than 0 when import.unknown_budget is \"create\"",
+            ConfigBadValue => "invalid value '{}'",
+            ConfigUnknownKey => "unknown key '{}' (keys: {})",
+            ConfigFile => "Configuration file: {}",
+            ConfigSaved => "Configuration saved. It will apply on next launch.",
+            ConfigFixHint => "Fix the file, or replace the value with: config set <key> <value>",
+            ConfigRewritten => "The invalid file is replaced with the default values and this change.",
+            PromptConfigKey => "Setting to change",
+            PromptConfigValue => "New value",
+
+            ImportLineSkipped => "Line {} skipped: {}",
+            ImportSummary => "Import finished: {} transaction(s) imported, {} line(s) skipped including {} duplicate(s), {} line(s) failed.",
+            ImportInvalidXml => "Unreadable XML file: {}",
+            ImportBadDebitCredit => "unknown credit/debit indicator '{}' (CRDT or DBIT expected)",
+            ImportPendingSkipped => "pending entry '{}' not imported",
+            ImportBalanceMismatch => "statement '{}': closing balance {} instead of {} (opening balance {} + entries {})",
+            ImportBalanceMissing => "statement '{}': opening or closing balance missing, entries cannot be checked",
+            ImportStatementUnbalanced => "statement '{}' does not balance, entry not imported",
+            AssignRuleNoCondition => "An assignment rule needs at least one condition (text, regex, payee or amount)",
+            AssignRuleNoAction => "An assignment rule must assign a budget, a category or tags",
+            AssignRuleBadRange => "The minimum amount is greater than the maximum amount",
+            AssignRuleBadRegex => "Invalid regular expression: {}",
+            AssignRuleAdded => "Assignment rule {} added.",
+            AssignRuleRemoved => "Assignment rule {} removed.",
+            AssignRuleNotFound => "No assignment rule #{}",
+            NoAssignRules => "No assignment rules.",
+            CondContains => "description contains \"{}\"",
+            CondRegex => "description matches /{}/",
+            CondPayee => "payee contains \"{}\"",
+            CondAmountMin => "amount ≥ {}",
+            CondAmountMax => "amount ≤ {}",
+            AssignRuleTestNone => "No rule applies.",
+            AssignRuleTestResult => "Result: budget {}, category {}, tags {}",
+            NoRuleSuggestions => "No rule to suggest: not enough transactions classified the same way.",
+            ConfirmRuleSuggestion => "Create rule '{}' → {} ({} of {} transaction(s))?",
+            RuleSuggestionsCreated => "{} rule(s) created.",
+            ColPriority => "Priority",
+            ColConditions => "Conditions",
+            ColMatches => "Transactions",
+            MenuAssignRules => "18.Automatic assignment rules",
+            AssignRulesAdd => "Add a rule",
+            AssignRulesRemove => "Remove a rule",
+            AssignRulesTest => "Test the rules on a transaction",
+            AssignRulesSuggest => "Suggest rules from classified transactions",
+            PromptRuleContains => "Text contained in the description (optional)",
+            PromptRuleRegex => "Regular expression on the description (optional)",
+            PromptRulePayee => "Text contained in the payee (optional)",
+            PromptRuleMinAmount => "Minimum amount (optional)",
+            PromptRuleMaxAmount => "Maximum amount (optional)",
+            PromptRuleBudget => "Assigned budget (Esc: none)",
+            PromptRuleCategory => "Assigned category (optional)",
+            PromptRuleTags => "Assigned tags, separated by spaces (optional)",
+            PromptRulePriority => "Priority (highest first)",
+            PromptAssignRuleToRemove => "Assignment rule to remove",
+            ConfirmRemoveAssignRule => "Remove rule #{} ({})?",
+            ImportBudgetUnmapped => "unknown budget '{}' with no alias (see `alias map`)",
+            ImportStatusNewBudget => "OK (budget created)",
+            ImportStatusUnassigned => "to triage",
+            ImportPreviewUnassigned => "{} line(s) without an existing budget set aside, to triage later (menu option 19, `inbox list`).",
+            ImportUnassignedSummary => "{} line(s) set aside among the transactions to triage.",
+            PromptAliasBudget => "Existing budget for lines of unknown budget '{}'",
+            AliasMapped => "Imported budget '{}' now goes to budget '{}'.",
+            AliasUnmapped => "Alias '{}' removed.",
+            AliasNotMapped => "No alias '{}'.",
+            NoAliases => "No budget alias.",
+            ColAlias => "Alias",
+            ColRequestedBudget => "Requested budget",
+            MenuInbox => "19.Transactions to triage ({})",
+            NoUnassigned => "No transaction to triage.",
+            InboxAssign => "Assign a transaction to a budget",
+            InboxRemove => "Delete a transaction",
+            PromptUnassignedToAssign => "Transaction to assign",
+            PromptUnassignedToRemove => "Transaction to delete",
+            UnassignedAssigned => "Transaction #{} assigned to budget '{}'.",
+            UnassignedRemoved => "Transaction #{} deleted.",
+            UnassignedNotFound => "No transaction to triage #{}.",
+            ConfirmAssignSameBudget => "Also assign the {} other transaction(s) of budget '{}' to '{}', and remember this alias for future imports?",
+            ConfirmAssignSameCategory => "Also assign the {} other transaction(s) of category '{}' to '{}', and remember this mapping for future imports?",
+            ConfirmAssignSameAccount => "Also assign the {} other transaction(s) of account '{}' to '{}', and remember this mapping for future imports?",
+            ConfirmRemoveUnassigned => "Delete transaction '{}' ({})?",
+            ImportTransferSkipped => "transfer '{}' not imported",
+            ImportCategoryUnmapped => "category '{}' has no budget (see `category map`)",
+            PromptCategoryBudget => "Budget for transactions of category '{}'",
+            CategoryMapped => "Category '{}' mapped to budget '{}'.",
+            CategoryUnmapped => "Category '{}' is no longer mapped to a budget.",
+            CategoryNotMapped => "Category '{}' is not mapped to any budget.",
+            NoCategories => "No category mapped to a budget.",
+            ColCategory => "Category",
+            ImportAccountUnmapped => "account '{}' has no budget (see `account map`)",
+            PromptAccountBudget => "Budget for transactions of account '{}'",
+            AccountMapped => "Account '{}' mapped to budget '{}'.",
+            AccountUnmapped => "Account '{}' is no longer mapped to a budget.",
+            AccountNotMapped => "Account '{}' is not mapped to any budget.",
+            NoAccounts => "No account mapped to a budget.",
+            ColAccount => "Account",
+            ImportStatusDuplicate => "duplicate: skipped",
+            ImportStatusDuplicateForced => "duplicate: imported",
+            ImportStatusSkipped => "skipped: {}",
+            ImportStatusFailed => "error: {}",
+            ImportPreviewSummary => "Preview: {} transaction(s) to import, {} duplicate(s), {} line(s) skipped, {} line(s) failed.",
+            ConfirmImportDuplicates => "Also import the {} duplicate(s)?",
+            ImportDryRun => "Dry run: nothing was saved.",
+            ConfirmImport => "Import these transactions?",
+            ConfirmImportOverspend => "The import overspends the following budgets (\"warn\" mode): {}. Import their lines anyway?",
+            ImportEmptyName => "empty transaction name",
+            ImportEmptyAmount => "missing amount",
+            ImportIncomeSkipped => "income '{}' not imported",
+            ImportNoBudget => "no budget (no budget column, rule or default budget)",
+            ImportChooseFormat => "File format",
+            ImportStandardFormat => "Detected automatically (standard format, OFX, CAMT.053, MT940, QIF)",
+            ImportNewProfile => "New profile from this file…",
+            ColProfile => "Profile",
+            ColDelimiter => "Delimiter",
+            ColEncoding => "Encoding",
+            ColHeaderRow => "Header",
+            ColDateFormat => "Date format",
+            ColDecimal => "Decimals",
+            ColSign => "Sign",
+            ColColumns => "Columns",
+            SignExpensesNegative => "One amount, expenses negative",
+            SignExpensesPositive => "One amount, expenses positive",
+            SignDebitCredit => "Debit and credit columns",
+            ProfileNameEmpty => "The profile name cannot be empty.",
+            ProfileSameSeparators => "The field delimiter and the decimal separator must differ.",
+            ProfileBadDelimiter => "The field delimiter must be an ASCII character.",
+            ProfileMissingColumn => "Column '{}' is required.",
+            ProfileColumnNotFound => "column '{}' not found in the file header",
+            ProfileNotFound => "Import profile '{}' does not exist.",
+            ProfileSaved => "Import profile '{}' saved.",
+            ProfileRemoved => "Import profile '{}' removed.",
+            NoProfiles => "No import profiles.",
+            ProfileSample => "First lines of the file:",
+            ProfilePreview => "Import preview:",
+            ProfileNoColumn => "(none)",
+            PromptEncoding => "File encoding",
+            PromptDelimiter => "Field delimiter",
+            PromptHeaderRow => "Header row (0: none)",
+            PromptSign => "Amounts",
+            PromptDateColumn => "Date column",
+            PromptNameColumn => "Description column",
+            PromptAmountColumn => "Amount column",
+            PromptDebitColumn => "Debit column",
+            PromptCreditColumn => "Credit column",
+            PromptPayeeColumn => "Payee column",
+            PromptNotesColumn => "Notes column",
+            PromptBudgetColumn => "Budget column",
+            PromptDefaultBudget => "Default budget",
+            PromptDecimalSeparator => "Decimal separator",
+            PromptDateFormat => "Date format (e.g. %d/%m/%Y; empty: flexible parsing)",
+            PromptProfileName => "Profile name",
+            ConfirmSaveProfile => "Save this profile?",
+
+            MenuTitle => "=== Budget Manager Menu ===",
+            MenuAddBudget => "1.Add a budget",
+            MenuRemoveBudget => "2.Remove a budget",
+            MenuEditBudget => "3.Edit a budget",
+            MenuListBudgets => "4.Show all budgets",
+            MenuAddTransaction => "5.Add a transaction",
+            MenuRemoveTransaction => "6.Remove a transaction",
+            MenuEditTransaction => "7.Edit a transaction",
+            MenuShowRemaining => "8.Show the remaining balance of a budget",
+            MenuAlerts => "9.Manage a budget's alerts",
+            MenuPolicy => "10.Set a budget's overspending policy",
+            MenuSearch => "11.Search transactions",
+            MenuQuery => "12.Query transactions (e.g. budget:Groceries amount>50 after:2026-01-01)",
+            MenuDashboard => "13.Dashboard",
+            MenuConfig => "14.Settings",
+            MenuRepl => "15.Command mode (history, Tab completion)",
+            MenuQuickAdd => "16.Quick add (e.g. coffee 3.50 Leisure yesterday #work)",
+            MenuImport => "17.Import transactions (CSV)",
+            MenuQuit => "0.Quit",
+            MenuChoose => "Choose an option",
+            MenuInvalidOption => "Invalid option, please try again",
+            PromptBudgetName => "Budget name",
+            PromptBudgetTotal => "Budget total amount",
+            PromptNewBudgetName => "New budget name",
+            PromptNewBudgetTotal => "New budget total amount",
+            PromptBudget => "Budget",
+            PromptBudgetToRemove => "Budget to remove",
+            PromptBudgetToEdit => "Budget to edit",
+            PromptTransactionName => "Transaction name",
+            PromptTransactionAmount => "Transaction amount",
+            PromptNewTransactionName => "New transaction name",
+            PromptNewTransactionAmount => "New transaction amount",
+            PromptTransactionToRemove => "Transaction to remove",
+            PromptTransactionToEdit => "Transaction to edit",
+            PromptPayee => "Payee (optional)",
+            PromptNotes => "Notes (optional)",
+            PromptTags => "Space-separated tags (optional)",
+            PromptDate => "Date (optional, defaults to today; e.g. yesterday, 03/15)",
+            PromptQuickAdd => "Transaction (name, amount, budget, date, #tags)",
+            PromptImportFile => "CSV file path (columns budget_name, transaction_name, amount...)",
+            PromptPolicy => "Overspending policy",
+            PromptSearch => "Search",
+            PromptQuery => "Query",
+            PromptThreshold => "Threshold",
+            PromptRuleKind => "Rule type",
+            PromptRuleToEdit => "Rule to edit",
+            PromptRuleToRemove => "Rule to remove",
+            FilterHint => "{} (type to filter, Esc to cancel)",
+            SearchHelp => "Search: words, prefixes (amaz*) or quoted phrases (\"credit card\")",
+            FieldRequired => "This field cannot be empty",
+            NotANumber => "'{}' is not a valid number",
+            InvalidDate => "invalid date '{}' (e.g. 2026-03-15, 03/15/2026, 03/15, yesterday)",
+            InvalidAmount => "Invalid amount. Try again",
+            PossibleValues => "possible values: {}",
+            InvalidThresholdRetry => "Invalid threshold.",
+            ConfirmRemoveBudget => "Remove budget '{}' and all its transactions?",
+            ConfirmRemoveTransaction => "Remove transaction '{}' ({})?",
+            ConfirmRemoveRule => "Remove rule \"{} ({})\"?",
+            ConfirmOverspend => "Confirm overspending?",
+            BudgetAddedMenu => "Budget '{}' was added successfully!",
+            PolicySet => "Policy of budget '{}': {}",
+            NoBudgets => "No budget yet. Add a budget first (option 1).",
+            NoTransactions => "Budget '{}' has no transactions.",
+            NoDate => "no date",
+            OverspendWarning => "Warning: this transaction overspends budget '{}' ({} left after).",
+            OverspendBlocked => "Error: budget '{}' does not allow overspending ({} left after).",
+            AlertRulesOf => "Alert rules of budget '{}':",
+            RuleAdd => "Add a rule",
+            RuleEdit => "Edit a rule",
+            RuleRemove => "Remove a rule",
+            Back => "Back",
+            RuleAdded => "Rule added successfully!",
+            RuleEdited => "Rule updated successfully!",
+            RuleRemoved => "Rule removed successfully!",
+            NoRules => "No rule for this budget.",
+
+            DashHelp => "Tab: switch panel  a: add  e: edit  d: delete  q: quit",
+            DashNewBudget => "New budget",
+            DashEditBudget => "Edit budget",
+            DashNewTransaction => "New transaction",
+            DashEditTransaction => "Edit transaction",
+            DashFieldName => "Name",
+            DashFieldTotal => "Total amount",
+            DashFieldAmount => "Amount",
+            DashInputCancelled => "Input cancelled.",
+            DashSaved => "Saved.",
+            DashDone => "Done.",
+            DashConfirmTitle => "Confirmation",
+            DashConfirmRemoveBudget => "Remove budget '{}' and all its transactions? (y/n)",
+            DashConfirmRemoveTransaction => "Remove transaction '{}'? (y/n)",
+            DashConfirmOverspend => "Budget overspent ({} left after). Confirm? (y/n)",
+            DashNoBudgets => "No budget. Press 'a' to create one.",
+            DashBudgets => "Budgets",
+            DashTransactions => "Transactions",
+            DashAlerts => "Alerts",
+            DashNoAlerts => "No alerts.",
+            DashFormHelp => "Enter: submit   Tab: next field   Esc: cancel",
+            ReplWelcome => "Command mode: type 'help' to list commands, 'help <command>' for details, 'exit' to quit.",
+            ReplNested => "You are already in command mode.",
+            ReplUnclosedQuote => "Unclosed quote",
+            ReplHistoryError => "History not saved ({}): {}",
+            BatchLineInvalid => "Line {}: {}",
+            BatchInvalid => "{} invalid line(s): no command was run.",
+            BatchNotAllowed => "command '{}' is not allowed in a command file",
+            BatchNeedsYes => "command '{}' needs --yes in a command file",
+            BatchExportOutput => "export --output is not allowed in a command file: a rollback would not delete the written file",
+            BatchCommandFailed => "the command failed",
+            BatchDone => "OK",
+            BatchFailed => "Failed: {}",
+            BatchUndone => "Rolled back",
+            BatchNotRun => "Not run",
+            BatchCommitted => "{} command(s) run, changes saved.",
+            BatchRolledBack => "Failed at line {}: all changes were rolled back.",
+            QuickAddNoAmount => "missing amount (e.g. coffee 3.50 Leisure)",
+            QuickAddNoName => "missing transaction name",
+            QuickAddNoBudget => "no budget recognized (or several possible) in '{}'",
+            QuickAddPreview => "Preview:",
+            QuickAddConfirm => "Add this transaction?",
+            HelpApp => "Personal budget manager",
+            HelpDb => "SQLite database path (default: `database` key of the configuration)",
+            HelpConfigFile => "TOML configuration file",
+            HelpFormat => "Output format for lists and reports",
+            HelpLang => "Interface language (fr, en); otherwise BUDGET_LANG or LANG",
+            HelpAdd => "Quickly add a transaction: add BUDGET NAME AMOUNT",
+            HelpAmount => "Amount (12.50, 12,50, \"45 €\", 12.5+3.2...)",
+            HelpDate => "Date (2026-03-15, 15/03, hier...; today by default)",
+            HelpTag => "Tag (repeatable option)",
+            HelpConfirmOverspend => "Confirm an overspend for a budget in \"warn\" mode",
+            HelpQuick => "Add in a single entry (e.g. quick coffee 3.50 Leisure yesterday #work)",
+            HelpQuickYes => "Save without a preview to confirm (overspend accepted in \"warn\" mode)",
+            HelpBudget => "Manage budgets",
+            HelpTx => "Manage transactions",
+            HelpAlert => "Manage the alert rules of a budget",
+            HelpBalance => "Show the remaining balance of a budget",
+            HelpSearch => "Full-text search (words, prefixes amaz*, \"phrases\")",
+            HelpQuery => "Query the transactions (e.g. budget:Groceries amount>50 after:2026-01-01)",
+            HelpImport => "Import transactions from a CSV file",
+            HelpImportProfile => "Import profile to use for a bank export (see `profile list`)",
+            HelpImportAccount => "Source account of the rows, used to detect duplicates (default: the profile name)",
+            HelpImportDryRun => "Show the preview without saving anything",
+            HelpImportForce => "Also import the rows flagged as duplicates",
+            HelpImportYes => "Import without asking for confirmation after the preview (overspends of \"warn\" budgets accepted)",
+            HelpImportUnknownBudget => "Rows with an unknown budget: skip, create, map or inbox (default: configuration)",
+            HelpProfile => "Manage the import profiles of bank exports",
+            HelpAccount => "Map the accounts of bank statements (OFX, CAMT.053, MT940) to budgets",
+            HelpCategory => "Map the categories of imported files (QIF) to budgets",
+            HelpRule => "Automatic assignment rules (budget, category, tags) for transactions",
+            HelpAlias => "Map the unknown budget names of imported files to existing budgets",
+            HelpInbox => "Imported transactions whose budget does not exist, to be filed into a budget",
+            HelpExport => "Export the transactions as CSV",
+            HelpExportOutput => "Output file (standard output by default)",
+            HelpExportQuery => "Only export the transactions matching this query",
+            HelpDashboard => "Open the full-screen dashboard",
+            HelpConfig => "Show or change the configuration",
+            HelpRepl => "Interactive command mode (history, Tab completion)",
+            HelpBatch => "Run a file of commands (one per line) in a single transaction: all or nothing",
+            HelpReplFormat => "Output format for this command",
+            HelpConfigShow => "Show the configuration file and the active values",
+            HelpConfigSet => "Change a key (language, currency, database, colors, budgets.max_total_amount, budgets.default_alert_percent, import.unknown_budget, import.new_budget_amount)",
+            HelpBudgetAdd => "Add a budget",
+            HelpBudgetList => "Show all budgets",
+            HelpBudgetEdit => "Edit a budget",
+            HelpNewName => "New name (unchanged by default)",
+            HelpNewTotal => "New total amount (unchanged by default)",
+            HelpBudgetRm => "Remove a budget",
+            HelpBudgetPolicy => "Set the overspend policy (allow, warn, block)",
+            HelpTxAdd => "Add a transaction",
+            HelpTxList => "List the transactions",
+            HelpTxListBudget => "Restrict to one budget",
+            HelpTxEdit => "Edit a transaction",
+            HelpNewAmount => "New amount (unchanged by default)",
+            HelpTxRm => "Remove a transaction",
+            HelpAlertList => "List the rules of a budget",
+            HelpAlertAdd => "Add a rule (percent_used, remaining_below, projected_overspend, large_transaction)",
+            HelpRuleEdit => "Edit a rule",
+            HelpRuleRm => "Remove a rule",
+            HelpProfileList => "List the import profiles",
+            HelpProfileCreate => "Create a profile from a sample file (interactive wizard)",
+            HelpProfileRm => "Remove a profile",
+            HelpAccountList => "List the accounts mapped to a budget",
+            HelpAccountMap => "Map an account (statement identifier) to a budget",
+            HelpAccountUnmap => "Remove the mapping of an account",
+            HelpCategoryList => "List the categories mapped to a budget",
+            HelpCategoryMap => "Map a category (e.g. \"Food:Groceries\") to a budget",
+            HelpCategoryUnmap => "Remove the mapping of a category",
+            HelpAliasList => "List the budget aliases",
+            HelpAliasMap => "File the imported rows of budget NAME (nonexistent) into an existing budget",
+            HelpAliasUnmap => "Remove an alias",
+            HelpInboxList => "List the transactions to be filed",
+            HelpInboxAssign => "File a transaction into a budget",
+            HelpInboxRm => "Remove a transaction to be filed",
+            HelpRuleList => "List the rules, in the order they apply",
+            HelpRuleAdd => "Add a rule: at least one condition, and a budget, a category or tags",
+            HelpRuleContains => "Text contained in the label or the note (case and accents ignored)",
+            HelpRuleRegex => "Regular expression on the label or the note (case ignored)",
+            HelpRulePayee => "Text contained in the payee",
+            HelpRuleMin => "Minimum amount",
+            HelpRuleMax => "Maximum amount",
+            HelpRulePriority => "Rules with a higher priority apply first",
+            HelpRuleTest => "Show the rules that apply to a transaction, and their result",
+            HelpRuleSuggest => "Suggest rules from the transactions already filed",
+            HelpRuleMinCount => "Minimum number of similar transactions",
+            HelpRuleApply => "Create all the suggested rules",
+            ConfigDocTitle => "Budget manager configuration",
+            ConfigDocLanguage => "Interface language: \"fr\" or \"en\" (absent: BUDGET_LANG / LANG variables)",
+            ConfigDocCurrency => "Currency symbol shown with amounts (at most 5 characters)",
+            ConfigDocDatabase => "SQLite database file (the --db option takes precedence)",
+            ConfigDocColors => "Colors: \"auto\" (terminal only), \"always\" or \"never\"",
+            ConfigDocMaxTotal => "Maximum total amount of a budget",
+            ConfigDocAlertPercent => "Threshold of the alert rule created with each new budget (% spent, at most 100)",
+            ConfigDocUnknownBudget => "Rows whose budget does not exist: \"skip\" (ignored), \"create\" (budget created),\n\"map\" (existing budget asked during import) or \"inbox\" (set aside, to be filed)",
+            ConfigDocNewBudgetAmount => "Total amount of the budgets created by the import (positive, required with unknown_budget = \"create\")",
+        }
+    }
+}
@@ -0,0 +1,1014 @@
+use chrono::NaiveDate;
+use console::Style;
+use prettytable::{row, Cell, Row, Table};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use crate::config::{self, UnknownBudgetPolicy};
+use crate::db::{BudgetManager, Encoding, ImportProfile, NewTransaction, OverspendCheck, RuleSet, SignConvention};
+use crate::events::BudgetEvent;
+use crate::i18n::{self, tr};
+use crate::parse;
+
+mod camt;
+mod mt940;
+mod ofx;
+mod qif;
+
+// Une ligne du fichier CSV d'échange (voir `export::CSV_HEADERS`) ;
+// seules les colonnes transaction_name et amount sont obligatoires. Sans
+// budget_name, le budget vient des règles d'affectation ou du compte.
+// Montants et dates sont lus avec `parse` ("12,50", "15/03/2026"...)
+#[derive(Debug, Deserialize)]
+struct CsvTransaction {
+    #[serde(default)]
+    budget_name: String,
+    // `desc` : en-tête de l'ancien format d'import
+    #[serde(alias = "desc")]
+    transaction_name: String,
+    amount: String,
+    #[serde(default)]
+    transaction_date: Option<String>,
+    #[serde(default)]
+    payee: String,
+    #[serde(default)]
+    notes: String,
+    #[serde(default)]
+    tags: String,
+    // Compte d'origine, pour reconnaître les lignes déjà importées
+    #[serde(default)]
+    account: String,
+    #[serde(default)]
+    category: String,
+}
+
+// Bilan d'un import
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ImportReport {
+    pub imported: usize,
+    // Lignes lisibles refusées : budget inconnu, dépassement refusé, entrée d'argent...
+    pub skipped: usize,
+    // Lignes illisibles : colonnes manquantes, montant ou date invalide
+    pub failed: usize,
+    // Doublons non importés (comptés aussi dans `skipped`)
+    pub duplicates: usize,
+    // Lignes au budget inconnu mises de côté, à classer (politique "inbox")
+    pub parked: usize,
+}
+
+// Ligne d'un fichier, lue et convertie
+pub enum ImportRow {
+    // `budget_name` : budget indiqué par le fichier lui-même, vide sinon
+    // (il est alors cherché par `check_rows`)
+    Transaction { budget_name: String, transaction: NewTransaction },
+    // Ligne dont le budget n'existe pas (vide si aucun n'est trouvé), à mettre
+    // de côté parmi les transactions à classer (politique "inbox")
+    Unassigned { budget_name: String, transaction: NewTransaction },
+    // Ligne lisible qui n'est pas à importer, avec la raison
+    Skipped(String),
+}
+
+// Numéro de ligne dans le fichier, et la ligne convertie ou le message d'erreur
+pub type ReadRow = (usize, Result<ImportRow, String>);
+
+// Ligne vérifiée avant l'import : budget existant, doublon probable
+pub struct CheckedRow {
+    pub line: usize,
+    pub row: Result<ImportRow, String>,
+    // Même empreinte (voir `NewTransaction::fingerprint`) qu'une ligne précédente du fichier
+    // ou qu'une transaction enregistrée ; importée seulement si l'import est forcé
+    pub duplicate: bool,
+    // Le budget n'existe pas encore : il sera créé à l'import (politique "create")
+    pub new_budget: bool,
+}
+
+// Format d'un fichier à importer sans profil, reconnu à son contenu
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileFormat {
+    // Format d'échange (celui de `export`)
+    Csv,
+    // Relevé OFX/QFX
+    Ofx,
+    // Relevé ISO 20022 CAMT.053
+    Camt,
+    // Relevé SWIFT MT940
+    Mt940,
+    // Export QIF (Microsoft Money, Quicken, GnuCash)
+    Qif,
+}
+
+impl FileFormat {
+    pub fn detect(text: &str) -> Self {
+        if ofx::is_ofx(text) {
+            FileFormat::Ofx
+        } else if camt::is_camt(text) {
+            FileFormat::Camt
+        } else if mt940::is_mt940(text) {
+            FileFormat::Mt940
+        } else if qif::is_qif(text) {
+            FileFormat::Qif
+        } else {
+            FileFormat::Csv
+        }
+    }
+}
+
+// Budget des lignes qui n'indiquent pas de budget et qu'aucune règle ne classe :
+// selon leur catégorie (QIF, ou attribuée par une règle) ou leur compte
+// (relevés OFX, CAMT.053, MT940). `aliases` : budget existant des noms de
+// budget inconnus indiqués par le fichier.
+#[derive(Debug, Clone, Default)]
+pub struct BudgetMappings {
+    pub accounts: HashMap<String, String>,
+    pub categories: HashMap<String, String>,
+    pub aliases: HashMap<String, String>,
+    // Associations choisies pour cet import, enregistrées par `import_rows` avec
+    // les lignes : un aperçu ou un import abandonné n'écrit rien
+    pub chosen: Vec<NewMapping>,
+}
+
+// Association choisie pendant l'import : (compte, catégorie ou alias ; budget)
+#[derive(Debug, Clone, PartialEq)]
+pub enum NewMapping {
+    Account(String, String),
+    Category(String, String),
+    Alias(String, String),
+}
+
+impl BudgetMappings {
+    fn save_chosen(&self, budget_manager: &BudgetManager) -> rusqlite::Result<()> {
+        for mapping in &self.chosen {
+            match mapping {
+                NewMapping::Account(account, budget_name) => budget_manager.set_account_budget(account, budget_name)?,
+                NewMapping::Category(category, budget_name) => budget_manager.set_category_budget(category, budget_name)?,
+                NewMapping::Alias(alias, budget_name) => budget_manager.set_budget_alias(alias, budget_name)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// Comptes et catégories des lignes dont le budget reste inconnu, et noms de
+// budget inexistants, à associer à des budgets
+#[derive(Debug, Clone, Default)]
+pub struct FileSources {
+    pub accounts: Vec<String>,
+    pub categories: Vec<String>,
+    pub budgets: Vec<String>,
+}
+
+//==================================Read an import file====================================
+// Avec un profil, le fichier est un export bancaire décrit par ce profil ;
+// sans profil, son format est reconnu.
+pub fn read_file(file_path: &str, profile: Option<&ImportProfile>) -> Result<Vec<ReadRow>, Box<dyn Error>> {
+    let bytes = std::fs::read(file_path)?;
+    if let Some(profile) = profile {
+        return read_with_profile(&bytes, profile);
+    }
+    let text = decode(&bytes, detect_encoding(&bytes));
+    match FileFormat::detect(&text) {
+        FileFormat::Csv => read_standard_csv(&text),
+        FileFormat::Ofx => Ok(ofx::read(&text)),
+        FileFormat::Camt => Ok(camt::read(&text)?),
+        FileFormat::Mt940 => Ok(mt940::read(&text)),
+        FileFormat::Qif => Ok(qif::read(&text)),
+    }
+}
+
+// Catégories et comptes à associer à un budget : ceux des lignes qui n'ont ni
+// budget dans le fichier, ni règle qui en donne un, ni association enregistrée.
+// Une ligne avec une catégorie ne demande que sa catégorie. Les noms de budget
+// du fichier absents de `budget_names` et sans alias sont aussi relevés.
+pub fn unmapped_sources(rows: &[ReadRow], rules: &RuleSet, mappings: &BudgetMappings, budget_names: &[String]) -> FileSources {
+    let mut sources = FileSources::default();
+    for (_, row) in rows {
+        let Ok(ImportRow::Transaction { budget_name, transaction }) = row else { continue };
+        let budget_name = budget_name.trim();
+        if !budget_name.is_empty() {
+            let known = budget_names.iter().any(|name| name == budget_name) || mappings.aliases.contains_key(budget_name);
+            if !known && !sources.budgets.iter().any(|name| name == budget_name) {
+                sources.budgets.push(budget_name.to_string());
+            }
+            continue;
+        }
+        let assignment = rules.assign(transaction);
+        if assignment.budget_name.is_some() {
+            continue;
+        }
+        let category = match transaction.category.as_str() {
+            "" => assignment.category.unwrap_or_default(),
+            category => category.to_string(),
+        };
+        let (known, wanted, source) = match category.is_empty() {
+            false => (mappings.categories.contains_key(&category), &mut sources.categories, category),
+            true => (mappings.accounts.contains_key(&transaction.account), &mut sources.accounts, transaction.account.clone()),
+        };
+        if !known && !source.is_empty() && !wanted.contains(&source) {
+            wanted.push(source);
+        }
+    }
+    sources
+}
+
+// Numéro de la ligne qui contient la position `offset` du texte
+fn line_number(text: &str, offset: usize) -> usize {
+    text[..offset].matches('\n').count() + 1
+}
+
+fn read_standard_csv(text: &str) -> Result<Vec<ReadRow>, Box<dyn Error>> {
+    let mut reader = csv::ReaderBuilder::new().has_headers(true).from_reader(text.as_bytes());
+    let rows = reader
+        .deserialize::<CsvTransaction>()
+        .enumerate()
+        .map(|(index, result)| {
+            let row = result.map_err(|err| err.to_string()).and_then(|record| {
+                let amount = parse::amount(&record.amount)?;
+                let date = record.transaction_date.as_deref().filter(|date| !date.is_empty()).map(parse::date).transpose()?;
+                let transaction = NewTransaction {
+                    payee: record.payee,
+                    notes: record.notes,
+                    tags: record.tags.split_whitespace().map(String::from).collect(),
+                    transaction_date: date.map(|date| date.to_string()),
+                    account: record.account,
+                    category: record.category,
+                    ..NewTransaction::new(record.transaction_name, amount)
+                };
+                Ok(ImportRow::Transaction { budget_name: record.budget_name, transaction })
+            });
+            (index + 2, row) // en-tête, puis numérotation à partir de 1
+        })
+        .collect();
+    Ok(rows)
+}
+
+//==================================Check rows before importing====================================
+// Rien n'est écrit. Le budget d'une ligne est, dans l'ordre : celui du fichier,
+// celui des règles d'affectation, celui de sa catégorie, celui de son compte,
+// puis `default_budget` ; les règles complètent aussi catégorie et tags.
+// Les lignes sans budget sont marquées ignorées. Un budget qui n'existe pas est
+// remplacé par son alias, sinon traité selon `policy`.
+// `account` : compte d'origine des lignes qui n'en indiquent pas.
+pub fn check_rows(
+    budget_manager: &BudgetManager,
+    rows: Vec<ReadRow>,
+    account: &str,
+    mappings: &BudgetMappings,
+    default_budget: &str,
+    policy: UnknownBudgetPolicy,
+) -> rusqlite::Result<Vec<CheckedRow>> {
+    let budget_names: HashSet<String> = budget_manager.list_budgets()?.into_iter().map(|budget| budget.budget_name).collect();
+    let rules = budget_manager.assignment_rules()?;
+    let mut fingerprints = budget_manager.transaction_fingerprints()?;
+    let mut checked = Vec::with_capacity(rows.len());
+    for (line, row) in rows {
+        let mut new_budget = false;
+        let row = match row {
+            Ok(ImportRow::Transaction { budget_name, mut transaction }) => {
+                let own_account = !transaction.account.is_empty();
+                if !own_account {
+                    transaction.account = account.to_string();
+                }
+                let rule_budget = rules.apply(&mut transaction);
+                let budget_name = Some(budget_name)
+                    .filter(|name| !name.trim().is_empty())
+                    .or(rule_budget)
+                    .or_else(|| mappings.categories.get(&transaction.category).cloned())
+                    .or_else(|| mappings.accounts.get(&transaction.account).cloned())
+                    .or_else(|| Some(default_budget.trim().to_string()).filter(|name| !name.is_empty()))
+                    .map(|name| match budget_names.contains(&name) {
+                        true => name,
+                        false => mappings.aliases.get(&name).cloned().unwrap_or(name),
+                    });
+                match budget_name {
+                    // Sans budget demandé, la catégorie et le compte guideront le classement
+                    None if policy == UnknownBudgetPolicy::Inbox => Ok(ImportRow::Unassigned { budget_name: String::new(), transaction }),
+                    None if !transaction.category.is_empty() => Ok(ImportRow::Skipped(tr!(ImportCategoryUnmapped, transaction.category))),
+                    None if own_account => Ok(ImportRow::Skipped(tr!(ImportAccountUnmapped, transaction.account))),
+                    None => Ok(ImportRow::Skipped(tr!(ImportNoBudget).to_string())),
+                    Some(budget_name) if budget_names.contains(&budget_name) => Ok(ImportRow::Transaction { budget_name, transaction }),
+                    Some(budget_name) => match policy {
+                        UnknownBudgetPolicy::Skip => Ok(ImportRow::Skipped(tr!(BudgetNotFound, budget_name))),
+                        UnknownBudgetPolicy::Map => Ok(ImportRow::Skipped(tr!(ImportBudgetUnmapped, budget_name))),
+                        UnknownBudgetPolicy::Create => {
+                            new_budget = true;
+                            Ok(ImportRow::Transaction { budget_name, transaction })
+                        }
+                        UnknownBudgetPolicy::Inbox => Ok(ImportRow::Unassigned { budget_name, transaction }),
+                    },
+                }
+            }
+            row => row,
+        };
+        let duplicate = match &row {
+            Ok(ImportRow::Transaction { transaction, .. } | ImportRow::Unassigned { transaction, .. }) => {
+                !fingerprints.insert(transaction.fingerprint())
+            }
+            _ => false,
+        };
+        checked.push(CheckedRow { line, row, duplicate, new_budget });
+    }
+    Ok(checked)
+}
+
+// Tableau d'aperçu, une ligne par ligne du fichier, suivi du décompte.
+// `force` : les doublons seront importés.
+pub fn print_preview(rows: &[CheckedRow], force: bool) {
+    let mut table = Table::new();
+    table.set_titles(row![tr!(ColLine), tr!(ColBudget), tr!(ColTransaction), tr!(ColAmount), tr!(ColDate), tr!(ColResult)]);
+    let (mut ready, mut duplicates, mut skipped, mut failed, mut parked) = (0, 0, 0, 0, 0);
+    for checked in rows {
+        match &checked.row {
+            Ok(ImportRow::Transaction { budget_name, transaction } | ImportRow::Unassigned { budget_name, transaction }) => {
+                let unassigned = matches!(checked.row, Ok(ImportRow::Unassigned { .. }));
+                let date = transaction.transaction_date.as_deref().map(i18n::date).unwrap_or_default();
+                let (status, color) = match checked.duplicate {
+                    true if force => (tr!(ImportStatusDuplicateForced), "Fy"),
+                    true => (tr!(ImportStatusDuplicate), "Fy"),
+                    false if unassigned => (tr!(ImportStatusUnassigned), "Fc"),
+                    false if checked.new_budget => (tr!(ImportStatusNewBudget), "Fg"),
+                    false => ("OK", "Fg"),
+                };
+                if checked.duplicate {
+                    duplicates += 1;
+                }
+                if (!checked.duplicate || force) && unassigned {
+                    parked += 1;
+                } else if !checked.duplicate || force {
+                    ready += 1;
+                }
+                table.add_row(preview_row(
+                    checked.line,
+                    [budget_name, &transaction.transaction_name, &i18n::amount(transaction.amount), &date],
+                    Cell::new(status).style_spec(color),
+                ));
+            }
+            Ok(ImportRow::Skipped(reason)) => {
+                skipped += 1;
+                table.add_row(preview_row(checked.line, [""; 4], Cell::new(&tr!(ImportStatusSkipped, reason)).style_spec("Fy")));
+            }
+            Err(err) => {
+                failed += 1;
+                table.add_row(preview_row(checked.line, [""; 4], Cell::new(&tr!(ImportStatusFailed, err)).style_spec("Fr")));
+            }
+        }
+    }
+    table.printstd();
+    println!("{}", tr!(ImportPreviewSummary, ready, duplicates, skipped, failed));
+    if parked > 0 {
+        println!("{}", tr!(ImportPreviewUnassigned, parked));
+    }
+}
+
+fn preview_row(line: usize, values: [&str; 4], status: Cell) -> Row {
+    let mut cells = vec![Cell::new(&line.to_string())];
+    cells.extend(values.iter().map(|value| Cell::new(value)));
+    cells.push(status);
+    Row::new(cells)
+}
+
+// Budgets en mode "warn" que les lignes à importer feraient passer en négatif :
+// l'import demande une seule confirmation pour l'ensemble du fichier
+pub fn overspent_budgets(budget_manager: &BudgetManager, rows: &[CheckedRow], force: bool) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut totals: Vec<(&str, f64)> = Vec::new();
+    for checked in rows.iter().filter(|checked| force || !checked.duplicate) {
+        if let Ok(ImportRow::Transaction { budget_name, transaction }) = &checked.row {
+            match totals.iter_mut().find(|(name, _)| *name == budget_name.as_str()) {
+                Some((_, total)) => *total += transaction.amount,
+                None => totals.push((budget_name, transaction.amount)),
+            }
+        }
+    }
+    let mut budgets = Vec::new();
+    for (budget_name, total) in totals {
+        // Un budget créé par l'import suit la politique par défaut
+        if budget_manager.get_budget_id(budget_name)?.is_none() {
+            continue;
+        }
+        if let OverspendCheck::NeedsConfirmation { .. } = budget_manager.check_overspend(budget_name, total)? {
+            budgets.push(budget_name.to_string());
+        }
+    }
+    Ok(budgets)
+}
+
+//==================================Import checked rows====================================
+// Toutes les lignes sont importées dans une seule transaction SQLite : une erreur
+// de la base annule l'import entier. Les lignes ignorées ou en erreur, déjà
+// signalées par l'aperçu, sont seulement comptées ; les doublons sont ignorés
+// sauf avec `force`. Les budgets inconnus sont créés ou leurs lignes mises de
+// côté selon la vérification (voir `check_rows`).
+// Les associations choisies pour l'import (`mappings.chosen`) sont enregistrées
+// dans la même transaction.
+// `overspend_confirmed` : dépassements acceptés pour les budgets en mode "warn"
+pub fn import_rows(
+    budget_manager: &BudgetManager,
+    rows: Vec<CheckedRow>,
+    mappings: &BudgetMappings,
+    force: bool,
+    overspend_confirmed: bool,
+) -> Result<ImportReport, Box<dyn Error>> {
+    let warning_style = Style::new().yellow();
+    let mut report = ImportReport::default();
+    // Budgets touchés et plus grosse transaction importée dans chacun
+    let mut touched: Vec<(String, f64)> = Vec::new();
+
+    budget_manager.atomically(|| -> Result<(), Box<dyn Error>> {
+        mappings.save_chosen(budget_manager)?;
+        for CheckedRow { line, row, duplicate, new_budget } in rows {
+            match row {
+                Err(_) => report.failed += 1,
+                Ok(ImportRow::Skipped(_)) => report.skipped += 1,
+                Ok(ImportRow::Transaction { .. } | ImportRow::Unassigned { .. }) if duplicate && !force => {
+                    report.skipped += 1;
+                    report.duplicates += 1;
+                }
+                Ok(ImportRow::Unassigned { budget_name, transaction }) => {
+                    budget_manager.park_transaction(&budget_name, &transaction)?;
+                    report.parked += 1;
+                }
+                Ok(ImportRow::Transaction { budget_name, transaction }) => {
+                    // Première ligne d'un budget à créer
+                    if new_budget && budget_manager.get_budget_id(&budget_name)?.is_none() {
+                        budget_manager.add_budget(&budget_name, config::get().import.new_budget_amount)?;
+                    }
+                    let amount = transaction.amount;
+                    match budget_manager.insert_transaction(&budget_name, transaction, overspend_confirmed) {
+                        Ok(()) => {
+                            report.imported += 1;
+                            match touched.iter_mut().find(|(name, _)| *name == budget_name) {
+                                Some((_, largest)) => *largest = largest.max(amount),
+                                None => touched.push((budget_name, amount)),
+                            }
+                        }
+                        // Règle métier (dépassement refusé...) : ligne ignorée
+                        Err(rusqlite::Error::InvalidParameterName(reason)) => {
+                            println!("{}", warning_style.apply_to(tr!(ImportLineSkipped, line, reason)));
+                            report.skipped += 1;
+                        }
+                        Err(err) => return Err(err.into()),
+                    }
+                }
+            }
+        }
+        Ok(())
+    })?;
+
+    // Alertes évaluées une seule fois par budget, une fois l'import enregistré
+    for (budget_name, largest) in &touched {
+        budget_manager.evaluate_alert_rules(budget_name, Some(*largest))?;
+    }
+    budget_manager.emit(BudgetEvent::ImportFinished {
+        imported: report.imported,
+        skipped: report.skipped,
+        failed: report.failed,
+    });
+    Ok(report)
+}
+
+//==================================Read a bank export with a profile====================================
+pub fn read_with_profile(bytes: &[u8], profile: &ImportProfile) -> Result<Vec<ReadRow>, Box<dyn Error>> {
+    let records = read_records(&decode(bytes, profile.encoding), profile.delimiter)?;
+    let headers = match profile.header_row {
+        0 => Vec::new(),
+        row => records.get(row - 1).map(|(_, fields)| fields.clone()).unwrap_or_default(),
+    };
+    let columns = ProfileColumns::resolve(profile, &headers)?;
+    Ok(records
+        .iter()
+        .skip(profile.header_row)
+        .map(|(line, fields)| (*line, columns.row(profile, fields)))
+        .collect())
+}
+
+// Lignes non vides du fichier : (numéro de ligne, champs)
+pub fn read_records(text: &str, delimiter: char) -> Result<Vec<(usize, Vec<String>)>, csv::Error> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .delimiter(delimiter as u8)
+        .from_reader(text.as_bytes());
+    let mut records = Vec::new();
+    for record in reader.records() {
+        let record = record?;
+        if record.iter().all(|field| field.trim().is_empty()) {
+            continue;
+        }
+        let line = record.position().map_or(0, |position| position.line() as usize);
+        records.push((line, record.iter().map(String::from).collect()));
+    }
+    Ok(records)
+}
+
+// Position des colonnes du profil dans le fichier
+struct ProfileColumns {
+    date: Option<usize>,
+    name: Option<usize>,
+    amount: Option<usize>,
+    debit: Option<usize>,
+    credit: Option<usize>,
+    payee: Option<usize>,
+    notes: Option<usize>,
+    budget: Option<usize>,
+}
+
+impl ProfileColumns {
+    // Une colonne est désignée par son nom dans l'en-tête ou par son numéro
+    fn resolve(profile: &ImportProfile, headers: &[String]) -> Result<Self, String> {
+        let find = |reference: &str| -> Result<Option<usize>, String> {
+            let reference = reference.trim();
+            if reference.is_empty() {
+                return Ok(None);
+            }
+            if let Some(number) = reference.parse::<usize>().ok().filter(|number| *number > 0) {
+                return Ok(Some(number - 1));
+            }
+            match headers.iter().position(|header| header.trim().eq_ignore_ascii_case(reference)) {
+                Some(index) => Ok(Some(index)),
+                None => Err(tr!(ProfileColumnNotFound, reference)),
+            }
+        };
+        Ok(Self {
+            date: find(&profile.date_column)?,
+            name: find(&profile.name_column)?,
+            amount: find(&profile.amount_column)?,
+            debit: find(&profile.debit_column)?,
+            credit: find(&profile.credit_column)?,
+            payee: find(&profile.payee_column)?,
+            notes: find(&profile.notes_column)?,
+            budget: find(&profile.budget_column)?,
+        })
+    }
+
+    fn row(&self, profile: &ImportProfile, fields: &[String]) -> Result<ImportRow, String> {
+        let field = |column: Option<usize>| column.and_then(|index| fields.get(index)).map_or("", |value| value.trim());
+        let amount_in = |column: Option<usize>| profile_amount(field(column), profile.decimal_separator);
+
+        let name = field(self.name);
+        if name.is_empty() {
+            return Err(tr!(ImportEmptyName).to_string());
+        }
+        let amount = match profile.sign {
+            SignConvention::ExpensesNegative => -amount_in(self.amount)?.ok_or_else(|| tr!(ImportEmptyAmount).to_string())?,
+            SignConvention::ExpensesPositive => amount_in(self.amount)?.ok_or_else(|| tr!(ImportEmptyAmount).to_string())?,
+            // Certaines banques écrivent les débits en négatif : seule la colonne compte
+            SignConvention::DebitCredit => match (amount_in(self.debit)?, amount_in(self.credit)?) {
+                (None, None) => return Err(tr!(ImportEmptyAmount).to_string()),
+                (debit, credit) => debit.unwrap_or(0.0).abs() - credit.unwrap_or(0.0).abs(),
+            },
+        };
+        if amount < 0.0 {
+            return Ok(ImportRow::Skipped(tr!(ImportIncomeSkipped, name)));
+        }
+
+        let date = match field(self.date) {
+            "" => None,
+            date if profile.date_format.is_empty() => Some(parse::date(date)?),
+            date => Some(NaiveDate::parse_from_str(date, &profile.date_format).map_err(|_| tr!(InvalidDate, date))?),
+        };
+        // Sans colonne budget, le budget par défaut du profil ne sert qu'après
+        // les règles d'affectation (voir `check_rows`)
+        let budget_name = field(self.budget);
+
+        let transaction = NewTransaction {
+            payee: field(self.payee).to_string(),
+            notes: field(self.notes).to_string(),
+            transaction_date: date.map(|date| date.to_string()),
+            ..NewTransaction::new(name.to_string(), amount)
+        };
+        Ok(ImportRow::Transaction { budget_name: budget_name.to_string(), transaction })
+    }
+}
+
+// Montant écrit avec le séparateur décimal du profil ("1.234,56", "-12,50 €", "12,50-").
+// Les autres séparateurs, espaces et symboles sont ignorés ; None si la case est vide.
+fn profile_amount(text: &str, decimal_separator: char) -> Result<Option<f64>, String> {
+    if text.is_empty() {
+        return Ok(None);
+    }
+    let invalid = || tr!(NotANumber, text);
+    let kept: String = text
+        .chars()
+        .filter(|c| c.is_ascii_digit() || *c == decimal_separator || matches!(c, '-' | '+'))
+        .map(|c| if c == decimal_separator { '.' } else { c })
+        .collect();
+    if !kept.chars().any(|c| c.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    // Signe placé après le nombre
+    let value = match kept.strip_suffix('-') {
+        Some(number) => -number.parse::<f64>().map_err(|_| invalid())?,
+        None => kept.parse::<f64>().map_err(|_| invalid())?,
+    };
+    Ok(Some(value))
+}
+
+//==================================Decoding and detection====================================
+pub fn decode(bytes: &[u8], encoding: Encoding) -> String {
+    match encoding {
+        Encoding::Utf8 => String::from_utf8_lossy(bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes)).into_owned(),
+        Encoding::Windows1252 => bytes.iter().map(|byte| windows_1252_char(*byte)).collect(),
+    }
+}
+
+// Latin-1, sauf 0x80-0x9F qui portent €, guillemets typographiques, Œ...
+fn windows_1252_char(byte: u8) -> char {
+    const HIGH: [char; 32] = [
+        '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8D}', 'Ž', '\u{8F}',
+        '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9D}', 'ž', 'Ÿ',
+    ];
+    match byte {
+        0x80..=0x9F => HIGH[(byte - 0x80) as usize],
+        _ => byte as char,
+    }
+}
+
+// UTF-8 si le fichier est valide, Windows-1252 sinon
+pub fn detect_encoding(bytes: &[u8]) -> Encoding {
+    if std::str::from_utf8(bytes).is_ok() { Encoding::Utf8 } else { Encoding::Windows1252 }
+}
+
+// Séparateur présent le même nombre de fois sur le plus de lignes
+pub fn detect_delimiter(text: &str) -> char {
+    let lines: Vec<&str> = text.lines().filter(|line| !line.trim().is_empty()).take(20).collect();
+    let mut best = (',', 0, 0);
+    for delimiter in [';', ',', '\t', '|'] {
+        let counts: Vec<usize> = lines.iter().map(|line| line.matches(delimiter).count()).collect();
+        let Some(&most_common) = counts.iter().filter(|count| **count > 0).max_by_key(|count| counts.iter().filter(|c| c == count).count()) else {
+            continue;
+        };
+        let consistent = counts.iter().filter(|count| **count == most_common).count();
+        if (consistent, most_common) > (best.1, best.2) {
+            best = (delimiter, consistent, most_common);
+        }
+    }
+    best.0
+}
+
+// Première ligne qui a le nombre de champs le plus courant (les préambules sont plus courts)
+pub fn detect_header_row(records: &[(usize, Vec<String>)]) -> usize {
+    let lengths: Vec<usize> = records.iter().map(|(_, fields)| fields.len()).collect();
+    let most_common = lengths.iter().copied().max_by_key(|len| lengths.iter().filter(|l| *l == len).count()).unwrap_or(0);
+    lengths.iter().position(|len| *len == most_common).map_or(0, |index| index + 1)
+}
+
+// Format chrono qui lit toutes les dates d'exemple
+pub fn detect_date_format(samples: &[&str]) -> Option<&'static str> {
+    const FORMATS: [&str; 8] = ["%d/%m/%Y", "%Y-%m-%d", "%d.%m.%Y", "%d-%m-%Y", "%m/%d/%Y", "%d/%m/%y", "%Y%m%d", "%d.%m.%y"];
+    let samples: Vec<&str> = samples.iter().map(|sample| sample.trim()).filter(|sample| !sample.is_empty()).collect();
+    if samples.is_empty() {
+        return None;
+    }
+    FORMATS.into_iter().find(|format| samples.iter().all(|sample| NaiveDate::parse_from_str(sample, format).is_ok()))
+}
+
+// Virgule décimale si un montant d'exemple se termine par une virgule et un ou deux chiffres
+pub fn detect_decimal_separator(samples: &[&str]) -> char {
+    let comma_decimal = samples.iter().any(|sample| {
+        let sample = sample.trim().trim_end_matches(|c: char| !c.is_ascii_digit());
+        sample.rsplit_once(',').is_some_and(|(_, decimals)| (1..=2).contains(&decimals.len()) && decimals.chars().all(|c| c.is_ascii_digit()))
+    });
+    if comma_decimal { ',' } else { '.' }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::OverspendPolicy;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    // Fichier temporaire propre à chaque test
+    fn temp_file(name: &str, text: &str) -> String {
+        let path = std::env::temp_dir().join(format!("budget_import_{}_{}", std::process::id(), name));
+        std::fs::write(&path, text).unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    fn import_csv(manager: &BudgetManager, path: &str) -> Result<ImportReport, Box<dyn Error>> {
+        let rows = check_rows(manager, read_file(path, None)?, "", &BudgetMappings::default(), "", UnknownBudgetPolicy::Skip)?;
+        import_rows(manager, rows, &BudgetMappings::default(), false, false)
+    }
+
+    fn spent(manager: &BudgetManager, budget_name: &str) -> f64 {
+        manager.get_budget(budget_name).unwrap().unwrap().spent_amount
+    }
+
+    #[test]
+    fn counts_imported_skipped_and_failed_lines() {
+        let mut manager = BudgetManager::new(":memory:").unwrap();
+        manager.add_budget("Courses", 500.0).unwrap();
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let received = Rc::clone(&events);
+        manager.subscribe(move |event: &BudgetEvent| received.borrow_mut().push(event.clone()));
+
+        let path = temp_file(
+            "report.csv",
+            "budget_name,desc,amount,transaction_date,tags\n\
+             Courses,Marché,\"12,50\",2026-03-15,frais bio\n\
+             Courses,Boulangerie,3.20,,\n\
+             Inconnu,Cinéma,9,,\n\
+             Courses,Pharmacie,beaucoup,,\n\
+             Courses,Garage,40,2026-02-31,\n\
+             Courses\n",
+        );
+        let report = import_csv(&manager, &path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(report, ImportReport { imported: 2, skipped: 1, failed: 3, duplicates: 0, parked: 0 });
+        assert_eq!(spent(&manager, "Courses"), 15.7);
+        assert_eq!(events.borrow().last(), Some(&BudgetEvent::ImportFinished { imported: 2, skipped: 1, failed: 3 }));
+    }
+
+    #[test]
+    fn a_database_error_cancels_the_whole_import() {
+        let db_path = temp_file("atomic.db", "");
+        let manager = BudgetManager::new(&db_path).unwrap();
+        manager.add_budget("Courses", 500.0).unwrap();
+        rusqlite::Connection::open(&db_path)
+            .unwrap()
+            .execute_batch(
+                "CREATE TRIGGER panne BEFORE INSERT ON transactions WHEN new.transaction_name = 'Panne'
+                 BEGIN SELECT RAISE(ABORT, 'disque plein'); END;",
+            )
+            .unwrap();
+
+        let path = temp_file("atomic.csv", "budget_name,transaction_name,amount\nCourses,Marché,12\nCourses,Panne,1\n");
+        assert!(import_csv(&manager, &path).is_err());
+        assert_eq!(spent(&manager, "Courses"), 0.0);
+        drop(manager);
+        std::fs::remove_file(path).unwrap();
+        std::fs::remove_file(db_path).unwrap();
+    }
+
+    fn bank_profile() -> ImportProfile {
+        ImportProfile {
+            id: 0,
+            name: "Banque".to_string(),
+            delimiter: ';',
+            encoding: Encoding::Windows1252,
+            header_row: 2,
+            date_format: "%d/%m/%Y".to_string(),
+            decimal_separator: ',',
+            sign: SignConvention::ExpensesNegative,
+            date_column: "Date".to_string(),
+            name_column: "Libellé".to_string(),
+            amount_column: "3".to_string(),
+            debit_column: String::new(),
+            credit_column: String::new(),
+            payee_column: String::new(),
+            notes_column: String::new(),
+            budget_column: String::new(),
+            default_budget: "Courses".to_string(),
+        }
+    }
+
+    #[test]
+    fn profile_amounts_follow_the_decimal_separator() {
+        assert_eq!(profile_amount("", ','), Ok(None));
+        assert_eq!(profile_amount("1.234,56", ','), Ok(Some(1234.56)));
+        assert_eq!(profile_amount("-12,50 €", ','), Ok(Some(-12.5)));
+        assert_eq!(profile_amount("12,50-", ','), Ok(Some(-12.5)));
+        assert_eq!(profile_amount("1,234.56", '.'), Ok(Some(1234.56)));
+        assert!(profile_amount("n/a", ',').is_err());
+    }
+
+    #[test]
+    fn reads_a_bank_export_with_its_profile() {
+        // Préambule, en-tête en deuxième ligne, fichier Windows-1252 ("é" = 0xE9)
+        let bytes = b"Compte 123;;\nDate;Libell\xE9;Montant\n15/03/2026;Caf\xE9;-3,50\n16/03/2026;Salaire;1200,00\n;Sans date;-2\n31/02/2026;Garage;-40\n";
+        let rows = read_with_profile(bytes, &bank_profile()).unwrap();
+        assert_eq!(rows.len(), 4);
+
+        let Ok(ImportRow::Transaction { budget_name, transaction }) = &rows[0].1 else { panic!("ligne 3 non lue") };
+        assert_eq!(rows[0].0, 3);
+        // Le budget par défaut du profil est appliqué par `check_rows`
+        assert_eq!(budget_name, "");
+        assert_eq!((transaction.transaction_name.as_str(), transaction.amount), ("Café", 3.5));
+        assert_eq!(transaction.transaction_date.as_deref(), Some("2026-03-15"));
+        assert!(matches!(rows[1].1, Ok(ImportRow::Skipped(_))));
+        assert!(matches!(&rows[2].1, Ok(ImportRow::Transaction { transaction, .. }) if transaction.transaction_date.is_none()));
+        assert!(rows[3].1.is_err());
+
+        let unknown_column = ImportProfile { date_column: "Jour".to_string(), ..bank_profile() };
+        assert!(read_with_profile(bytes, &unknown_column).is_err());
+    }
+
+    #[test]
+    fn debit_and_credit_columns_give_the_sign() {
+        let profile = ImportProfile {
+            header_row: 1,
+            sign: SignConvention::DebitCredit,
+            amount_column: String::new(),
+            debit_column: "Débit".to_string(),
+            credit_column: "Crédit".to_string(),
+            ..bank_profile()
+        };
+        let text = "Date;Libellé;Débit;Crédit\n15/03/2026;Café;-3,50;\n16/03/2026;Salaire;;1200\n17/03/2026;Vide;;\n";
+        let rows = read_with_profile(text.as_bytes(), &ImportProfile { encoding: Encoding::Utf8, ..profile }).unwrap();
+        assert!(matches!(&rows[0].1, Ok(ImportRow::Transaction { transaction, .. }) if transaction.amount == 3.5));
+        assert!(matches!(rows[1].1, Ok(ImportRow::Skipped(_))));
+        assert!(rows[2].1.is_err());
+    }
+
+    #[test]
+    fn detects_the_format_of_a_bank_export() {
+        assert_eq!(detect_encoding("Café".as_bytes()), Encoding::Utf8);
+        assert_eq!(detect_encoding(b"Caf\xE9"), Encoding::Windows1252);
+        assert_eq!(decode(b"\x80 \xE9t\xE9", Encoding::Windows1252), "€ été");
+
+        let text = "Compte;123\nDate;Libellé;Montant\n15/03/2026;Café, thé;-3,50\n16/03/2026;Pain;-1,20\n";
+        assert_eq!(detect_delimiter(text), ';');
+        assert_eq!(detect_header_row(&read_records(text, ';').unwrap()), 2);
+        assert_eq!(detect_date_format(&["15/03/2026", "01/12/2025"]), Some("%d/%m/%Y"));
+        assert_eq!(detect_date_format(&["2026-03-15", ""]), Some("%Y-%m-%d"));
+        assert_eq!(detect_date_format(&["demain"]), None);
+        assert_eq!(detect_decimal_separator(&["-3,50", "1 200,00 €"]), ',');
+        assert_eq!(detect_decimal_separator(&["1,234.56", "-3.5"]), '.');
+    }
+
+    #[test]
+    fn preview_marks_unknown_budgets_and_duplicates() {
+        let manager = BudgetManager::new(":memory:").unwrap();
+        manager.add_budget("Courses", 500.0).unwrap();
+        let marche = NewTransaction { transaction_date: Some("2026-03-15".to_string()), ..NewTransaction::new("Marché".to_string(), 12.5) };
+        manager.add_transaction("Courses", marche, false).unwrap();
+
+        let path = temp_file(
+            "preview.csv",
+            "budget_name,transaction_name,amount,transaction_date\n\
+             Courses,Marché,\"12,50\",2026-03-15\n\
+             Courses,Pain,1.20,2026-03-16\n\
+             Courses,Pain,1.20,2026-03-16\n\
+             Inconnu,Cinéma,9,\n\
+             Courses,Garage,quarante,\n",
+        );
+        let rows = check_rows(&manager, read_file(&path, None).unwrap(), "", &BudgetMappings::default(), "", UnknownBudgetPolicy::Skip).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        let statuses: Vec<(usize, &str)> = rows
+            .iter()
+            .map(|checked| {
+                let status = match &checked.row {
+                    Ok(ImportRow::Transaction { .. }) if checked.duplicate => "doublon",
+                    Ok(ImportRow::Transaction { .. }) => "ok",
+                    Ok(ImportRow::Unassigned { .. }) => "à classer",
+                    Ok(ImportRow::Skipped(_)) => "ignorée",
+                    Err(_) => "erreur",
+                };
+                (checked.line, status)
+            })
+            .collect();
+        assert_eq!(statuses, [(2, "doublon"), (3, "ok"), (4, "doublon"), (5, "ignorée"), (6, "erreur")]);
+        // L'aperçu n'écrit rien
+        assert_eq!(spent(&manager, "Courses"), 12.5);
+    }
+
+    #[test]
+    fn duplicates_are_imported_only_when_forced() {
+        let manager = BudgetManager::new(":memory:").unwrap();
+        manager.add_budget("Courses", 500.0).unwrap();
+        let path = temp_file(
+            "force.csv",
+            "budget_name,transaction_name,amount,transaction_date,account\n\
+             Courses,CB Café-Élysée,3.50,2026-03-15,\n\
+             Courses,cb cafe elysee,3.5,2026-03-15,\n\
+             Courses,CB Café-Élysée,3.50,2026-03-15,Livret\n",
+        );
+        let rows = || check_rows(&manager, read_file(&path, None).unwrap(), "Compte courant", &BudgetMappings::default(), "", UnknownBudgetPolicy::Skip).unwrap();
+
+        let report = import_rows(&manager, rows(), &BudgetMappings::default(), false, false).unwrap();
+        assert_eq!(report, ImportReport { imported: 2, skipped: 1, failed: 0, duplicates: 1, parked: 0 });
+        // Réimport du même fichier : tout est déjà là
+        assert!(rows().iter().all(|checked| checked.duplicate));
+        let report = import_rows(&manager, rows(), &BudgetMappings::default(), true, false).unwrap();
+        assert_eq!(report, ImportReport { imported: 3, skipped: 0, failed: 0, duplicates: 0, parked: 0 });
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(spent(&manager, "Courses"), 17.5);
+    }
+
+    // Une ligne pour un budget existant, deux pour "Course" et une pour "Cadeaux", qui n'existent pas
+    fn unknown_budgets(manager: &BudgetManager, policy: UnknownBudgetPolicy, aliases: &[(&str, &str)]) -> (Vec<CheckedRow>, ImportReport) {
+        let path = temp_file(
+            &format!("unknown_{}.csv", policy.as_str()),
+            "budget_name,transaction_name,amount,transaction_date\n\
+             Courses,Marché,12,2026-03-15\n\
+             Course,Pain,1.20,2026-03-16\n\
+             Course,Lait,0.90,2026-03-16\n\
+             Cadeaux,Livre,20,2026-03-17\n",
+        );
+        let mappings = BudgetMappings {
+            aliases: aliases.iter().map(|(alias, budget)| (alias.to_string(), budget.to_string())).collect(),
+            ..BudgetMappings::default()
+        };
+        let check = || check_rows(manager, read_file(&path, None).unwrap(), "", &mappings, "", policy).unwrap();
+        let preview = check();
+        let report = import_rows(manager, check(), &mappings, false, false).unwrap();
+        std::fs::remove_file(path).unwrap();
+        (preview, report)
+    }
+
+    fn courses() -> BudgetManager {
+        let manager = BudgetManager::new(":memory:").unwrap();
+        manager.add_budget("Courses", 500.0).unwrap();
+        manager
+    }
+
+    #[test]
+    fn unknown_budgets_are_skipped_or_created() {
+        let manager = courses();
+        let (_, report) = unknown_budgets(&manager, UnknownBudgetPolicy::Skip, &[]);
+        assert_eq!(report, ImportReport { imported: 1, skipped: 3, failed: 0, duplicates: 0, parked: 0 });
+        assert_eq!(manager.list_budgets().unwrap().len(), 1);
+
+        let manager = courses();
+        let (preview, report) = unknown_budgets(&manager, UnknownBudgetPolicy::Create, &[]);
+        assert_eq!(preview.iter().map(|checked| checked.new_budget).collect::<Vec<_>>(), [false, true, true, true]);
+        assert_eq!(report, ImportReport { imported: 4, skipped: 0, failed: 0, duplicates: 0, parked: 0 });
+        assert_eq!(spent(&manager, "Course"), 2.1);
+        assert_eq!(spent(&manager, "Cadeaux"), 20.0);
+    }
+
+    #[test]
+    fn aliases_send_unknown_budgets_to_existing_ones() {
+        let manager = courses();
+        let (_, report) = unknown_budgets(&manager, UnknownBudgetPolicy::Map, &[("Course", "Courses")]);
+        // "Cadeaux" reste sans alias : la ligne attend d'être associée
+        assert_eq!(report, ImportReport { imported: 3, skipped: 1, failed: 0, duplicates: 0, parked: 0 });
+        assert_eq!(spent(&manager, "Courses"), 14.1);
+        assert_eq!(manager.get_budget("Course").unwrap().map(|budget| budget.budget_name), None);
+    }
+
+    #[test]
+    fn inbox_parks_the_lines_until_they_are_assigned() {
+        let manager = courses();
+        let (_, report) = unknown_budgets(&manager, UnknownBudgetPolicy::Inbox, &[]);
+        assert_eq!(report, ImportReport { imported: 1, skipped: 0, failed: 0, duplicates: 0, parked: 3 });
+        let parked = manager.list_unassigned().unwrap();
+        assert_eq!(parked.iter().map(|t| (t.budget_name.as_str(), t.transaction_name.as_str())).collect::<Vec<_>>(),
+            [("Cadeaux", "Livre"), ("Course", "Pain"), ("Course", "Lait")]);
+
+        // Les lignes mises de côté sont reconnues au réimport
+        let (preview, report) = unknown_budgets(&manager, UnknownBudgetPolicy::Inbox, &[]);
+        assert!(preview.iter().all(|checked| checked.duplicate));
+        assert_eq!((report.duplicates, report.parked), (4, 0));
+
+        assert!(manager.assign_unassigned(parked[1].id, "Courses", false).unwrap());
+        assert!(!manager.assign_unassigned(parked[1].id, "Courses", false).unwrap());
+        assert!(manager.assign_unassigned(parked[0].id, "Inconnu", false).is_err());
+        assert!(manager.remove_unassigned(parked[2].id).unwrap());
+        assert_eq!(manager.count_unassigned().unwrap(), 1);
+        assert_eq!(spent(&manager, "Courses"), 13.2);
+    }
+
+    // Budgets de 10 en mode "warn"
+    fn warn_budgets() -> BudgetManager {
+        let manager = BudgetManager::new(":memory:").unwrap();
+        for budget_name in ["Courses", "Loisirs"] {
+            manager.add_budget(budget_name, 10.0).unwrap();
+            manager.set_overspend_policy(budget_name, OverspendPolicy::Warn).unwrap();
+        }
+        manager
+    }
+
+    #[test]
+    fn overspending_is_confirmed_once_for_the_whole_file() {
+        let path = temp_file(
+            "overspend.csv",
+            "budget_name,transaction_name,amount\nCourses,Marché,6\nCourses,Boucherie,6\nLoisirs,Cinéma,9\n",
+        );
+        let rows = |manager: &BudgetManager| {
+            check_rows(manager, read_file(&path, None).unwrap(), "", &BudgetMappings::default(), "", UnknownBudgetPolicy::Skip).unwrap()
+        };
+
+        // Chaque ligne tient dans le budget, pas leur somme
+        let manager = warn_budgets();
+        assert_eq!(overspent_budgets(&manager, &rows(&manager), false).unwrap(), ["Courses"]);
+        let report = import_rows(&manager, rows(&manager), &BudgetMappings::default(), false, false).unwrap();
+        assert_eq!((report.imported, report.skipped), (2, 1));
+
+        let manager = warn_budgets();
+        let report = import_rows(&manager, rows(&manager), &BudgetMappings::default(), false, true).unwrap();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(report.imported, 3);
+        assert_eq!(spent(&manager, "Courses"), 12.0);
+    }
+
+    #[test]
+    fn chosen_mappings_are_saved_with_the_import() {
+        let manager = courses();
+        let path = temp_file("chosen.csv", "budget_name,transaction_name,amount\nCourse,Pain,1.20\n");
+        let mappings = BudgetMappings {
+            aliases: HashMap::from([("Course".to_string(), "Courses".to_string())]),
+            chosen: vec![NewMapping::Alias("Course".to_string(), "Courses".to_string())],
+            ..BudgetMappings::default()
+        };
+        let rows = check_rows(&manager, read_file(&path, None).unwrap(), "", &mappings, "", UnknownBudgetPolicy::Map).unwrap();
+        std::fs::remove_file(path).unwrap();
+        // L'aperçu utilise l'alias sans l'enregistrer
+        assert!(matches!(&rows[0].row, Ok(ImportRow::Transaction { budget_name, .. }) if budget_name == "Courses"));
+        assert!(manager.list_budget_aliases().unwrap().is_empty());
+
+        import_rows(&manager, rows, &mappings, false, false).unwrap();
+        assert_eq!(manager.budget_aliases().unwrap(), mappings.aliases);
+        assert_eq!(spent(&manager, "Courses"), 1.2);
+    }
+}
@@ -0,0 +1,252 @@
+use chrono::NaiveDate;
+use roxmltree::{Document, Node};
+use crate::db::NewTransaction;
+use crate::i18n::tr;
+use super::{ImportRow, ReadRow};
+
+// Relevés ISO 20022 CAMT.053 (XML). Les balises sont lues par leur nom local,
+// quelle que soit la version du schéma (camt.053.001.02 à .08) :
+//   <Stmt>      relevé d'un compte (Acct/Id/IBAN ou Acct/Id/Othr/Id)
+//   <Ntry>      une écriture : Amt, CdtDbtInd, Sts, BookgDt, AcctSvcrRef
+//   <TxDtls>    détail d'une écriture : RltdPties (contrepartie), RmtInf
+//               (motif) ; une écriture groupée en contient plusieurs
+
+pub fn is_camt(text: &str) -> bool {
+    let head: String = text.chars().take(4096).collect();
+    head.contains("camt.053") || head.contains("<BkToCstmrStmt") || head.contains(":BkToCstmrStmt")
+}
+
+//==================================Read a CAMT.053 statement====================================
+// Le relevé n'indique pas de budget : il dépend du compte (voir `check_rows`)
+pub fn read(text: &str) -> Result<Vec<ReadRow>, String> {
+    let document = Document::parse(text).map_err(|err| tr!(ImportInvalidXml, err))?;
+    let mut rows = Vec::new();
+    for statement in descendants(document.root(), "Stmt") {
+        let account = account(statement);
+        for entry in statement.children().filter(|node| node.has_tag_name("Ntry")) {
+            let line = document.text_pos_at(entry.range().start).row as usize;
+            let details: Vec<Node> = descendants(entry, "TxDtls").collect();
+            // Une écriture groupée (plusieurs virements payés en une fois) donne
+            // une transaction par détail, si chaque détail porte son montant
+            let split = details.len() > 1 && details.iter().all(|detail| detail_amount(*detail).is_some());
+            if split {
+                for (index, detail) in details.iter().enumerate() {
+                    let row = transaction(entry, Some(*detail), Some(index + 1), &account);
+                    rows.push((document.text_pos_at(detail.range().start).row as usize, row));
+                }
+            } else {
+                let row = transaction(entry, details.first().copied(), None, &account);
+                rows.push((line, row));
+            }
+        }
+    }
+    Ok(rows)
+}
+
+fn transaction(entry: Node, detail: Option<Node>, index: Option<usize>, account: &str) -> Result<ImportRow, String> {
+    let debit = match text_at(entry, &["CdtDbtInd"]).as_deref() {
+        Some("DBIT") => true,
+        Some("CRDT") => false,
+        Some(other) => return Err(tr!(ImportBadDebitCredit, other)),
+        None => return Err(tr!(ImportBadDebitCredit, "")),
+    };
+    let counterparty = detail.and_then(|detail| counterparty(detail, debit));
+    let remittance = detail.and_then(remittance).or_else(|| text_at(entry, &["AddtlNtryInf"]));
+    let name = counterparty
+        .clone()
+        .or_else(|| remittance.clone())
+        .ok_or_else(|| tr!(ImportEmptyName).to_string())?;
+
+    let amount_text = match index {
+        Some(_) => detail.and_then(detail_amount),
+        None => text_at(entry, &["Amt"]),
+    }
+    .ok_or_else(|| tr!(ImportEmptyAmount).to_string())?;
+    let amount = amount_text.parse::<f64>().map_err(|_| tr!(NotANumber, amount_text))?;
+    if !debit {
+        return Ok(ImportRow::Skipped(tr!(ImportIncomeSkipped, name)));
+    }
+    // Statut en code (Sts/Cd) depuis la version .08, en texte (Sts) avant
+    let status = text_at(entry, &["Sts", "Cd"]).or_else(|| text_at(entry, &["Sts"]));
+    if status.as_deref() == Some("PDNG") {
+        return Ok(ImportRow::Skipped(tr!(ImportPendingSkipped, name)));
+    }
+    let date = booking_date(entry)?;
+
+    let transaction = NewTransaction {
+        payee: counterparty.unwrap_or_default(),
+        notes: remittance.filter(|remittance| *remittance != name).unwrap_or_default(),
+        transaction_date: date.map(|date| date.to_string()),
+        account: account.to_string(),
+        external_id: external_id(entry, detail, index),
+        ..NewTransaction::new(&name, amount)
+    };
+    Ok(ImportRow::Transaction { budget_name: String::new(), transaction })
+}
+
+// IBAN du compte, ou son identifiant national
+fn account(statement: Node) -> String {
+    text_at(statement, &["Acct", "Id", "IBAN"])
+        .or_else(|| text_at(statement, &["Acct", "Id", "Othr", "Id"]))
+        .unwrap_or_default()
+}
+
+// Date de comptabilisation (date seule ou date et heure), sinon date de valeur
+fn booking_date(entry: Node) -> Result<Option<NaiveDate>, String> {
+    let value = text_at(entry, &["BookgDt", "Dt"])
+        .or_else(|| text_at(entry, &["BookgDt", "DtTm"]))
+        .or_else(|| text_at(entry, &["ValDt", "Dt"]))
+        .or_else(|| text_at(entry, &["ValDt", "DtTm"]));
+    value
+        .map(|value| {
+            value
+                .get(..10)
+                .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+                .ok_or_else(|| tr!(InvalidDate, value))
+        })
+        .transpose()
+}
+
+// Bénéficiaire d'un débit, émetteur d'un crédit ; le nom est dans Pty depuis la version .08
+fn counterparty(detail: Node, debit: bool) -> Option<String> {
+    let party = if debit { "Cdtr" } else { "Dbtr" };
+    text_at(detail, &["RltdPties", party, "Nm"]).or_else(|| text_at(detail, &["RltdPties", party, "Pty", "Nm"]))
+}
+
+// Motif non structuré (plusieurs lignes Ustrd), sinon la référence structurée
+fn remittance(detail: Node) -> Option<String> {
+    let information = child(detail, "RmtInf")?;
+    let lines: Vec<String> = information
+        .children()
+        .filter(|node| node.has_tag_name("Ustrd"))
+        .filter_map(|node| node.text().map(|text| text.trim().to_string()))
+        .filter(|text| !text.is_empty())
+        .collect();
+    if lines.is_empty() {
+        text_at(information, &["Strd", "CdtrRefInf", "Ref"])
+    } else {
+        Some(lines.join(" "))
+    }
+}
+
+// Montant propre à un détail d'écriture groupée
+fn detail_amount(detail: Node) -> Option<String> {
+    text_at(detail, &["Amt"]).or_else(|| text_at(detail, &["AmtDtls", "TxAmt", "Amt"]))
+}
+
+// Référence de la banque ; le numéro du détail la complète pour une écriture groupée
+fn external_id(entry: Node, detail: Option<Node>, index: Option<usize>) -> String {
+    let reference = text_at(entry, &["AcctSvcrRef"])
+        .or_else(|| detail.and_then(|detail| text_at(detail, &["Refs", "AcctSvcrRef"])))
+        .or_else(|| detail.and_then(|detail| text_at(detail, &["Refs", "EndToEndId"])).filter(|id| id != "NOTPROVIDED"))
+        .or_else(|| text_at(entry, &["NtryRef"]));
+    match (reference, index) {
+        (Some(reference), Some(index)) => format!("{}#{}", reference, index),
+        (Some(reference), None) => reference,
+        (None, _) => String::new(),
+    }
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, tag: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|child| child.has_tag_name(tag))
+}
+
+fn descendants<'a, 'input: 'a>(node: Node<'a, 'input>, tag: &'a str) -> impl Iterator<Item = Node<'a, 'input>> + 'a {
+    node.descendants().filter(move |node| node.has_tag_name(tag))
+}
+
+// Texte d'une balise au bout d'un chemin de balises filles, sans les espaces autour
+fn text_at(node: Node, path: &[&str]) -> Option<String> {
+    let node = path.iter().try_fold(node, |node, tag| child(node, tag))?;
+    let text = node.text()?.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Relevé .08 : une écriture groupée (deux virements), une écriture simple en
+    // attente (Sts/Cd) ; relevé .02 : statut en texte (Sts)
+    const CAMT_08: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.053.001.08">
+<BkToCstmrStmt><Stmt>
+  <Acct><Id><IBAN>CH9300762011623852957</IBAN></Id></Acct>
+  <Ntry>
+    <Amt Ccy="CHF">150.00</Amt><CdtDbtInd>DBIT</CdtDbtInd><Sts><Cd>BOOK</Cd></Sts>
+    <BookgDt><Dt>2026-03-15</Dt></BookgDt><AcctSvcrRef>REF-1</AcctSvcrRef>
+    <NtryDtls>
+      <TxDtls><Amt Ccy="CHF">100.00</Amt><RltdPties><Cdtr><Pty><Nm>Régie Dupont</Nm></Pty></Cdtr></RltdPties>
+        <RmtInf><Ustrd>Loyer</Ustrd><Ustrd>mars</Ustrd></RmtInf></TxDtls>
+      <TxDtls><AmtDtls><TxAmt><Amt Ccy="CHF">50.00</Amt></TxAmt></AmtDtls>
+        <RmtInf><Strd><CdtrRefInf><Ref>RF18 5390</Ref></CdtrRefInf></Strd></RmtInf></TxDtls>
+    </NtryDtls>
+  </Ntry>
+  <Ntry>
+    <Amt Ccy="CHF">9.90</Amt><CdtDbtInd>DBIT</CdtDbtInd><Sts><Cd>PDNG</Cd></Sts>
+    <BookgDt><DtTm>2026-03-16T10:00:00</DtTm></BookgDt><AddtlNtryInf>Kiosque</AddtlNtryInf>
+  </Ntry>
+</Stmt></BkToCstmrStmt>
+</Document>
+"#;
+
+    const CAMT_02: &str = r#"<Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.053.001.02">
+<BkToCstmrStmt><Stmt>
+  <Acct><Id><Othr><Id>0012345</Id></Othr></Id></Acct>
+  <Ntry><Amt>20.00</Amt><CdtDbtInd>DBIT</CdtDbtInd><Sts>PDNG</Sts><AddtlNtryInf>Boulangerie</AddtlNtryInf></Ntry>
+  <Ntry><Amt>35.00</Amt><CdtDbtInd>DBIT</CdtDbtInd><Sts>BOOK</Sts><ValDt><Dt>2026-03-02</Dt></ValDt>
+    <NtryRef>N-7</NtryRef>
+    <NtryDtls><TxDtls><Amt>35.00</Amt><RltdPties><Cdtr><Nm>Garage</Nm></Cdtr></RltdPties></TxDtls></NtryDtls>
+  </Ntry>
+  <Ntry><Amt>1000.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Sts>BOOK</Sts><AddtlNtryInf>Salaire</AddtlNtryInf></Ntry>
+</Stmt></BkToCstmrStmt>
+</Document>
+"#;
+
+    fn transaction(row: &ReadRow) -> &NewTransaction {
+        match &row.1 {
+            Ok(ImportRow::Transaction { transaction, .. }) => transaction,
+            other => panic!("ligne {} : {:?}", row.0, other.as_ref().err()),
+        }
+    }
+
+    #[test]
+    fn splits_batched_entries_by_detail() {
+        assert!(is_camt(CAMT_08));
+        let rows = read(CAMT_08).unwrap();
+        assert_eq!(rows.len(), 3);
+        assert_eq!((rows[0].0, rows[1].0), (9, 11));
+
+        let rent = transaction(&rows[0]);
+        assert_eq!((rent.transaction_name.as_str(), rent.amount), ("Régie Dupont", 100.0));
+        assert_eq!((rent.payee.as_str(), rent.notes.as_str()), ("Régie Dupont", "Loyer mars"));
+        assert_eq!(rent.transaction_date.as_deref(), Some("2026-03-15"));
+        assert_eq!((rent.account.as_str(), rent.external_id.as_str()), ("CH9300762011623852957", "REF-1#1"));
+
+        // Sans contrepartie, la référence structurée donne le libellé
+        let second = transaction(&rows[1]);
+        assert_eq!((second.transaction_name.as_str(), second.amount, second.external_id.as_str()), ("RF18 5390", 50.0, "REF-1#2"));
+    }
+
+    #[test]
+    fn skips_pending_entries_with_coded_or_text_status() {
+        let rows = read(CAMT_08).unwrap();
+        assert!(matches!(rows[2].1, Ok(ImportRow::Skipped(_))));
+
+        let rows = read(CAMT_02).unwrap();
+        assert_eq!(rows.len(), 3);
+        assert!(matches!(rows[0].1, Ok(ImportRow::Skipped(_))));
+        assert!(matches!(rows[2].1, Ok(ImportRow::Skipped(_))));
+        // Un seul détail : pas de numéro ; date de valeur à défaut de comptabilisation
+        let garage = transaction(&rows[1]);
+        assert_eq!((garage.transaction_name.as_str(), garage.account.as_str()), ("Garage", "0012345"));
+        assert_eq!((garage.external_id.as_str(), garage.transaction_date.as_deref()), ("N-7", Some("2026-03-02")));
+    }
+
+    #[test]
+    fn rejects_invalid_documents_and_indicators() {
+        assert!(read("<Document><BkToCstmrStmt>").is_err());
+        let text = CAMT_02.replacen("<CdtDbtInd>DBIT</CdtDbtInd>", "<CdtDbtInd>XXXX</CdtDbtInd>", 1);
+        assert!(read(&text).unwrap()[0].1.is_err());
+    }
+}
@@ -0,0 +1,379 @@
+use chrono::{Datelike, NaiveDate};
+use crate::db::NewTransaction;
+use crate::i18n::{self, tr};
+use super::{ImportRow, ReadRow};
+
+// Relevés SWIFT MT940 : un champ par balise, sur une ou plusieurs lignes.
+//   :20:    référence du relevé       :25:    compte
+//   :60F:   solde initial (:60M: pour la suite d'un relevé sur plusieurs pages)
+//   :61:    une opération : date, sens, montant, type, références
+//   :86:    informations sur l'opération précédente (libre, ou en sous-champs ?NN)
+//   :62F:   solde final (:62M: en fin de page)
+// Le solde final doit être égal au solde initial plus les opérations : un
+// relevé qui ne tombe pas juste n'est pas importé.
+
+pub fn is_mt940(text: &str) -> bool {
+    let starts = |tag: &str| text.lines().take(50).any(|line| line.trim_start().starts_with(tag));
+    starts(":20:") && starts(":25:")
+}
+
+// Un champ du relevé et sa ligne dans le fichier
+struct Field {
+    line: usize,
+    tag: String,
+    lines: Vec<String>,
+}
+
+// Solde initial ou final, en centimes
+struct Balance {
+    line: usize,
+    cents: i64,
+    currency: String,
+}
+
+struct Entry {
+    line: usize,
+    statement_line: String,
+    details: Option<String>,
+    information: Vec<String>,
+}
+
+#[derive(Default)]
+struct Statement {
+    line: usize,
+    reference: String,
+    account: String,
+    opening: Option<Balance>,
+    closing: Option<Balance>,
+    entries: Vec<Entry>,
+}
+
+fn fields(text: &str) -> Vec<Field> {
+    let mut fields: Vec<Field> = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim_end();
+        // En-têtes SWIFT ({1:...}{2:...}{4:) et fin de message (-})
+        if line.starts_with('{') || line == "-}" || line == "-" {
+            continue;
+        }
+        let tag = line
+            .strip_prefix(':')
+            .and_then(|rest| rest.split_once(':'))
+            .filter(|(tag, _)| !tag.is_empty() && tag.len() <= 3 && tag.chars().all(|c| c.is_ascii_alphanumeric()));
+        match (tag, fields.last_mut()) {
+            (Some((tag, value)), _) => fields.push(Field { line: index + 1, tag: tag.to_string(), lines: vec![value.to_string()] }),
+            (None, Some(field)) => field.lines.push(line.to_string()),
+            (None, None) => {}
+        }
+    }
+    fields
+}
+
+fn statements(text: &str) -> Vec<Statement> {
+    let mut statements: Vec<Statement> = Vec::new();
+    for field in fields(text) {
+        let value = field.lines.first().map(|line| line.trim().to_string()).unwrap_or_default();
+        if field.tag == "20" || statements.is_empty() {
+            statements.push(Statement { line: field.line, ..Default::default() });
+        }
+        let Some(statement) = statements.last_mut() else { continue };
+        match field.tag.as_str() {
+            "20" => statement.reference = value,
+            "25" => statement.account = value,
+            "60F" | "60M" => statement.opening = parse_balance(&value).map(|(cents, currency)| Balance { line: field.line, cents, currency }),
+            "62F" | "62M" => statement.closing = parse_balance(&value).map(|(cents, currency)| Balance { line: field.line, cents, currency }),
+            "61" => statement.entries.push(Entry {
+                line: field.line,
+                statement_line: value,
+                details: field.lines.get(1).map(|line| line.trim().to_string()).filter(|line| !line.is_empty()),
+                information: Vec::new(),
+            }),
+            "86" => {
+                if let Some(entry) = statement.entries.last_mut().filter(|entry| entry.information.is_empty()) {
+                    entry.information = field.lines;
+                }
+            }
+            _ => {}
+        }
+    }
+    statements
+}
+
+//==================================Read an MT940 statement====================================
+// Le relevé n'indique pas de budget : il dépend du compte (voir `check_rows`)
+pub fn read(text: &str) -> Vec<ReadRow> {
+    let mut rows = Vec::new();
+    for statement in statements(text) {
+        let mut statement_rows = Vec::new();
+        let mut total = 0;
+        for entry in &statement.entries {
+            let row = parse_entry(&entry.statement_line).and_then(|line| {
+                total += line.cents;
+                transaction(entry, &line, &statement.account)
+            });
+            statement_rows.push((entry.line, row));
+        }
+
+        // Un relevé incohérent n'importe rien : ses opérations passent en erreur
+        let check = match (&statement.opening, &statement.closing) {
+            (Some(opening), Some(closing)) if opening.cents + total == closing.cents => None,
+            (Some(opening), Some(closing)) => Some((
+                closing.line,
+                tr!(
+                    ImportBalanceMismatch,
+                    statement.reference,
+                    format_cents(closing.cents, &closing.currency),
+                    format_cents(opening.cents + total, &opening.currency),
+                    format_cents(opening.cents, &opening.currency),
+                    format_cents(total, &opening.currency)
+                ),
+            )),
+            _ => Some((statement.line, tr!(ImportBalanceMissing, statement.reference))),
+        };
+        if let Some((line, message)) = check {
+            for (_, row) in statement_rows.iter_mut() {
+                if matches!(row, Ok(ImportRow::Transaction { .. })) {
+                    *row = Err(tr!(ImportStatementUnbalanced, statement.reference));
+                }
+            }
+            statement_rows.push((line, Err(message)));
+        }
+        rows.extend(statement_rows);
+    }
+    rows
+}
+
+fn transaction(entry: &Entry, line: &StatementLine, account: &str) -> Result<ImportRow, String> {
+    let information = Information::parse(&entry.information);
+    let name = information
+        .counterparty
+        .clone()
+        .or_else(|| information.purpose.clone())
+        .or_else(|| information.booking_text.clone())
+        .or_else(|| entry.details.clone())
+        .or_else(|| line.customer_reference.clone())
+        .ok_or_else(|| tr!(ImportEmptyName).to_string())?;
+    if line.cents > 0 {
+        return Ok(ImportRow::Skipped(tr!(ImportIncomeSkipped, name)));
+    }
+
+    let transaction = NewTransaction {
+        payee: information.counterparty.unwrap_or_default(),
+        notes: information.purpose.filter(|purpose| *purpose != name).unwrap_or_default(),
+        transaction_date: Some(line.date.to_string()),
+        account: account.to_string(),
+        external_id: line.bank_reference.clone().or_else(|| line.customer_reference.clone()).unwrap_or_default(),
+        ..NewTransaction::new(name, -line.cents as f64 / 100.0)
+    };
+    Ok(ImportRow::Transaction { budget_name: String::new(), transaction })
+}
+
+// Champ :61: "2603150316DR12,50NMSCNONREF//BQ123" : date de valeur, date de
+// comptabilisation (facultative), sens, code devise (facultatif), montant,
+// type d'opération, référence du client, référence de la banque
+struct StatementLine {
+    date: NaiveDate,
+    // Crédit positif, débit négatif
+    cents: i64,
+    customer_reference: Option<String>,
+    bank_reference: Option<String>,
+}
+
+fn parse_entry(value: &str) -> Result<StatementLine, String> {
+    let value_date = value.get(..6).and_then(|date| NaiveDate::parse_from_str(date, "%y%m%d").ok()).ok_or_else(|| tr!(InvalidDate, value))?;
+    let mut rest = &value[6..];
+    let entry_date = match rest.get(..4).filter(|digits| digits.chars().all(|c| c.is_ascii_digit())) {
+        Some(digits) => {
+            rest = &rest[4..];
+            Some(booking_date(value_date, digits).ok_or_else(|| tr!(InvalidDate, value))?)
+        }
+        None => None,
+    };
+    // Les annulations inversent le sens : RC (crédit annulé) est un débit
+    let (sign, mark_len) = if rest.starts_with("RC") {
+        (-1, 2)
+    } else if rest.starts_with("RD") {
+        (1, 2)
+    } else if rest.starts_with('C') {
+        (1, 1)
+    } else if rest.starts_with('D') {
+        (-1, 1)
+    } else {
+        return Err(tr!(ImportBadDebitCredit, rest.chars().take(2).collect::<String>()));
+    };
+    rest = &rest[mark_len..];
+    if rest.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        rest = &rest[1..];
+    }
+    let amount_len = rest.find(|c: char| !c.is_ascii_digit() && c != ',').unwrap_or(rest.len());
+    let amount_text = &rest[..amount_len];
+    let cents = parse_cents(amount_text).ok_or_else(|| tr!(NotANumber, amount_text))?;
+    // Type d'opération sur 4 caractères (NMSC, FTRF...), puis les références
+    let references = rest[amount_len..].get(4..).unwrap_or_default();
+    let (customer, bank) = references.split_once("//").unwrap_or((references, ""));
+    let reference = |text: &str| Some(text.trim().to_string()).filter(|text| !text.is_empty() && text != "NONREF");
+
+    Ok(StatementLine {
+        date: entry_date.unwrap_or(value_date),
+        cents: sign * cents,
+        customer_reference: reference(customer),
+        bank_reference: reference(bank),
+    })
+}
+
+// La date de comptabilisation (MMJJ) n'a pas d'année : celle de la date de
+// valeur, à un an près autour du 1er janvier
+fn booking_date(value_date: NaiveDate, digits: &str) -> Option<NaiveDate> {
+    let (month, day) = (digits[..2].parse::<u32>().ok()?, digits[2..].parse::<u32>().ok()?);
+    let year = match (value_date.month(), month) {
+        (12, 1) => value_date.year() + 1,
+        (1, 12) => value_date.year() - 1,
+        _ => value_date.year(),
+    };
+    NaiveDate::from_ymd_opt(year, month, day)
+}
+
+// Solde "C260301EUR1234,56" : sens, date, devise, montant
+fn parse_balance(value: &str) -> Option<(i64, String)> {
+    let sign = match value.get(..1)? {
+        "C" => 1,
+        "D" => -1,
+        _ => return None,
+    };
+    let currency = value.get(7..10)?.to_string();
+    Some((sign * parse_cents(value.get(10..)?)?, currency))
+}
+
+// "1234,5" donne 123450 ; les montants MT940 ont toujours une virgule décimale
+fn parse_cents(text: &str) -> Option<i64> {
+    let (units, decimals) = text.trim().split_once(',')?;
+    if units.is_empty() || decimals.len() > 2 || !units.chars().chain(decimals.chars()).all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    Some(units.parse::<i64>().ok()? * 100 + format!("{:0<2}", decimals).parse::<i64>().ok()?)
+}
+
+fn format_cents(cents: i64, currency: &str) -> String {
+    format!("{} {}", i18n::number(cents as f64 / 100.0, 2), currency)
+}
+
+// Champ :86:, libre ou en sous-champs ("166?00SEPA-LASTSCHRIFT?20Facture 12?32Nom") :
+//   ?00 libellé de l'opération   ?20 à ?29 motif   ?32 et ?33 contrepartie
+#[derive(Default)]
+struct Information {
+    booking_text: Option<String>,
+    purpose: Option<String>,
+    counterparty: Option<String>,
+}
+
+impl Information {
+    fn parse(lines: &[String]) -> Self {
+        let non_empty = |text: String| Some(text.trim().to_string()).filter(|text| !text.is_empty());
+        if !lines.first().is_some_and(|line| line.contains('?')) {
+            return Information { purpose: non_empty(lines.iter().map(|line| line.trim()).collect::<Vec<_>>().join(" ")), ..Default::default() };
+        }
+        // Les lignes coupent les sous-champs n'importe où : elles se recollent sans espace
+        let text: String = lines.concat();
+        let mut information = Information::default();
+        let (mut purpose, mut counterparty) = (String::new(), String::new());
+        for subfield in text.split('?').skip(1) {
+            let (code, value) = (subfield.get(..2).unwrap_or_default(), subfield.get(2..).unwrap_or_default());
+            match code {
+                "00" => information.booking_text = non_empty(value.to_string()),
+                "20" | "21" | "22" | "23" | "24" | "25" | "26" | "27" | "28" | "29" => purpose.push_str(value),
+                "32" | "33" => counterparty.push_str(value),
+                _ => {}
+            }
+        }
+        information.purpose = non_empty(purpose);
+        information.counterparty = non_empty(counterparty);
+        information
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MT940: &str = "{1:F01BANKDEFFXXXX0000000000}{2:O940}{4:
+:20:REL-0315
+:25:DE89370400440532013000
+:28C:1/1
+:60F:C260314EUR1000,00
+:61:2603150315DR12,50NMSCNONREF//BQ-1
+CB BOULANGERIE
+:86:166?00SEPA-LASTSCHRIFT?20Facture 12?21 mars?32Boulan
+gerie Martin
+:61:2603160316RC5,NTRFKD-9//BQ-2
+:86:Annulation remboursement
+:61:260317C100,00NTRFSALAIRE
+:62F:C260317EUR1082,50
+-}
+";
+
+    #[test]
+    fn reads_a_balanced_statement() {
+        assert!(is_mt940(MT940));
+        let rows = read(MT940);
+        assert_eq!(rows.iter().map(|(line, _)| *line).collect::<Vec<_>>(), vec![6, 10, 12]);
+
+        let Ok(ImportRow::Transaction { transaction, .. }) = &rows[0].1 else { panic!("{:?}", rows[0].1.as_ref().err()) };
+        assert_eq!((transaction.transaction_name.as_str(), transaction.amount), ("Boulangerie Martin", 12.5));
+        assert_eq!((transaction.payee.as_str(), transaction.notes.as_str()), ("Boulangerie Martin", "Facture 12 mars"));
+        assert_eq!(transaction.transaction_date.as_deref(), Some("2026-03-15"));
+        assert_eq!((transaction.account.as_str(), transaction.external_id.as_str()), ("DE89370400440532013000", "BQ-1"));
+
+        // Crédit annulé (RC) : un débit ; le :86: libre devient le motif
+        let Ok(ImportRow::Transaction { transaction, .. }) = &rows[1].1 else { panic!("{:?}", rows[1].1.as_ref().err()) };
+        assert_eq!((transaction.transaction_name.as_str(), transaction.amount), ("Annulation remboursement", 5.0));
+        assert!(matches!(rows[2].1, Ok(ImportRow::Skipped(_))));
+    }
+
+    #[test]
+    fn an_unbalanced_statement_imports_nothing() {
+        let rows = read(&MT940.replace(":62F:C260317EUR1082,50", ":62F:C260317EUR1082,49"));
+        assert_eq!(rows.len(), 4);
+        assert!(rows[..2].iter().all(|(_, row)| row.is_err()));
+        assert_eq!(rows[3].0, 13);
+
+        let rows = read(&MT940.replace(":60F:C260314EUR1000,00\n", ""));
+        assert!(rows.last().is_some_and(|(line, row)| *line == 2 && row.is_err()));
+    }
+
+    #[test]
+    fn reads_debit_credit_marks() {
+        let cents = |value: &str| parse_entry(value).map(|line| line.cents);
+        assert_eq!(cents("260315D12,50NMSCNONREF"), Ok(-1250));
+        assert_eq!(cents("260315C12,5NMSCNONREF"), Ok(1250));
+        assert_eq!(cents("260315RC3,NMSCNONREF"), Ok(-300));
+        assert_eq!(cents("260315RD3,NMSCNONREF"), Ok(300));
+        // Troisième lettre de la devise avant le montant
+        assert_eq!(cents("260315DR7,00NMSCNONREF"), Ok(-700));
+        assert!(cents("260315X7,00NMSC").is_err());
+        assert!(cents("260315D7NMSC").is_err());
+
+        let line = parse_entry("260315D1,00NMSCREF-CLIENT//REF-BANQUE").unwrap();
+        assert_eq!((line.customer_reference.as_deref(), line.bank_reference.as_deref()), (Some("REF-CLIENT"), Some("REF-BANQUE")));
+        assert_eq!(parse_entry("260315D1,00NMSCNONREF").unwrap().customer_reference, None);
+    }
+
+    #[test]
+    fn booking_dates_roll_over_the_new_year() {
+        let date = |value: &str| parse_entry(value).map(|line| line.date.to_string());
+        assert_eq!(date("2612310102D1,00NMSCNONREF"), Ok("2027-01-02".to_string()));
+        assert_eq!(date("2701021231D1,00NMSCNONREF"), Ok("2026-12-31".to_string()));
+        assert_eq!(date("2603150316D1,00NMSCNONREF"), Ok("2026-03-16".to_string()));
+        assert!(date("2603151332D1,00NMSCNONREF").is_err());
+    }
+
+    #[test]
+    fn reads_amounts_and_balances() {
+        assert_eq!(parse_cents("1234,5"), Some(123450));
+        assert_eq!(parse_cents("0,07"), Some(7));
+        assert_eq!(parse_cents("12"), None);
+        assert_eq!(parse_cents(",50"), None);
+        assert_eq!(parse_cents("1,234"), None);
+        assert_eq!(parse_balance("D260301EUR12,00"), Some((-1200, "EUR".to_string())));
+        assert_eq!(parse_balance("X260301EUR12,00"), None);
+    }
+}
@@ -0,0 +1,163 @@
+use chrono::NaiveDate;
+use crate::db::NewTransaction;
+use crate::i18n::tr;
+use super::{line_number, ImportRow, ReadRow};
+
+// Relevés OFX/QFX, en SGML (OFX 1.x : les balises de valeur ne sont pas
+// fermées) ou en XML (OFX 2.x). Seules les balises utiles sont lues, ce qui
+// convient aux deux variantes :
+//   <STMTRS> / <CCSTMTRS>   relevé d'un compte (ACCTID)
+//   <STMTTRN>               une opération : DTPOSTED, TRNAMT, FITID, NAME, MEMO
+
+pub fn is_ofx(text: &str) -> bool {
+    let head: String = text.chars().take(4096).collect::<String>().to_uppercase();
+    head.contains("OFXHEADER") || head.contains("<OFX>")
+}
+
+//==================================Read an OFX statement====================================
+// Le relevé n'indique pas de budget : il dépend du compte (voir `check_rows`)
+pub fn read(text: &str) -> Vec<ReadRow> {
+    let mut rows = Vec::new();
+    for (offset, statement) in statements(text) {
+        let account = leaf(statement, "ACCTID").unwrap_or_default();
+        for (start, block) in blocks(statement, "STMTTRN") {
+            let line = line_number(text, offset + start);
+            rows.push((line, transaction(block, &account)));
+        }
+    }
+    rows
+}
+
+fn transaction(block: &str, account: &str) -> Result<ImportRow, String> {
+    let name = leaf(block, "NAME").or_else(|| leaf(block, "MEMO")).ok_or_else(|| tr!(ImportEmptyName).to_string())?;
+    let amount_text = leaf(block, "TRNAMT").ok_or_else(|| tr!(ImportEmptyAmount).to_string())?;
+    // Débits négatifs dans le relevé ; certaines banques écrivent une virgule décimale
+    let amount = -amount_text.replace(',', ".").parse::<f64>().map_err(|_| tr!(NotANumber, amount_text))?;
+    if amount < 0.0 {
+        return Ok(ImportRow::Skipped(tr!(ImportIncomeSkipped, name)));
+    }
+    let date = leaf(block, "DTPOSTED").map(|date| parse_date(&date)).transpose()?;
+
+    let transaction = NewTransaction {
+        notes: leaf(block, "MEMO").filter(|memo| *memo != name).unwrap_or_default(),
+        transaction_date: date.map(|date| date.to_string()),
+        account: account.to_string(),
+        external_id: leaf(block, "FITID").unwrap_or_default(),
+        ..NewTransaction::new(name, amount)
+    };
+    Ok(ImportRow::Transaction { budget_name: String::new(), transaction })
+}
+
+// AAAAMMJJ, suivi ou non de l'heure et du fuseau ("20260315120000.000[-5:EST]")
+fn parse_date(value: &str) -> Result<NaiveDate, String> {
+    value
+        .get(..8)
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y%m%d").ok())
+        .ok_or_else(|| tr!(InvalidDate, value))
+}
+
+// Relevés bancaires et de cartes, dans l'ordre du fichier : (position, contenu)
+fn statements(text: &str) -> Vec<(usize, &str)> {
+    let mut statements = blocks(text, "STMTRS");
+    statements.extend(blocks(text, "CCSTMTRS"));
+    statements.sort_by_key(|(offset, _)| *offset);
+    statements
+}
+
+// Contenu des blocs <TAG>...</TAG> et position de ce contenu ; un bloc non fermé va jusqu'à la fin
+fn blocks<'a>(text: &'a str, tag: &str) -> Vec<(usize, &'a str)> {
+    let (open, close) = (format!("<{}>", tag), format!("</{}>", tag));
+    let mut found = Vec::new();
+    let mut from = 0;
+    while let Some(start) = text[from..].find(&open).map(|index| from + index) {
+        let content_start = start + open.len();
+        let end = text[content_start..].find(&close).map_or(text.len(), |index| content_start + index);
+        found.push((content_start, &text[content_start..end]));
+        from = end;
+    }
+    found
+}
+
+// Valeur d'une balise simple : le texte jusqu'à la balise suivante, fermante ou non
+fn leaf(block: &str, tag: &str) -> Option<String> {
+    let open = format!("<{}>", tag);
+    let start = block.find(&open)? + open.len();
+    let value = block[start..].split('<').next().unwrap_or_default().trim();
+    (!value.is_empty()).then(|| unescape(value))
+}
+
+fn unescape(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SGML: &str = "OFXHEADER:100\nDATA:OFXSGML\n\n<OFX>\n<BANKMSGSRSV1><STMTTRNRS><STMTRS>\n<BANKACCTFROM><ACCTID>FR76 123\n</BANKACCTFROM>\n<BANKTRANLIST>\n<STMTTRN>\n<TRNTYPE>DEBIT\n<DTPOSTED>20260315120000.000[-5:EST]\n<TRNAMT>-42,50\n<FITID>A1\n<NAME>CARREFOUR &amp; CIE\n<MEMO>CB 14/03\n</STMTTRN>\n<STMTTRN>\n<TRNTYPE>CREDIT\n<DTPOSTED>20260316\n<TRNAMT>1500.00\n<FITID>A2\n<NAME>SALAIRE\n</STMTTRN>\n</BANKTRANLIST>\n</STMTRS></STMTTRNRS></BANKMSGSRSV1>\n</OFX>\n";
+
+    const XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<?OFX OFXHEADER="200" VERSION="220"?>
+<OFX><CREDITCARDMSGSRSV1><CCSTMTTRNRS><CCSTMTRS>
+<CCACCTFROM><ACCTID>4970</ACCTID></CCACCTFROM>
+<BANKTRANLIST>
+<STMTTRN><TRNTYPE>DEBIT</TRNTYPE><DTPOSTED>20260301</DTPOSTED><TRNAMT>-9.99</TRNAMT><FITID>X9</FITID><MEMO>NETFLIX</MEMO></STMTTRN>
+</BANKTRANLIST>
+</CCSTMTRS></CCSTMTTRNRS></CREDITCARDMSGSRSV1></OFX>
+"#;
+
+    fn transactions(rows: &[ReadRow]) -> Vec<&NewTransaction> {
+        rows.iter()
+            .filter_map(|(_, row)| match row {
+                Ok(ImportRow::Transaction { transaction, .. }) => Some(transaction),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn detects_both_variants() {
+        assert!(is_ofx(SGML));
+        assert!(is_ofx(XML));
+        assert!(!is_ofx("date,amount\n2026-03-15,12\n"));
+    }
+
+    #[test]
+    fn reads_sgml_with_unclosed_tags() {
+        let rows = read(SGML);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].0, 9);
+        assert!(matches!(rows[1].1, Ok(ImportRow::Skipped(_))));
+
+        let transaction = transactions(&rows)[0];
+        assert_eq!(transaction.transaction_name, "CARREFOUR & CIE");
+        assert_eq!(transaction.amount, 42.5);
+        assert_eq!(transaction.transaction_date.as_deref(), Some("2026-03-15"));
+        assert_eq!(transaction.notes, "CB 14/03");
+        assert_eq!((transaction.account.as_str(), transaction.external_id.as_str()), ("FR76 123", "A1"));
+    }
+
+    #[test]
+    fn reads_xml_card_statements() {
+        let rows = read(XML);
+        let transaction = transactions(&rows)[0];
+        // Sans NAME, le mémo donne le libellé (et n'est pas répété en note)
+        assert_eq!((transaction.transaction_name.as_str(), transaction.notes.as_str()), ("NETFLIX", ""));
+        assert_eq!(transaction.amount, 9.99);
+        assert_eq!((transaction.account.as_str(), transaction.external_id.as_str()), ("4970", "X9"));
+    }
+
+    #[test]
+    fn reports_bad_dates_and_amounts() {
+        let block = "<STMTTRN><DTPOSTED>2026<TRNAMT>-1<NAME>A</STMTTRN>";
+        assert!(transaction(block, "").is_err());
+        assert!(transaction("<NAME>A<TRNAMT>abc", "").is_err());
+        assert!(transaction("<TRNAMT>-1", "").is_err());
+    }
+}
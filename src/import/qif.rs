@@ -0,0 +1,301 @@
+use chrono::NaiveDate;
+use crate::db::NewTransaction;
+use crate::i18n::{lang, tr, Lang};
+use super::{detect_decimal_separator, profile_amount, ImportRow, ReadRow};
+
+// Fichiers QIF (Microsoft Money, Quicken, anciennes versions de GnuCash) : un
+// enregistrement par opération, une ligne par champ, "^" en fin d'enregistrement.
+//   D date   T montant   P bénéficiaire   M mémo
+//   L catégorie ("Alimentation:Courses", "[Compte]" pour un virement)
+//   S E $    catégorie, mémo et montant d'une ligne de ventilation
+// Seules les sections !Type:Bank, !Type:Cash et !Type:CCard sont importées ;
+// le nom du compte vient de la section !Account qui les précède.
+
+pub fn is_qif(text: &str) -> bool {
+    let start = text.trim_start().to_lowercase();
+    ["!type:", "!account", "!option:"].iter().any(|header| start.starts_with(header))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Account,
+    Transactions,
+    Other,
+}
+
+// Un enregistrement d'opération : (code, valeur) de chaque ligne
+struct Record {
+    line: usize,
+    account: String,
+    fields: Vec<(char, String)>,
+}
+
+impl Record {
+    fn field(&self, code: char) -> Option<&str> {
+        self.fields.iter().find(|(c, _)| *c == code).map(|(_, value)| value.as_str()).filter(|value| !value.is_empty())
+    }
+}
+
+// Une opération, ou une ligne de sa ventilation
+struct Part<'a> {
+    category: Option<&'a str>,
+    memo: Option<&'a str>,
+    amount: Option<&'a str>,
+}
+
+fn records(text: &str) -> Vec<Record> {
+    let mut records = Vec::new();
+    let mut section = Section::Other;
+    let mut account = String::new();
+    let mut current: Option<Record> = None;
+    let mut close = |record: Record, section: Section, account: &mut String| match section {
+        Section::Account => *account = record.field('N').unwrap_or_default().to_string(),
+        Section::Transactions => records.push(record),
+        Section::Other => {}
+    };
+
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim_end();
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(header) = line.strip_prefix('!') {
+            if let Some(record) = current.take() {
+                close(record, section, &mut account);
+            }
+            let header = header.trim().to_lowercase();
+            if header == "account" {
+                section = Section::Account;
+            } else if let Some(kind) = header.strip_prefix("type:") {
+                section = if matches!(kind.trim(), "bank" | "cash" | "ccard") { Section::Transactions } else { Section::Other };
+            }
+            // Les options (!Option:AutoSwitch...) ne changent pas de section
+            continue;
+        }
+        if line.starts_with('^') {
+            if let Some(record) = current.take() {
+                close(record, section, &mut account);
+            }
+            continue;
+        }
+        let mut chars = line.chars();
+        let code = chars.next().unwrap_or_default();
+        current
+            .get_or_insert_with(|| Record { line: index + 1, account: account.clone(), fields: Vec::new() })
+            .fields
+            .push((code, chars.as_str().trim().to_string()));
+    }
+    // Dernier enregistrement sans "^"
+    if let Some(record) = current {
+        close(record, section, &mut account);
+    }
+    records
+}
+
+// Catégorie sans la classe ("Loisirs/Vacances" : "Loisirs") ; None pour un virement ("[Épargne]")
+fn clean_category(category: &str) -> Option<&str> {
+    let category = category.split('/').next().unwrap_or_default().trim();
+    (!category.is_empty() && !category.starts_with('[')).then_some(category)
+}
+
+//==================================Read a QIF file====================================
+// Le fichier n'indique pas de budget : il dépend de la catégorie (voir `check_rows`)
+pub fn read(text: &str) -> Vec<ReadRow> {
+    let records = records(text);
+    let field_values = |codes: &[char]| -> Vec<&str> {
+        records
+            .iter()
+            .flat_map(|record| record.fields.iter())
+            .filter(|(code, _)| codes.contains(code))
+            .map(|(_, value)| value.as_str())
+            .collect()
+    };
+    let decimal_separator = detect_decimal_separator(&field_values(&['T', 'U', '$']));
+    let dates: Vec<String> = field_values(&['D']).into_iter().map(normalize_date).collect();
+    let date_format = date_formats().into_iter().find(|format| {
+        dates.iter().all(|date| NaiveDate::parse_from_str(date, format).is_ok())
+    });
+
+    let mut rows = Vec::new();
+    for record in &records {
+        let name = record.field('P').or_else(|| record.field('M'));
+        let date = match (record.field('D'), date_format) {
+            (None, _) => Ok(None),
+            (Some(date), Some(format)) => Ok(NaiveDate::parse_from_str(&normalize_date(date), format).ok()),
+            (Some(date), None) => Err(tr!(InvalidDate, date)),
+        };
+        let parts = parts(record);
+        let split = parts.len() > 1;
+        for (index, part) in parts.iter().enumerate() {
+            let row = match (name, &date) {
+                (None, _) => Err(tr!(ImportEmptyName).to_string()),
+                (_, Err(err)) => Err(err.clone()),
+                (Some(name), Ok(date)) => part_row(record, name, *date, part, decimal_separator).map(|row| match row {
+                    // Les lignes d'une ventilation partagent date, bénéficiaire et compte :
+                    // chacune reçoit sa propre référence pour ne pas passer pour un doublon
+                    ImportRow::Transaction { budget_name, transaction } if split => ImportRow::Transaction {
+                        budget_name,
+                        transaction: NewTransaction { external_id: split_id(record, index), ..transaction },
+                    },
+                    row => row,
+                }),
+            };
+            rows.push((record.line, row));
+        }
+    }
+    rows
+}
+
+fn part_row(record: &Record, name: &str, date: Option<NaiveDate>, part: &Part, decimal_separator: char) -> Result<ImportRow, String> {
+    let amount = -profile_amount(part.amount.unwrap_or_default(), decimal_separator)?.ok_or_else(|| tr!(ImportEmptyAmount).to_string())?;
+    let category = part.category.and_then(clean_category);
+    if part.category.is_some_and(|category| category.trim_start().starts_with('[')) {
+        return Ok(ImportRow::Skipped(tr!(ImportTransferSkipped, name)));
+    }
+    if amount < 0.0 {
+        return Ok(ImportRow::Skipped(tr!(ImportIncomeSkipped, name)));
+    }
+
+    let transaction = NewTransaction {
+        notes: part.memo.or(record.field('M')).filter(|memo| *memo != name).unwrap_or_default().to_string(),
+        transaction_date: date.map(|date| date.to_string()),
+        account: record.account.clone(),
+        category: category.unwrap_or_default().to_string(),
+        ..NewTransaction::new(name, amount)
+    };
+    Ok(ImportRow::Transaction { budget_name: String::new(), transaction })
+}
+
+// Référence d'une ligne de ventilation : l'opération (numéro, date, bénéficiaire,
+// total) et le rang de la ligne, stable d'un import du même fichier à l'autre
+fn split_id(record: &Record, index: usize) -> String {
+    let operation: Vec<&str> = ['N', 'D', 'P', 'T'].iter().map(|code| record.field(*code).unwrap_or_default()).collect();
+    format!("{}#{}", operation.join("|"), index + 1)
+}
+
+// Lignes de ventilation (S, E, $), ou l'opération entière si elle n'est pas ventilée
+fn parts(record: &Record) -> Vec<Part<'_>> {
+    let mut parts: Vec<Part> = Vec::new();
+    for (code, value) in &record.fields {
+        match code {
+            'S' => parts.push(Part { category: Some(value), memo: None, amount: None }),
+            'E' => {
+                if let Some(part) = parts.last_mut() {
+                    part.memo = Some(value);
+                }
+            }
+            '$' => {
+                if let Some(part) = parts.last_mut() {
+                    part.amount = Some(value);
+                }
+            }
+            _ => {}
+        }
+    }
+    if parts.is_empty() {
+        parts.push(Part {
+            category: record.field('L'),
+            memo: record.field('M'),
+            amount: record.field('T').or_else(|| record.field('U')),
+        });
+    }
+    parts
+}
+
+// "3/15'26" et " 3/15/26" deviennent "3/15/2026". Quicken marque les années
+// 2000 d'une apostrophe, complétée d'une espace avant 2010 : "3/15' 6" (2006).
+fn normalize_date(date: &str) -> String {
+    let date: String = date.chars().filter(|c| !c.is_whitespace()).collect();
+    if let Some((start, year)) = date.split_once('\'') {
+        if (1..=2).contains(&year.len()) && year.chars().all(|c| c.is_ascii_digit()) {
+            return format!("{}/20{:0>2}", start, year);
+        }
+    }
+    let date = date.replace('\'', "/");
+    match date.rsplit_once(['/', '.', '-']) {
+        Some((start, year)) if year.len() == 2 && year.chars().all(|c| c.is_ascii_digit()) && !start.contains('-') => {
+            let century = if year < "70" { "20" } else { "19" };
+            format!("{}{}{}{}", start, &date[start.len()..start.len() + 1], century, year)
+        }
+        _ => date,
+    }
+}
+
+// Le jour vient en premier en français, le mois en anglais, comme pour `parse::date`
+fn date_formats() -> Vec<&'static str> {
+    let (day_first, month_first) = ("%d/%m/%Y", "%m/%d/%Y");
+    let mut formats = match lang() {
+        Lang::Fr => vec![day_first, month_first],
+        Lang::En => vec![month_first, day_first],
+    };
+    formats.extend(["%Y-%m-%d", "%d.%m.%Y", "%Y/%m/%d"]);
+    formats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const QIF: &str = "!Option:AutoSwitch\n!Account\nNCompte courant\nTBank\n^\n!Clear:AutoSwitch\n!Type:Bank\nD3/15'26\nT-1,234.56\nPLoyer Mars\nLLogement\n^\nD3/16' 6\nT-85.40\nPCarrefour\nMCourses semaine\nSAlimentation:Courses\nEPain et lait\n$-60.40\nSLoisirs/Vacances\n$-25.00\n^\nD3/17'26\nT2,500.00\nPEmployeur\n^\nD3/18'26\nT-200.00\nPVirement\nL[Livret A]\n^\n!Type:Cat\nNLogement\n^\n";
+
+    #[test]
+    fn normalizes_two_digit_years() {
+        assert_eq!(normalize_date("3/15'26"), "3/15/2026");
+        assert_eq!(normalize_date(" 3/15/26"), "3/15/2026");
+        assert_eq!(normalize_date("3/15' 6"), "3/15/2006");
+        assert_eq!(normalize_date("12/31'99"), "12/31/2099");
+        assert_eq!(normalize_date("15.03.98"), "15.03.1998");
+        assert_eq!(normalize_date("2026-03-15"), "2026-03-15");
+        assert_eq!(normalize_date("15/03/2026"), "15/03/2026");
+    }
+
+    #[test]
+    fn reads_records_splits_and_skipped_lines() {
+        let rows = read(QIF);
+        let lines: Vec<usize> = rows.iter().map(|(line, _)| *line).collect();
+        assert_eq!(lines, vec![8, 13, 13, 23, 27]);
+
+        let transaction = |index: usize| match &rows[index].1 {
+            Ok(ImportRow::Transaction { transaction, .. }) => transaction.clone(),
+            other => panic!("ligne {} : {:?}", index, other.as_ref().err()),
+        };
+        let rent = transaction(0);
+        assert_eq!((rent.transaction_name.as_str(), rent.amount), ("Loyer Mars", 1234.56));
+        assert_eq!(rent.transaction_date.as_deref(), Some("2026-03-15"));
+        assert_eq!((rent.category.as_str(), rent.account.as_str()), ("Logement", "Compte courant"));
+        assert!(rent.external_id.is_empty());
+
+        // Une transaction par ligne de ventilation, chacune avec sa référence
+        let (food, leisure) = (transaction(1), transaction(2));
+        assert_eq!((food.amount, food.category.as_str(), food.notes.as_str()), (60.4, "Alimentation:Courses", "Pain et lait"));
+        assert_eq!((leisure.amount, leisure.category.as_str(), leisure.notes.as_str()), (25.0, "Loisirs", "Courses semaine"));
+        assert_eq!(food.transaction_date.as_deref(), Some("2006-03-16"));
+        assert_ne!(food.fingerprint(), leisure.fingerprint());
+        assert!(food.external_id.ends_with("#1") && leisure.external_id.ends_with("#2"));
+
+        // Entrée d'argent et virement entre comptes
+        assert!(matches!(rows[3].1, Ok(ImportRow::Skipped(_))));
+        assert!(matches!(rows[4].1, Ok(ImportRow::Skipped(_))));
+    }
+
+    #[test]
+    fn equal_split_parts_are_not_duplicates() {
+        let text = "!Type:Bank\nD03/16/2026\nT-40.00\nPCarrefour\nSAlimentation\n$-20.00\nSLoisirs\n$-20.00\n^\n";
+        let fingerprints: Vec<String> = read(text)
+            .into_iter()
+            .filter_map(|(_, row)| match row {
+                Ok(ImportRow::Transaction { transaction, .. }) => Some(transaction.fingerprint()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(fingerprints.len(), 2);
+        assert_ne!(fingerprints[0], fingerprints[1]);
+    }
+
+    #[test]
+    fn detects_qif_headers() {
+        assert!(is_qif("\n!Type:Bank\nD1/1/26\n"));
+        assert!(is_qif("!Account\nNCompte\n^\n"));
+        assert!(!is_qif("date,amount\n"));
+    }
+}
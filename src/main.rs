@@ -1,18 +1,101 @@
+mod batch;
+mod cli;
+mod config;
+mod dashboard;
 mod db;
+mod events;
+mod export;
+mod i18n;
+mod import;
 mod menu;
+mod output;
+mod parse;
+mod query;
+mod quick_add;
+mod repl;
 
+use clap::Parser;
+use cli::{run_command, run_config_command, Cli, Command};
+use config::Config;
 use db::BudgetManager;
+use events::AlertPrinter;
+use i18n::{tr, Lang};
 use menu::interactive_menu;
 use std::error::Error;
+use std::process::ExitCode;
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let db_name = "budget_manager.db";
+fn main() -> ExitCode {
+    // Code de sortie non nul en cas d'échec, pour les scripts
+    match run() {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("{}", tr!(Error, err));
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run() -> Result<bool, Box<dyn Error>> {
+    // clap lit les montants et les dates selon la langue et la configuration :
+    // elles sont donc fixées avant l'analyse complète des arguments.
+    // Les options de la ligne de commande sont prioritaires sur le fichier de configuration
+    let args: Vec<String> = std::env::args().collect();
+    let config = match Config::load(early_option(&args, "config").as_deref().unwrap_or(config::DEFAULT_PATH)) {
+        Ok(config) => config,
+        // `config show` et `config set` signalent l'erreur et permettent de corriger le fichier
+        Err(_) if early_command(&args) == Some("config") => Config::default(),
+        Err(err) => return Err(format!("{}\n{}", err, tr!(ConfigFixHint)).into()),
+    };
+    let lang = early_option(&args, "lang").and_then(|lang| Lang::parse(&lang));
+    i18n::set_lang(lang.or(config.language).unwrap_or_else(Lang::from_env));
+    output::apply_color_mode(config.colors);
+    let database = config.database.clone();
+    config::set(config);
+
+    let cli = Cli::parse_from(args);
+    let db_path = cli.db.unwrap_or(database);
+
+    // La configuration se modifie sans ouvrir la base de données
+    if let Some(Command::Config(command)) = cli.command {
+        return run_config_command(&cli.config, command);
+    }
 
     // Initialiser le gestionnaire de budget
-    let budget_manager = BudgetManager::new(db_name)?;
+    let mut budget_manager = BudgetManager::new(&db_path)?;
+    budget_manager.subscribe(AlertPrinter);
 
-    // Lancer le menu interactif
-    interactive_menu(&budget_manager)?;
+    match cli.command {
+        Some(command) => run_command(&budget_manager, command, cli.format, &cli.config),
+        None => {
+            // Lancer le menu interactif
+            interactive_menu(&budget_manager, &cli.config)?;
+            Ok(true)
+        }
+    }
+}
+
+// Sous-commande (premier argument qui n'est ni une option ni la valeur d'une
+// option globale) lue avant clap
+fn early_command(args: &[String]) -> Option<&str> {
+    let mut args = args.iter().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--db" | "--config" | "--format" | "--lang" => {
+                args.next();
+            }
+            arg if arg.starts_with('-') => {}
+            arg => return Some(arg),
+        }
+    }
+    None
+}
 
-    Ok(())
+// Valeur d'une option globale (`--lang en` ou `--lang=en`) lue avant clap
+fn early_option(args: &[String], name: &str) -> Option<String> {
+    let flag = format!("--{}", name);
+    let prefix = format!("{}=", flag);
+    args.iter().enumerate().skip(1).find_map(|(i, arg)| {
+        if *arg == flag { args.get(i + 1).cloned() } else { arg.strip_prefix(&prefix).map(String::from) }
+    })
 }
@@ -1,212 +1,1114 @@
-use console::{Style, Term};
-use std::io::{self, Write};
-use crate::db::BudgetManager;
-
-pub fn interactive_menu(budget_manager: &BudgetManager) -> Result<(), Box<dyn std::error::Error>> {
-    let _term = Term::stdout();
-    let prompt_style = Style::new().bold().green();
-    let error_style = Style::new().red();
-    let menu_style = Style::new().cyan().bold();
-
-    loop {
-        println!("{}", menu_style.apply_to("=== Menu Gestionnaire Budget ==="));
-        println!("1.Ajouter un budget");
-        println!("2.Supprimer un budget");
-        println!("3.Modifier un budget");
-        println!("4.Afficher tous les budgets");
-        println!("5.Ajouter une transaction");
-        println!("6.Supprimer une transaction");
-        println!("7.Modifier une transaction");
-        println!("8.Afficher le solde restant d'un budget");
-        println!("9.Quitter\n");
-        print!("{}", prompt_style.apply_to("Choisissez une option : "));
-        io::stdout().flush()?;
-
-        let mut choice = String::new();
-        io::stdin().read_line(&mut choice)?;
-        let choice = choice.trim();
-
-        match choice {
-            "1" => {
-                print!("Nom du budget : ");
-                io::stdout().flush()?;
-                let mut budget_name = String::new();
-                io::stdin().read_line(&mut budget_name)?;
-                let budget_name = budget_name.trim();
-
-                print!("Montant total du budget : ");
-                io::stdout().flush()?;
-                let mut total_amount = String::new();
-                io::stdin().read_line(&mut total_amount)?;
-
-                 // Conversion du montant total
-                let total_amount: f64 = match total_amount.trim().parse() {
-                    Ok(val) => val,
-                    Err(_) => {
-                        println!("{}", error_style.apply_to(" Montant invalide. Réessayez "));
-                        continue;
-                    }
-                };
-
-                // Ajout du budget dans la base de données
-             match budget_manager.add_budget(budget_name, total_amount as f64) {
-             Ok(_) => {
-                println!("{}", prompt_style.apply_to(format!("Le budget '{}' a été ajouté avec succès !", budget_name)));
-            }
-             Err(err) => {
-            eprintln!("{}: {}", error_style.apply_to("Erreur lors de l'ajout du budget"), err);
-        }
-    }
-            }
-            "2" => {
-                print!("Nom du budget à supprimer :");
-                io::stdout().flush()?;
-                let mut budget_name = String::new();
-                io::stdin().read_line(&mut budget_name)?;
-                let budget_name = budget_name.trim();
-
-                if let Err(err) = budget_manager.remove_budget(budget_name) {
-                    eprintln!(" Erreur : {} ", err);
-                }
-            }
-            "3" => {
-                print!("Nom du budget à modifier : ");
-                io::stdout().flush()?;
-                let mut old_budget_name = String::new();
-                io::stdin().read_line(&mut old_budget_name)?;
-                let old_budget_name = old_budget_name.trim();
-
-                print!("Nouveau nom du budget : ");
-                io::stdout().flush()?;
-                let mut new_budget_name = String::new();
-                io::stdin().read_line(&mut new_budget_name)?;
-                let new_budget_name = new_budget_name.trim();
-
-                print!("Nouveau montant total du budget : ");
-                io::stdout().flush()?;
-                let mut new_total_amount = String::new();
-                io::stdin().read_line(&mut new_total_amount)?;
-                let new_total_amount: f64 = match new_total_amount.trim().parse() {
-                    Ok(val) => val,
-                    Err(_) => {
-                        println!("{}", error_style.apply_to(" Montant invalide. Réessayez  "));
-                        continue;
-                    }
-                };
-
-                if let Err(err) = budget_manager.edit_budget(new_budget_name.to_string(), old_budget_name.to_string(), new_total_amount) {
-                    eprintln!(" Erreur : {}", err);
-                }
-            }
-            "4" => {
-                if let Err(err) = budget_manager.display_budgets() {
-                    eprintln!(" Erreur : {}", err);
-                }
-            }
-            "5" => {
-                print!("Nom du budget : ");
-                io::stdout().flush()?;
-                let mut budget_name = String::new();
-                io::stdin().read_line(&mut budget_name)?;
-                let budget_name = budget_name.trim();
-
-                print!("Nom de la transaction : ");
-                io::stdout().flush()?;
-                let mut transaction_name = String::new();
-                io::stdin().read_line(&mut transaction_name)?;
-                let transaction_name = transaction_name.trim();
-
-                print!("Montant de la transaction :\n ");
-                io::stdout().flush()?;
-                let mut amount = String::new();
-                io::stdin().read_line(&mut amount)?;
-                let amount: f64 = match amount.trim().parse() {
-                    Ok(val) => val,
-                    Err(_) => {
-                        println!("{}", error_style.apply_to("Montant invalide. Réessayez "));
-                        continue;
-                    }
-                };
-
-                if let Err(err) = budget_manager.add_transaction(budget_name, transaction_name.to_string(), amount) {
-                    eprintln!(" Erreur : {}", err);
-                }
-            }
-            "6" => {
-                print!("Nom du budget : ");
-                io::stdout().flush()?;
-                let mut budget_name = String::new();
-                io::stdin().read_line(&mut budget_name)?;
-                let budget_name = budget_name.trim();
-
-                print!("Nom de la transaction à supprimer : ");
-                io::stdout().flush()?;
-                let mut transaction_name = String::new();
-                io::stdin().read_line(&mut transaction_name)?;
-                let transaction_name = transaction_name.trim();
-
-                if let Err(err) = budget_manager.remove_transaction(budget_name, transaction_name) {
-                    eprintln!("Erreur : {}", err);
-                }
-            }
-            "7" => {
-                print!("Nom du budget : ");
-                io::stdout().flush()?;
-                let mut budget_name = String::new();
-                io::stdin().read_line(&mut budget_name)?;
-                let budget_name = budget_name.trim();
-
-                print!("Nom de la transaction à modifier : ");
-                io::stdout().flush()?;
-                let mut old_name = String::new();
-                io::stdin().read_line(&mut old_name)?;
-                let old_name = old_name.trim();
-
-                print!("Nouveau nom de la transaction : ");
-                io::stdout().flush()?;
-                let mut new_name = String::new();
-                io::stdin().read_line(&mut new_name)?;
-                let new_name = new_name.trim();
-
-                print!("Nouveau montant de la transaction : ");
-                io::stdout().flush()?;
-                let mut new_amount = String::new();
-                io::stdin().read_line(&mut new_amount)?;
-                let new_amount: f64 = match new_amount.trim().parse() {
-                    Ok(val) => val,
-                    Err(_) => {
-                        println!("{}", error_style.apply_to("Montant invalide. Réessayez "));
-                        continue;
-                    }
-                };
-
-                if let Err(err) = budget_manager.edit_transaction(budget_name, old_name.to_string(), new_name.to_string(), new_amount) {
-                    eprintln!(" Erreur : {}", err);
-                }
-            }
-            "8" => {
-                print!("Nom du budget : ");
-                io::stdout().flush()?;
-                let mut budget_name = String::new();
-                io::stdin().read_line(&mut budget_name)?;
-                let budget_name = budget_name.trim();
-                
-               //
-                if let Err(err) = budget_manager.show_remaining_amount(budget_name) {
-                    eprintln!("Erreur : {}", err);
-                }
-            }
-            "9" => {
-                println!("{}", menu_style.apply_to("Au revoir !!!"));
-                break;
-            }
-            _ => {
-                println!("{}", error_style.apply_to("Option invalide, veuillez réessayer "));
-            }
-        }
-
-        println!(); // Ligne vide pour la lisibilité
-    }
-
-    Ok(())
-}
+use console::{Style, Term};
+use dialoguer::theme::ColorfulTheme;
+use dialoguer::{Confirm, FuzzySelect, Input, Select};
+use std::io::{self, Write};
+use crate::db::{
+    AlertRule, AlertRuleKind, AssignmentRule, BudgetManager, Encoding, ImportProfile, NewTransaction, OverspendCheck, OverspendPolicy,
+    SignConvention, TransactionRecord, UnassignedTransaction,
+};
+use crate::config::{self, Config, UnknownBudgetPolicy};
+use crate::i18n::{self, tr};
+use crate::import::{self, BudgetMappings, ImportRow, NewMapping, ReadRow};
+use crate::parse;
+use crate::output::{self, print_transactions, OutputFormat};
+use crate::query::{Filter, Query};
+use crate::quick_add::QuickAdd;
+use prettytable::{Table, row};
+
+pub fn interactive_menu(budget_manager: &BudgetManager, config_path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let _term = Term::stdout();
+    let prompt_style = Style::new().bold().green();
+    let error_style = Style::new().red();
+    let menu_style = Style::new().cyan().bold();
+
+    loop {
+        println!("{}", menu_style.apply_to(tr!(MenuTitle)));
+        println!("{}", tr!(MenuAddBudget));
+        println!("{}", tr!(MenuRemoveBudget));
+        println!("{}", tr!(MenuEditBudget));
+        println!("{}", tr!(MenuListBudgets));
+        println!("{}", tr!(MenuAddTransaction));
+        println!("{}", tr!(MenuRemoveTransaction));
+        println!("{}", tr!(MenuEditTransaction));
+        println!("{}", tr!(MenuShowRemaining));
+        println!("{}", tr!(MenuAlerts));
+        println!("{}", tr!(MenuPolicy));
+        println!("{}", tr!(MenuSearch));
+        println!("{}", tr!(MenuQuery));
+        println!("{}", tr!(MenuDashboard));
+        println!("{}", tr!(MenuConfig));
+        println!("{}", tr!(MenuRepl));
+        println!("{}", tr!(MenuQuickAdd));
+        println!("{}", tr!(MenuImport));
+        println!("{}", tr!(MenuAssignRules));
+        println!("{}", tr!(MenuInbox, budget_manager.count_unassigned()?));
+        println!("{}\n", tr!(MenuQuit));
+        print!("{}", prompt_style.apply_to(format!("{} : ", tr!(MenuChoose))));
+        io::stdout().flush()?;
+
+        let mut choice = String::new();
+        io::stdin().read_line(&mut choice)?;
+        let choice = choice.trim();
+
+        match choice {
+            "1" => {
+                let budget_name = read_text(tr!(PromptBudgetName), None)?;
+                let total_amount = read_budget_amount(tr!(PromptBudgetTotal), None)?;
+
+                // Ajout du budget dans la base de données
+                match budget_manager.add_budget(&budget_name, total_amount) {
+                    Ok(_) => {
+                        println!("{}", prompt_style.apply_to(tr!(BudgetAddedMenu, budget_name)));
+                    }
+                    Err(err) => {
+                        eprintln!("{}", error_style.apply_to(tr!(Error, err)));
+                    }
+                }
+            }
+            "2" => {
+                let Some(budget_name) = select_budget(budget_manager, tr!(PromptBudgetToRemove))? else { continue };
+                if !confirm(&tr!(ConfirmRemoveBudget, budget_name))? {
+                    println!("{}", tr!(DeletionCancelled));
+                    continue;
+                }
+
+                if let Err(err) = budget_manager.remove_budget(&budget_name) {
+                    eprintln!("{}", tr!(Error, err));
+                }
+            }
+            "3" => {
+                let Some(old_budget_name) = select_budget(budget_manager, tr!(PromptBudgetToEdit))? else { continue };
+                let current_total = budget_manager.get_budget(&old_budget_name)?.map(|budget| budget.total_amount);
+
+                let new_budget_name = read_text(tr!(PromptNewBudgetName), Some(&old_budget_name))?;
+                let new_total_amount = read_budget_amount(tr!(PromptNewBudgetTotal), current_total)?;
+
+                if let Err(err) = budget_manager.edit_budget(new_budget_name, old_budget_name, Some(new_total_amount)) {
+                    eprintln!("{}", tr!(Error, err));
+                }
+            }
+            "4" => {
+                if let Err(err) = budget_manager.display_budgets(OutputFormat::Table) {
+                    eprintln!("{}", tr!(Error, err));
+                }
+            }
+            "5" => {
+                let Some(budget_name) = select_budget(budget_manager, tr!(PromptBudget))? else { continue };
+                let transaction_name = read_text(tr!(PromptTransactionName), None)?;
+                let amount = read_amount(tr!(PromptTransactionAmount), None)?;
+                let transaction_date = read_date(tr!(PromptDate))?;
+
+                let payee = read_optional(tr!(PromptPayee))?;
+                let notes = read_optional(tr!(PromptNotes))?;
+                let tags = read_optional(tr!(PromptTags))?
+                    .split_whitespace()
+                    .map(String::from)
+                    .collect();
+                let mut transaction = NewTransaction { payee, notes, tags, transaction_date, ..NewTransaction::new(transaction_name, amount) };
+                // Le budget est choisi : les règles n'ajoutent que catégorie et tags
+                budget_manager.apply_assignment_rules(&mut transaction)?;
+
+                let Some(overspend_confirmed) = confirm_overspend(budget_manager, &budget_name, amount)? else { continue };
+                // Les refus (budget introuvable, dépassement) sont déjà affichés
+                match budget_manager.add_transaction(&budget_name, transaction, overspend_confirmed) {
+                    Ok(()) | Err(rusqlite::Error::InvalidParameterName(_)) => {}
+                    Err(err) => eprintln!("{}", tr!(Error, err)),
+                }
+            }
+            "6" => {
+                let Some(budget_name) = select_budget(budget_manager, tr!(PromptBudget))? else { continue };
+                let Some(transaction) = select_transaction(budget_manager, &budget_name, tr!(PromptTransactionToRemove))? else { continue };
+                if !confirm(&tr!(ConfirmRemoveTransaction, transaction.transaction_name, i18n::amount(transaction.amount)))? {
+                    println!("{}", tr!(DeletionCancelled));
+                    continue;
+                }
+
+                if let Err(err) = budget_manager.remove_transaction_by_id(&budget_name, transaction.id) {
+                    eprintln!("{}", tr!(Error, err));
+                }
+            }
+            "7" => {
+                let Some(budget_name) = select_budget(budget_manager, tr!(PromptBudget))? else { continue };
+                let Some(transaction) = select_transaction(budget_manager, &budget_name, tr!(PromptTransactionToEdit))? else { continue };
+                let new_name = read_text(tr!(PromptNewTransactionName), Some(&transaction.transaction_name))?;
+                let new_amount = read_amount(tr!(PromptNewTransactionAmount), Some(transaction.amount))?;
+
+                let extra_spending = budget_manager.edit_extra_spending_by_id(&budget_name, transaction.id, new_amount).unwrap_or(0.0);
+                let Some(overspend_confirmed) = confirm_overspend(budget_manager, &budget_name, extra_spending)? else { continue };
+                if let Err(err) = budget_manager.edit_transaction_by_id(&budget_name, transaction.id, new_name, new_amount, overspend_confirmed) {
+                    eprintln!("{}", tr!(Error, err));
+                }
+            }
+            "8" => {
+                let Some(budget_name) = select_budget(budget_manager, tr!(PromptBudget))? else { continue };
+
+                if let Err(err) = budget_manager.show_remaining_amount(&budget_name) {
+                    eprintln!("{}", tr!(Error, err));
+                }
+            }
+            "9" => {
+                let Some(budget_name) = select_budget(budget_manager, tr!(PromptBudget))? else { continue };
+                if let Err(err) = manage_alert_rules(budget_manager, &budget_name) {
+                    eprintln!("{}", tr!(Error, err));
+                }
+            }
+            "10" => {
+                let Some(budget_name) = select_budget(budget_manager, tr!(PromptBudget))? else { continue };
+                let current = budget_manager.overspend_policy(&budget_name)?;
+                let labels: Vec<&str> = OverspendPolicy::ALL.iter().map(|policy| policy.label()).collect();
+                let choice = Select::with_theme(&ColorfulTheme::default())
+                    .with_prompt(tr!(PromptPolicy))
+                    .items(&labels)
+                    .default(OverspendPolicy::ALL.iter().position(|policy| *policy == current).unwrap_or(0))
+                    .interact_opt()?;
+                let Some(policy) = choice.map(|i| OverspendPolicy::ALL[i]) else { continue };
+                match budget_manager.set_overspend_policy(&budget_name, policy) {
+                    Ok(()) => println!("{}", prompt_style.apply_to(tr!(PolicySet, budget_name, policy.label()))),
+                    Err(err) => eprintln!("{}", tr!(Error, err)),
+                }
+            }
+            "11" => {
+                println!("{}", tr!(SearchHelp));
+                let query = read_text(tr!(PromptSearch), None)?;
+                let hits = budget_manager.search_transactions(&query);
+                if let Err(err) = hits.map_err(Into::into).and_then(|hits| print_transactions(&hits, OutputFormat::Table)) {
+                    eprintln!("{}", tr!(Error, err));
+                }
+            }
+            "12" => {
+                let query = read_text(tr!(PromptQuery), None)?;
+                if let Err(err) = run_query(budget_manager, &query, OutputFormat::Table) {
+                    eprintln!("{}", error_style.apply_to(err));
+                }
+            }
+            "13" => {
+                if let Err(err) = crate::dashboard::run(budget_manager) {
+                    eprintln!("{}", error_style.apply_to(tr!(Error, err)));
+                }
+            }
+            "14" => {
+                if let Err(err) = edit_config(config_path) {
+                    eprintln!("{}", error_style.apply_to(tr!(Error, err)));
+                }
+            }
+            "15" => {
+                if let Err(err) = crate::repl::run(budget_manager, OutputFormat::Table, config_path) {
+                    eprintln!("{}", error_style.apply_to(tr!(Error, err)));
+                }
+            }
+            "16" => {
+                let budget_names: Vec<String> = budget_manager.list_budgets()?.into_iter().map(|budget| budget.budget_name).collect();
+                let input: String = Input::with_theme(&ColorfulTheme::default())
+                    .with_prompt(tr!(PromptQuickAdd))
+                    .validate_with(|text: &String| QuickAdd::parse(text, &budget_names).map(|_| ()))
+                    .interact_text()?;
+                if let Err(err) = run_quick_add(budget_manager, &input, false) {
+                    eprintln!("{}", error_style.apply_to(tr!(Error, err)));
+                }
+            }
+            "17" => {
+                let file_path = read_text(tr!(PromptImportFile), None)?;
+                let Some(profile) = select_import_format(budget_manager, &file_path)? else { continue };
+                if let Err(err) = run_import(budget_manager, &file_path, profile.as_ref(), ImportOptions::default()) {
+                    eprintln!("{}", error_style.apply_to(tr!(Error, err)));
+                }
+            }
+            "18" => {
+                if let Err(err) = manage_assignment_rules(budget_manager) {
+                    eprintln!("{}", error_style.apply_to(tr!(Error, err)));
+                }
+            }
+            "19" => {
+                if let Err(err) = manage_inbox(budget_manager) {
+                    eprintln!("{}", error_style.apply_to(tr!(Error, err)));
+                }
+            }
+            "0" => {
+                println!("{}", menu_style.apply_to(tr!(Goodbye)));
+                break;
+            }
+            _ => {
+                println!("{}", error_style.apply_to(tr!(MenuInvalidOption)));
+            }
+        }
+
+        println!(); // Ligne vide pour la lisibilité
+    }
+
+    Ok(())
+}
+
+// Analyse et exécute une requête du mini-langage, puis affiche les résultats
+pub fn run_query(budget_manager: &BudgetManager, query: &str, format: OutputFormat) -> Result<(), Box<dyn std::error::Error>> {
+    let query = Query::parse(query)?;
+    let records = budget_manager.query_transactions(&query)?;
+    print_transactions(&records, format)
+}
+
+//==================================Quick add====================================
+// Analyse une saisie d'ajout rapide, affiche l'aperçu puis enregistre la transaction.
+// Avec `assume_yes`, ni aperçu à confirmer ni choix du budget : le dépassement
+// d'un budget en mode "warn" est accepté.
+pub fn run_quick_add(budget_manager: &BudgetManager, input: &str, assume_yes: bool) -> Result<bool, Box<dyn std::error::Error>> {
+    let budget_names: Vec<String> = budget_manager.list_budgets()?.into_iter().map(|budget| budget.budget_name).collect();
+    let mut entry = QuickAdd::parse(input, &budget_names)?;
+    // Les règles d'affectation complètent les tags et la catégorie, et donnent
+    // le budget s'il n'a pas été reconnu dans la saisie
+    let mut transaction = entry.transaction();
+    let rule_budget = budget_manager.apply_assignment_rules(&mut transaction)?;
+    entry.tags = transaction.tags;
+    entry.category = transaction.category;
+    if entry.budget.is_none() {
+        entry.budget = rule_budget.filter(|budget_name| budget_names.contains(budget_name));
+    }
+    if entry.budget.is_none() {
+        if assume_yes {
+            return Err(tr!(QuickAddNoBudget, input).into());
+        }
+        // Budget non reconnu : choix dans la liste
+        let Some(budget_name) = select_budget(budget_manager, tr!(PromptBudget))? else { return Ok(false) };
+        entry.budget = Some(budget_name);
+    }
+    let budget_name = entry.budget.clone().unwrap_or_default();
+
+    entry.print_preview();
+    let overspend_confirmed = if assume_yes {
+        true
+    } else {
+        let confirmed = Confirm::with_theme(&ColorfulTheme::default()).with_prompt(tr!(QuickAddConfirm)).default(true).interact()?;
+        if !confirmed {
+            println!("{}", tr!(OperationCancelled));
+            return Ok(false);
+        }
+        let Some(overspend_confirmed) = confirm_overspend(budget_manager, &budget_name, entry.amount)? else { return Ok(false) };
+        overspend_confirmed
+    };
+    match budget_manager.add_transaction(&budget_name, entry.transaction(), overspend_confirmed) {
+        Ok(()) => Ok(true),
+        // Refus déjà affiché par add_transaction
+        Err(rusqlite::Error::InvalidParameterName(_)) => Ok(false),
+        Err(err) => Err(err.into()),
+    }
+}
+
+//==================================CSV import====================================
+// Options d'un import depuis le menu ou la ligne de commande
+#[derive(Debug, Default, Clone)]
+pub struct ImportOptions {
+    // Compte d'origine des lignes qui n'en indiquent pas (par défaut : le nom du profil)
+    pub account: Option<String>,
+    // Aperçu seulement, rien n'est enregistré
+    pub dry_run: bool,
+    // Importer aussi les doublons
+    pub force: bool,
+    // Pas de confirmation après l'aperçu
+    pub assume_yes: bool,
+    // Sort des lignes au budget inconnu (par défaut : clé `import.unknown_budget` de la configuration)
+    pub unknown_budget: Option<UnknownBudgetPolicy>,
+}
+
+// Lit et vérifie le fichier, affiche l'aperçu ligne par ligne puis importe après
+// confirmation. Les doublons ne sont importés qu'avec `force` ou si l'utilisateur
+// le demande. Retourne false si des lignes sont en erreur ou en cas d'abandon ;
+// les lignes ignorées et les doublons écartés ne font pas échouer l'import.
+pub fn run_import(
+    budget_manager: &BudgetManager,
+    file_path: &str,
+    profile: Option<&ImportProfile>,
+    options: ImportOptions,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let account = options.account.as_deref().or(profile.map(|profile| profile.name.as_str())).unwrap_or_default();
+    let default_budget = profile.map_or("", |profile| profile.default_budget.as_str());
+    let policy = options.unknown_budget.unwrap_or(config::get().import.unknown_budget);
+    config::get().check_new_budget_amount(policy)?;
+    let rows = import::read_file(file_path, profile)?;
+    let mappings = budget_mappings(budget_manager, &rows, policy, !options.assume_yes)?;
+    let rows = import::check_rows(budget_manager, rows, account, &mappings, default_budget, policy)?;
+    import::print_preview(&rows, options.force);
+    let duplicates = rows.iter().filter(|checked| checked.duplicate).count();
+    if options.dry_run {
+        println!("{}", tr!(ImportDryRun));
+        return Ok(rows.iter().all(|checked| checked.row.is_ok()));
+    }
+
+    let mut force = options.force;
+    let mut overspend_confirmed = options.assume_yes;
+    if !options.assume_yes {
+        if duplicates > 0 && !force {
+            force = Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt(tr!(ConfirmImportDuplicates, duplicates))
+                .default(false)
+                .interact()?;
+        }
+        if !Confirm::with_theme(&ColorfulTheme::default()).with_prompt(tr!(ConfirmImport)).default(true).interact()? {
+            println!("{}", tr!(OperationCancelled));
+            return Ok(false);
+        }
+        let overspent = import::overspent_budgets(budget_manager, &rows, force)?;
+        if !overspent.is_empty() {
+            overspend_confirmed = Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt(tr!(ConfirmImportOverspend, overspent.join(", ")))
+                .default(false)
+                .interact()?;
+        }
+    }
+    let report = import::import_rows(budget_manager, rows, &mappings, force, overspend_confirmed)?;
+    println!("{}", tr!(ImportSummary, report.imported, report.skipped, report.duplicates, report.failed));
+    if report.parked > 0 {
+        println!("{}", tr!(ImportUnassignedSummary, report.parked));
+    }
+    Ok(report.failed == 0)
+}
+
+// Budgets des comptes (OFX, CAMT.053, MT940) et des catégories (QIF) des lignes
+// qu'aucune règle d'affectation ne classe. Une catégorie qui porte le nom d'un
+// budget (ou dont une partie le porte : "Alimentation:Courses") va dans ce
+// budget. En mode interactif, le budget des comptes et catégories inconnus est
+// demandé puis mémorisé pour les imports suivants ; avec la politique "map",
+// celui des noms de budget inconnus aussi (alias).
+fn budget_mappings(
+    budget_manager: &BudgetManager,
+    rows: &[ReadRow],
+    policy: UnknownBudgetPolicy,
+    interactive: bool,
+) -> Result<BudgetMappings, Box<dyn std::error::Error>> {
+    let mut mappings = BudgetMappings {
+        accounts: budget_manager.account_budgets()?,
+        categories: budget_manager.category_budgets()?,
+        aliases: budget_manager.budget_aliases()?,
+        chosen: Vec::new(),
+    };
+    let budget_names: Vec<String> = budget_manager.list_budgets()?.into_iter().map(|budget| budget.budget_name).collect();
+    let sources = import::unmapped_sources(rows, &budget_manager.assignment_rules()?, &mappings, &budget_names);
+
+    for account in sources.accounts {
+        if mappings.accounts.contains_key(&account) || !interactive {
+            continue;
+        }
+        if let Some(budget_name) = select_budget(budget_manager, &tr!(PromptAccountBudget, account))? {
+            mappings.chosen.push(NewMapping::Account(account.clone(), budget_name.clone()));
+            mappings.accounts.insert(account, budget_name);
+        }
+    }
+    for category in sources.categories {
+        if mappings.categories.contains_key(&category) {
+            continue;
+        }
+        let same_name = std::iter::once(category.as_str())
+            .chain(category.rsplit(':'))
+            .find_map(|part| budget_names.iter().find(|name| parse::fold(name.trim()) == parse::fold(part.trim())));
+        if let Some(budget_name) = same_name {
+            mappings.categories.insert(category, budget_name.clone());
+        } else if interactive {
+            if let Some(budget_name) = select_budget(budget_manager, &tr!(PromptCategoryBudget, category))? {
+                mappings.chosen.push(NewMapping::Category(category.clone(), budget_name.clone()));
+                mappings.categories.insert(category, budget_name);
+            }
+        }
+    }
+    if policy == UnknownBudgetPolicy::Map && interactive {
+        for alias in sources.budgets {
+            if let Some(budget_name) = select_budget(budget_manager, &tr!(PromptAliasBudget, alias))? {
+                mappings.chosen.push(NewMapping::Alias(alias.clone(), budget_name.clone()));
+                mappings.aliases.insert(alias, budget_name);
+            }
+        }
+    }
+    Ok(mappings)
+}
+
+// Choix du format du fichier : standard, profil enregistré ou nouveau profil
+// créé à partir du fichier. Ok(None) si l'utilisateur abandonne.
+fn select_import_format(budget_manager: &BudgetManager, file_path: &str) -> Result<Option<Option<ImportProfile>>, Box<dyn std::error::Error>> {
+    let mut profiles = budget_manager.list_import_profiles()?;
+    let items: Vec<String> = std::iter::once(tr!(ImportStandardFormat).to_string())
+        .chain(profiles.iter().map(|profile| profile.name.clone()))
+        .chain(std::iter::once(tr!(ImportNewProfile).to_string()))
+        .collect();
+    let choice = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt(tr!(ImportChooseFormat))
+        .items(&items)
+        .default(0)
+        .interact_opt()?;
+    Ok(match choice {
+        None => None,
+        Some(0) => Some(None),
+        Some(i) if i <= profiles.len() => Some(Some(profiles.swap_remove(i - 1))),
+        Some(_) => create_import_profile(budget_manager, file_path, None)?.map(Some),
+    })
+}
+
+// Assistant de création d'un profil d'import à partir d'un fichier exemple :
+// chaque réglage est proposé avec la valeur détectée dans le fichier.
+pub fn create_import_profile(budget_manager: &BudgetManager, sample_path: &str, name: Option<&str>) -> Result<Option<ImportProfile>, Box<dyn std::error::Error>> {
+    let theme = ColorfulTheme::default();
+    let bytes = std::fs::read(sample_path)?;
+
+    let detected_encoding = import::detect_encoding(&bytes);
+    let labels: Vec<&str> = Encoding::ALL.iter().map(|encoding| encoding.as_str()).collect();
+    let Some(choice) = Select::with_theme(&theme)
+        .with_prompt(tr!(PromptEncoding))
+        .items(&labels)
+        .default(Encoding::ALL.iter().position(|encoding| *encoding == detected_encoding).unwrap_or(0))
+        .interact_opt()?
+    else {
+        return Ok(None);
+    };
+    let encoding = Encoding::ALL[choice];
+    let text = import::decode(&bytes, encoding);
+
+    const DELIMITERS: [(char, &str); 4] = [(';', ";"), (',', ","), ('\t', "tab"), ('|', "|")];
+    let detected_delimiter = import::detect_delimiter(&text);
+    let labels: Vec<&str> = DELIMITERS.iter().map(|(_, label)| *label).collect();
+    let Some(choice) = Select::with_theme(&theme)
+        .with_prompt(tr!(PromptDelimiter))
+        .items(&labels)
+        .default(DELIMITERS.iter().position(|(delimiter, _)| *delimiter == detected_delimiter).unwrap_or(0))
+        .interact_opt()?
+    else {
+        return Ok(None);
+    };
+    let delimiter = DELIMITERS[choice].0;
+
+    let records = import::read_records(&text, delimiter)?;
+    println!("{}", tr!(ProfileSample));
+    for (index, (_, fields)) in records.iter().take(6).enumerate() {
+        println!("  {:>2} | {}", index + 1, fields.join(" | "));
+    }
+    let header_row: usize = Input::with_theme(&theme)
+        .with_prompt(tr!(PromptHeaderRow))
+        .default(import::detect_header_row(&records))
+        .validate_with(|row: &usize| if *row <= records.len() { Ok(()) } else { Err(tr!(MenuInvalidOption)) })
+        .interact_text()?;
+
+    // Colonnes désignées par leur nom d'en-tête, ou par leur numéro sans en-tête
+    let headers: Vec<String> = match header_row {
+        0 => Vec::new(),
+        row => records[row - 1].1.iter().map(|header| header.trim().to_string()).collect(),
+    };
+    let data: Vec<&Vec<String>> = records.iter().skip(header_row).map(|(_, fields)| fields).collect();
+    let column_count = records.iter().map(|(_, fields)| fields.len()).max().unwrap_or(0);
+    let references: Vec<String> = (0..column_count)
+        .map(|index| match headers.get(index) {
+            Some(header) if !header.is_empty() && headers.iter().filter(|h| *h == header).count() == 1 => header.clone(),
+            _ => (index + 1).to_string(),
+        })
+        .collect();
+    let samples = |column: &str| -> Vec<&str> {
+        let Some(index) = references.iter().position(|reference| reference == column) else { return Vec::new() };
+        data.iter().filter_map(|fields| fields.get(index)).map(|value| value.trim()).take(20).collect()
+    };
+    let select_column = |prompt: &str, optional: bool, keywords: &[&str]| -> io::Result<Option<String>> {
+        let mut items: Vec<String> = references
+            .iter()
+            .enumerate()
+            .map(|(index, reference)| {
+                let example = data.first().and_then(|fields| fields.get(index)).map_or("", |value| value.trim());
+                format!("{} ({})", reference, example)
+            })
+            .collect();
+        if optional {
+            items.push(tr!(ProfileNoColumn).to_string());
+        }
+        let guess = references.iter().position(|reference| {
+            let reference = reference.to_lowercase();
+            keywords.iter().any(|keyword| reference.contains(keyword))
+        });
+        let choice = Select::with_theme(&theme)
+            .with_prompt(prompt)
+            .items(&items)
+            .default(guess.unwrap_or(if optional { items.len() - 1 } else { 0 }))
+            .interact_opt()?;
+        Ok(choice.and_then(|i| references.get(i).cloned()))
+    };
+    let has_column = |keywords: &[&str]| {
+        references.iter().any(|reference| keywords.iter().any(|keyword| reference.to_lowercase().contains(keyword)))
+    };
+
+    const DEBIT: [&str; 2] = ["débit", "debit"];
+    const CREDIT: [&str; 2] = ["crédit", "credit"];
+    let detected_sign = if has_column(&DEBIT) && has_column(&CREDIT) { SignConvention::DebitCredit } else { SignConvention::ExpensesNegative };
+    let labels: Vec<&str> = SignConvention::ALL.iter().map(|sign| sign.label()).collect();
+    let Some(choice) = Select::with_theme(&theme)
+        .with_prompt(tr!(PromptSign))
+        .items(&labels)
+        .default(SignConvention::ALL.iter().position(|sign| *sign == detected_sign).unwrap_or(0))
+        .interact_opt()?
+    else {
+        return Ok(None);
+    };
+    let sign = SignConvention::ALL[choice];
+
+    let mut profile = ImportProfile {
+        id: 0,
+        name: name.unwrap_or_default().to_string(),
+        delimiter,
+        encoding,
+        header_row,
+        date_format: String::new(),
+        decimal_separator: '.',
+        sign,
+        date_column: String::new(),
+        name_column: String::new(),
+        amount_column: String::new(),
+        debit_column: String::new(),
+        credit_column: String::new(),
+        payee_column: String::new(),
+        notes_column: String::new(),
+        budget_column: String::new(),
+        default_budget: String::new(),
+    };
+    let Some(name_column) = select_column(tr!(PromptNameColumn), false, &["libell", "désignation", "description", "label", "memo"])? else {
+        return Ok(None);
+    };
+    profile.name_column = name_column;
+    if sign == SignConvention::DebitCredit {
+        let Some(debit_column) = select_column(tr!(PromptDebitColumn), false, &DEBIT)? else { return Ok(None) };
+        profile.debit_column = debit_column;
+        profile.credit_column = select_column(tr!(PromptCreditColumn), true, &CREDIT)?.unwrap_or_default();
+    } else {
+        let Some(amount_column) = select_column(tr!(PromptAmountColumn), false, &["montant", "amount", "somme"])? else { return Ok(None) };
+        profile.amount_column = amount_column;
+    }
+    profile.date_column = select_column(tr!(PromptDateColumn), true, &["date"])?.unwrap_or_default();
+    profile.payee_column = select_column(tr!(PromptPayeeColumn), true, &["bénéficiaire", "payee", "tiers"])?.unwrap_or_default();
+    profile.notes_column = select_column(tr!(PromptNotesColumn), true, &["note", "commentaire", "comment"])?.unwrap_or_default();
+    profile.budget_column = select_column(tr!(PromptBudgetColumn), true, &["budget", "catégorie", "category"])?.unwrap_or_default();
+
+    let budget_names: Vec<String> = std::iter::once(tr!(ProfileNoColumn).to_string())
+        .chain(budget_manager.list_budgets()?.into_iter().map(|budget| budget.budget_name))
+        .collect();
+    let choice = Select::with_theme(&theme)
+        .with_prompt(tr!(PromptDefaultBudget))
+        .items(&budget_names)
+        .default(0)
+        .interact_opt()?;
+    if let Some(i) = choice.filter(|i| *i > 0) {
+        profile.default_budget = budget_names[i].clone();
+    }
+
+    let amount_samples: Vec<&str> = [&profile.amount_column, &profile.debit_column, &profile.credit_column]
+        .iter()
+        .flat_map(|column| samples(column))
+        .collect();
+    let separators = [',', '.'];
+    let detected_separator = import::detect_decimal_separator(&amount_samples);
+    let Some(choice) = Select::with_theme(&theme)
+        .with_prompt(tr!(PromptDecimalSeparator))
+        .items(&separators)
+        .default(separators.iter().position(|separator| *separator == detected_separator).unwrap_or(0))
+        .interact_opt()?
+    else {
+        return Ok(None);
+    };
+    profile.decimal_separator = separators[choice];
+
+    let date_samples = samples(&profile.date_column);
+    let date_format: String = Input::with_theme(&theme)
+        .with_prompt(tr!(PromptDateFormat))
+        .with_initial_text(import::detect_date_format(&date_samples).unwrap_or_default())
+        .allow_empty(true)
+        .validate_with(|format: &String| match date_samples.first() {
+            Some(sample) if !format.trim().is_empty() => chrono::NaiveDate::parse_from_str(sample, format.trim())
+                .map(|_| ())
+                .map_err(|_| tr!(InvalidDate, sample)),
+            _ => Ok(()),
+        })
+        .interact_text()?;
+    profile.date_format = date_format.trim().to_string();
+
+    // Aperçu des premières lignes telles qu'elles seront importées
+    println!("{}", tr!(ProfilePreview));
+    let mut table = Table::new();
+    table.add_row(row![tr!(ColLine), tr!(ColBudget), tr!(ColTransaction), tr!(ColAmount), tr!(ColDate), tr!(ColResult)]);
+    for (line, row) in import::read_with_profile(&bytes, &profile)?.into_iter().take(5) {
+        match row {
+            Ok(ImportRow::Transaction { budget_name, transaction } | ImportRow::Unassigned { budget_name, transaction }) => {
+                let date = transaction.transaction_date.as_deref().map(i18n::date).unwrap_or_default();
+                let budget_name = if budget_name.is_empty() { profile.default_budget.clone() } else { budget_name };
+                table.add_row(row![line, budget_name, transaction.transaction_name, i18n::amount(transaction.amount), date, "OK"]);
+            }
+            Ok(ImportRow::Skipped(reason)) | Err(reason) => {
+                table.add_row(row![line, "", "", "", "", reason]);
+            }
+        }
+    }
+    table.printstd();
+    if !Confirm::with_theme(&theme).with_prompt(tr!(ConfirmSaveProfile)).default(true).interact()? {
+        println!("{}", tr!(OperationCancelled));
+        return Ok(None);
+    }
+
+    profile.name = read_text(tr!(PromptProfileName), name)?;
+    budget_manager.save_import_profile(&profile)?;
+    println!("{}", Style::new().green().apply_to(tr!(ProfileSaved, profile.name)));
+    Ok(Some(profile))
+}
+
+//==================================Configuration====================================
+// Affiche la configuration puis modifie un paramètre ; enregistrée dans le fichier
+fn edit_config(config_path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = Config::load(config_path)?;
+    println!("{}", tr!(ConfigFile, config_path));
+    let items: Vec<String> = config::KEYS
+        .iter()
+        .map(|key| format!("{} = {}", key, config.value(key).unwrap_or_default()))
+        .chain(std::iter::once(tr!(Back).to_string()))
+        .collect();
+
+    let choice = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt(tr!(PromptConfigKey))
+        .items(&items)
+        .default(0)
+        .interact_opt()?;
+    let Some(key) = choice.and_then(|i| config::KEYS.get(i)) else { return Ok(()) };
+
+    // Re-demande la valeur tant qu'elle est refusée par la validation
+    let current = config.value(key).unwrap_or_default();
+    let mut candidate = config.clone();
+    let theme = ColorfulTheme::default();
+    Input::<String>::with_theme(&theme)
+        .with_prompt(format!("{} ({})", tr!(PromptConfigValue), key))
+        .with_initial_text(current)
+        .validate_with(|value: &String| candidate.set_value(key, value).map_err(|err| err.to_string()))
+        .interact_text()?;
+    config = candidate;
+
+    config.save(config_path)?;
+    println!("{}", Style::new().green().apply_to(tr!(ConfigSaved)));
+    Ok(())
+}
+
+//==================================Input helpers====================================
+// Saisie obligatoire : l'invite est répétée tant que le champ est vide
+fn read_text(prompt: &str, initial: Option<&str>) -> io::Result<String> {
+    let theme = ColorfulTheme::default();
+    let mut input = Input::<String>::with_theme(&theme);
+    input.with_prompt(prompt).validate_with(|text: &String| {
+        if text.trim().is_empty() { Err(tr!(FieldRequired)) } else { Ok(()) }
+    });
+    if let Some(initial) = initial {
+        input.with_initial_text(initial);
+    }
+    Ok(input.interact_text()?.trim().to_string())
+}
+
+// Saisie facultative (peut rester vide)
+fn read_optional(prompt: &str) -> io::Result<String> {
+    let input: String = Input::with_theme(&ColorfulTheme::default()).with_prompt(prompt).allow_empty(true).interact_text()?;
+    Ok(input.trim().to_string())
+}
+
+// Montant d'une transaction : l'invite est répétée tant que la saisie n'est pas un nombre
+fn read_amount(prompt: &str, default: Option<f64>) -> io::Result<f64> {
+    read_number(prompt, default, |amount| {
+        if amount.is_finite() { Ok(()) } else { Err(tr!(InvalidAmount).to_string()) }
+    })
+}
+
+// Montant total d'un budget, compris entre 0 et le plafond
+fn read_budget_amount(prompt: &str, default: Option<f64>) -> io::Result<f64> {
+    read_number(prompt, default, |amount| {
+        let max_total_amount = config::get().budgets.max_total_amount;
+        if (0.0..=max_total_amount).contains(&amount) {
+            Ok(())
+        } else {
+            Err(tr!(BudgetAmountOutOfRange, i18n::amount(max_total_amount)))
+        }
+    })
+}
+
+fn read_number(prompt: &str, default: Option<f64>, validate: impl Fn(f64) -> Result<(), String>) -> io::Result<f64> {
+    let theme = ColorfulTheme::default();
+    let mut input = Input::<String>::with_theme(&theme);
+    input.with_prompt(prompt).validate_with(|text: &String| parse::amount(text).and_then(&validate));
+    if let Some(default) = default {
+        input.default(default.to_string());
+    }
+    // La validation garantit que la saisie est un nombre
+    Ok(parse::amount(&input.interact_text()?).unwrap_or_default())
+}
+
+// Date facultative (aujourd'hui si vide), au format ISO
+fn read_date(prompt: &str) -> io::Result<Option<String>> {
+    let input: String = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt(prompt)
+        .allow_empty(true)
+        .validate_with(|text: &String| if text.trim().is_empty() { Ok(()) } else { parse::date(text).map(|_| ()) })
+        .interact_text()?;
+    Ok(parse::date(&input).ok().map(|date| date.to_string()))
+}
+
+// Demande de confirmation (non par défaut)
+fn confirm(prompt: &str) -> io::Result<bool> {
+    Confirm::with_theme(&ColorfulTheme::default()).with_prompt(prompt).default(false).interact()
+}
+
+// Liste filtrable des budgets existants. Retourne None s'il n'y a aucun budget
+// ou si l'utilisateur annule avec Échap.
+fn select_budget(budget_manager: &BudgetManager, prompt: &str) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    let budgets = budget_manager.list_budgets()?;
+    if budgets.is_empty() {
+        println!("{}", tr!(NoBudgets));
+        return Ok(None);
+    }
+
+    let items: Vec<String> = budgets
+        .iter()
+        .map(|budget| format!("{} ({} / {})", budget.budget_name, i18n::amount(budget.remaining_amount), i18n::amount(budget.total_amount)))
+        .collect();
+    let choice = FuzzySelect::with_theme(&ColorfulTheme::default())
+        .with_prompt(tr!(FilterHint, prompt))
+        .items(&items)
+        .default(0)
+        .interact_opt()?;
+    Ok(choice.map(|i| budgets[i].budget_name.clone()))
+}
+
+// Liste filtrable des transactions d'un budget ; None si le budget n'en a pas ou en cas d'annulation
+fn select_transaction(budget_manager: &BudgetManager, budget_name: &str, prompt: &str) -> Result<Option<TransactionRecord>, Box<dyn std::error::Error>> {
+    let query = Query { filters: vec![Filter::Budget(budget_name.to_string())] };
+    let mut transactions = budget_manager.query_transactions(&query)?;
+    if transactions.is_empty() {
+        println!("{}", tr!(NoTransactions, budget_name));
+        return Ok(None);
+    }
+
+    let items: Vec<String> = transactions
+        .iter()
+        .map(|t| {
+            let date = t.transaction_date.as_deref().map(i18n::date).unwrap_or_else(|| tr!(NoDate).to_string());
+            format!("{} — {} ({})", t.transaction_name, i18n::amount(t.amount), date)
+        })
+        .collect();
+    let choice = FuzzySelect::with_theme(&ColorfulTheme::default())
+        .with_prompt(tr!(FilterHint, prompt))
+        .items(&items)
+        .default(0)
+        .interact_opt()?;
+    Ok(choice.map(|i| transactions.swap_remove(i)))
+}
+
+// Applique la politique de dépassement avant d'enregistrer une dépense.
+// Retourne None si l'opération doit être abandonnée, sinon l'indicateur de confirmation.
+fn confirm_overspend(budget_manager: &BudgetManager, budget_name: &str, extra_spending: f64) -> io::Result<Option<bool>> {
+    let error_style = Style::new().red();
+    let warning_style = Style::new().yellow();
+
+    // Budget inconnu : add/edit_transaction afficheront l'erreur
+    let Ok(check) = budget_manager.check_overspend(budget_name, extra_spending) else { return Ok(Some(false)) };
+    match check {
+        OverspendCheck::Allowed => Ok(Some(false)),
+        OverspendCheck::NeedsConfirmation { remaining_after } => {
+            println!("{}", warning_style.apply_to(tr!(OverspendWarning, budget_name, i18n::amount(remaining_after))));
+            if confirm(tr!(ConfirmOverspend))? {
+                Ok(Some(true))
+            } else {
+                println!("{}", tr!(TransactionCancelled));
+                Ok(None)
+            }
+        }
+        OverspendCheck::Blocked { remaining_after } => {
+            println!("{}", error_style.apply_to(tr!(OverspendBlocked, budget_name, i18n::amount(remaining_after))));
+            Ok(None)
+        }
+    }
+}
+
+//==================================Alert rules sub-menu====================================
+fn manage_alert_rules(budget_manager: &BudgetManager, budget_name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let success_style = Style::new().green();
+
+    loop {
+        let rules = budget_manager.list_alert_rules(budget_name)?;
+        let mut table = Table::new();
+        table.add_row(row![tr!(ColId), tr!(ColRuleKind), tr!(ColThreshold)]);
+        for rule in &rules {
+            table.add_row(row![rule.id, rule.kind.label(), i18n::number(rule.threshold, 2)]);
+        }
+        println!("{}", tr!(AlertRulesOf, budget_name));
+        table.printstd();
+
+        let choice = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt(tr!(MenuChoose))
+            .items(&[tr!(RuleAdd), tr!(RuleEdit), tr!(RuleRemove), tr!(Back)])
+            .default(0)
+            .interact_opt()?;
+        match choice {
+            Some(0) => {
+                let Some((kind, threshold)) = read_alert_rule(None)? else { continue };
+                budget_manager.add_alert_rule(budget_name, kind, threshold)?;
+                println!("{}", success_style.apply_to(tr!(RuleAdded)));
+            }
+            Some(1) => {
+                let Some(rule) = select_alert_rule(&rules, tr!(PromptRuleToEdit))? else { continue };
+                let Some((kind, threshold)) = read_alert_rule(Some(rule))? else { continue };
+                budget_manager.edit_alert_rule(rule.id, kind, threshold)?;
+                println!("{}", success_style.apply_to(tr!(RuleEdited)));
+            }
+            Some(2) => {
+                let Some(rule) = select_alert_rule(&rules, tr!(PromptRuleToRemove))? else { continue };
+                if !confirm(&tr!(ConfirmRemoveRule, rule.kind.label(), i18n::number(rule.threshold, 2)))? {
+                    continue;
+                }
+                budget_manager.remove_alert_rule(rule.id)?;
+                println!("{}", success_style.apply_to(tr!(RuleRemoved)));
+            }
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn select_alert_rule<'a>(rules: &'a [AlertRule], prompt: &str) -> io::Result<Option<&'a AlertRule>> {
+    if rules.is_empty() {
+        println!("{}", tr!(NoRules));
+        return Ok(None);
+    }
+    let items: Vec<String> = rules.iter().map(|rule| format!("{} : {}", rule.kind.label(), i18n::number(rule.threshold, 2))).collect();
+    let choice = Select::with_theme(&ColorfulTheme::default()).with_prompt(prompt).items(&items).default(0).interact_opt()?;
+    Ok(choice.map(|i| &rules[i]))
+}
+
+// Demande le type et le seuil d'une règle ; None si l'utilisateur annule
+fn read_alert_rule(current: Option<&AlertRule>) -> io::Result<Option<(AlertRuleKind, f64)>> {
+    let labels: Vec<&str> = AlertRuleKind::ALL.iter().map(|kind| kind.label()).collect();
+    let default = current.and_then(|rule| AlertRuleKind::ALL.iter().position(|kind| *kind == rule.kind)).unwrap_or(0);
+    let choice = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt(tr!(PromptRuleKind))
+        .items(&labels)
+        .default(default)
+        .interact_opt()?;
+    let Some(kind) = choice.map(|i| AlertRuleKind::ALL[i]) else { return Ok(None) };
+
+    let threshold = read_number(tr!(PromptThreshold), current.map(|rule| rule.threshold), |threshold| {
+        if threshold.is_finite() && threshold >= 0.0 { Ok(()) } else { Err(tr!(InvalidThresholdRetry).to_string()) }
+    })?;
+    Ok(Some((kind, threshold)))
+}
+
+//==================================Assignment rules====================================
+fn manage_assignment_rules(budget_manager: &BudgetManager) -> Result<(), Box<dyn std::error::Error>> {
+    let success_style = Style::new().green();
+
+    loop {
+        let rules = budget_manager.list_assignment_rules()?;
+        if rules.is_empty() {
+            println!("{}", tr!(NoAssignRules));
+        } else {
+            output::render(&rules, OutputFormat::Table, io::stdout())?;
+        }
+
+        let choice = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt(tr!(MenuChoose))
+            .items(&[tr!(AssignRulesAdd), tr!(AssignRulesRemove), tr!(AssignRulesTest), tr!(AssignRulesSuggest), tr!(Back)])
+            .default(0)
+            .interact_opt()?;
+        match choice {
+            Some(0) => {
+                let rule = read_assignment_rule(budget_manager)?;
+                match budget_manager.add_assignment_rule(&rule) {
+                    Ok(id) => println!("{}", success_style.apply_to(tr!(AssignRuleAdded, id))),
+                    Err(err) => eprintln!("{}", tr!(Error, err)),
+                }
+            }
+            Some(1) => {
+                if rules.is_empty() {
+                    continue;
+                }
+                let items: Vec<String> = rules.iter().map(|rule| format!("{} : {}", rule.id, rule.conditions())).collect();
+                let choice = Select::with_theme(&ColorfulTheme::default())
+                    .with_prompt(tr!(PromptAssignRuleToRemove))
+                    .items(&items)
+                    .default(0)
+                    .interact_opt()?;
+                let Some(rule) = choice.map(|i| &rules[i]) else { continue };
+                if !confirm(&tr!(ConfirmRemoveAssignRule, rule.id, rule.conditions()))? {
+                    continue;
+                }
+                budget_manager.remove_assignment_rule(rule.id)?;
+                println!("{}", success_style.apply_to(tr!(AssignRuleRemoved, rule.id)));
+            }
+            Some(2) => {
+                let name = read_text(tr!(PromptTransactionName), None)?;
+                let amount = read_amount(tr!(PromptTransactionAmount), None)?;
+                let payee = read_optional(tr!(PromptPayee))?;
+                print_rule_test(budget_manager, &NewTransaction { payee, ..NewTransaction::new(name, amount) })?;
+            }
+            Some(3) => {
+                let suggestions = budget_manager.suggest_assignment_rules(3)?;
+                if suggestions.is_empty() {
+                    println!("{}", tr!(NoRuleSuggestions));
+                    continue;
+                }
+                let mut created = 0;
+                for suggestion in &suggestions {
+                    let rule = suggestion.rule();
+                    let question = tr!(ConfirmRuleSuggestion, rule.conditions(), suggestion.budget_name, suggestion.matches, suggestion.total);
+                    if Confirm::with_theme(&ColorfulTheme::default()).with_prompt(question).default(true).interact()? {
+                        budget_manager.add_assignment_rule(&rule)?;
+                        created += 1;
+                    }
+                }
+                println!("{}", success_style.apply_to(tr!(RuleSuggestionsCreated, created)));
+            }
+            _ => return Ok(()),
+        }
+    }
+}
+
+// Conditions et affectations d'une nouvelle règle ; la validation est faite à l'enregistrement
+fn read_assignment_rule(budget_manager: &BudgetManager) -> Result<AssignmentRule, Box<dyn std::error::Error>> {
+    let contains = read_optional(tr!(PromptRuleContains))?;
+    let regex = read_optional(tr!(PromptRuleRegex))?;
+    let payee = read_optional(tr!(PromptRulePayee))?;
+    let min_amount = read_optional_amount(tr!(PromptRuleMinAmount))?;
+    let max_amount = read_optional_amount(tr!(PromptRuleMaxAmount))?;
+    let budget_name = select_budget(budget_manager, tr!(PromptRuleBudget))?.unwrap_or_default();
+    let category = read_optional(tr!(PromptRuleCategory))?;
+    let tags = read_optional(tr!(PromptRuleTags))?.split_whitespace().map(String::from).collect();
+    let priority = read_number(tr!(PromptRulePriority), Some(0.0), |priority| {
+        if priority.fract() == 0.0 && priority.abs() <= i32::MAX as f64 { Ok(()) } else { Err(tr!(InvalidAmount).to_string()) }
+    })? as i32;
+    Ok(AssignmentRule { priority, contains, regex, payee, min_amount, max_amount, budget_name, category, tags, ..Default::default() })
+}
+
+// Montant facultatif : None si la saisie reste vide
+fn read_optional_amount(prompt: &str) -> io::Result<Option<f64>> {
+    let input: String = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt(prompt)
+        .allow_empty(true)
+        .validate_with(|text: &String| if text.trim().is_empty() { Ok(()) } else { parse::amount(text).map(|_| ()) })
+        .interact_text()?;
+    Ok(parse::amount(&input).ok())
+}
+
+// Règles qui s'appliquent à la transaction, puis ce qu'elles lui attribuent
+pub fn print_rule_test(budget_manager: &BudgetManager, transaction: &NewTransaction) -> Result<(), Box<dyn std::error::Error>> {
+    let rules = budget_manager.assignment_rules()?;
+    let matching: Vec<AssignmentRule> = rules.matching(transaction).into_iter().cloned().collect();
+    if matching.is_empty() {
+        println!("{}", tr!(AssignRuleTestNone));
+        return Ok(());
+    }
+    output::render(&matching, OutputFormat::Table, io::stdout())?;
+    let assignment = rules.assign(transaction);
+    let none = || "-".to_string();
+    let tags = if assignment.tags.is_empty() { none() } else { assignment.tags.join(" ") };
+    println!("{}", tr!(AssignRuleTestResult, assignment.budget_name.unwrap_or_else(none), assignment.category.unwrap_or_else(none), tags));
+    Ok(())
+}
+
+//==================================Transactions to triage====================================
+// Transactions importées dont le budget n'existe pas. Après en avoir rangé une,
+// les autres lignes du même budget inconnu peuvent suivre dans le même budget,
+// qui devient l'alias de ce nom pour les imports suivants.
+fn manage_inbox(budget_manager: &BudgetManager) -> Result<(), Box<dyn std::error::Error>> {
+    let success_style = Style::new().green();
+
+    loop {
+        let transactions = budget_manager.list_unassigned()?;
+        if transactions.is_empty() {
+            println!("{}", tr!(NoUnassigned));
+            return Ok(());
+        }
+        output::render(&transactions, OutputFormat::Table, io::stdout())?;
+
+        let choice = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt(tr!(MenuChoose))
+            .items(&[tr!(InboxAssign), tr!(InboxRemove), tr!(Back)])
+            .default(0)
+            .interact_opt()?;
+        match choice {
+            Some(0) => {
+                let Some(transaction) = select_unassigned(&transactions, tr!(PromptUnassignedToAssign))? else { continue };
+                let Some(budget_name) = select_budget(budget_manager, tr!(PromptBudget))? else { continue };
+                let Some(overspend_confirmed) = confirm_overspend(budget_manager, &budget_name, transaction.amount)? else { continue };
+                if let Err(err) = budget_manager.assign_unassigned(transaction.id, &budget_name, overspend_confirmed) {
+                    eprintln!("{}", tr!(Error, err));
+                    continue;
+                }
+                println!("{}", success_style.apply_to(tr!(UnassignedAssigned, transaction.id, budget_name)));
+
+                let Some(source) = inbox_source(transaction) else { continue };
+                let others: Vec<&UnassignedTransaction> = transactions
+                    .iter()
+                    .filter(|other| other.id != transaction.id && inbox_source(other) == Some(source))
+                    .collect();
+                let question = match source {
+                    InboxSource::Budget(name) => tr!(ConfirmAssignSameBudget, others.len(), name, budget_name),
+                    InboxSource::Category(category) => tr!(ConfirmAssignSameCategory, others.len(), category, budget_name),
+                    InboxSource::Account(account) => tr!(ConfirmAssignSameAccount, others.len(), account, budget_name),
+                };
+                if others.is_empty() || !confirm(&question)? {
+                    continue;
+                }
+                match source {
+                    InboxSource::Budget(name) => {
+                        budget_manager.set_budget_alias(name, &budget_name)?;
+                        println!("{}", tr!(AliasMapped, name, budget_name));
+                    }
+                    InboxSource::Category(category) => {
+                        budget_manager.set_category_budget(category, &budget_name)?;
+                        println!("{}", tr!(CategoryMapped, category, budget_name));
+                    }
+                    InboxSource::Account(account) => {
+                        budget_manager.set_account_budget(account, &budget_name)?;
+                        println!("{}", tr!(AccountMapped, account, budget_name));
+                    }
+                }
+                for other in others {
+                    // Un dépassement refusé laisse la transaction à classer
+                    match budget_manager.assign_unassigned(other.id, &budget_name, false) {
+                        Ok(_) => println!("{}", success_style.apply_to(tr!(UnassignedAssigned, other.id, budget_name))),
+                        Err(err) => eprintln!("{}", tr!(Error, err)),
+                    }
+                }
+            }
+            Some(1) => {
+                let Some(transaction) = select_unassigned(&transactions, tr!(PromptUnassignedToRemove))? else { continue };
+                if !confirm(&tr!(ConfirmRemoveUnassigned, transaction.transaction_name, i18n::amount(transaction.amount)))? {
+                    continue;
+                }
+                budget_manager.remove_unassigned(transaction.id)?;
+                println!("{}", success_style.apply_to(tr!(UnassignedRemoved, transaction.id)));
+            }
+            _ => return Ok(()),
+        }
+    }
+}
+
+// Origine commune de transactions à classer : le budget demandé par le fichier,
+// sinon la catégorie, sinon le compte. Le budget choisi est retenu pour elle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum InboxSource<'a> {
+    Budget(&'a str),
+    Category(&'a str),
+    Account(&'a str),
+}
+
+fn inbox_source(transaction: &UnassignedTransaction) -> Option<InboxSource<'_>> {
+    if !transaction.budget_name.is_empty() {
+        Some(InboxSource::Budget(&transaction.budget_name))
+    } else if !transaction.category.is_empty() {
+        Some(InboxSource::Category(&transaction.category))
+    } else if !transaction.account.is_empty() {
+        Some(InboxSource::Account(&transaction.account))
+    } else {
+        None
+    }
+}
+
+fn select_unassigned<'a>(transactions: &'a [UnassignedTransaction], prompt: &str) -> io::Result<Option<&'a UnassignedTransaction>> {
+    let items: Vec<String> = transactions
+        .iter()
+        .map(|transaction| {
+            let source = match inbox_source(transaction) {
+                Some(InboxSource::Budget(name) | InboxSource::Category(name) | InboxSource::Account(name)) => name,
+                None => "",
+            };
+            format!("{} : {} · {} · {}", transaction.id, source, transaction.transaction_name, i18n::amount(transaction.amount))
+        })
+        .collect();
+    let choice = FuzzySelect::with_theme(&ColorfulTheme::default()).with_prompt(prompt).items(&items).default(0).interact_opt()?;
+    Ok(choice.map(|i| &transactions[i]))
+}
@@ -0,0 +1,465 @@
+use prettytable::{Cell, Row, Table};
+use serde::Serialize;
+use std::error::Error;
+use std::io::Write;
+use crate::config::ColorMode;
+use crate::db::{
+    AccountBudget, AlertRule, AssignmentRule, Budget, BudgetAlias, CategoryBudget, ImportProfile, RuleSuggestion, TransactionRecord,
+    UnassignedTransaction,
+};
+use crate::i18n::{self, tr};
+
+// Format de sortie des listes et rapports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum OutputFormat {
+    // Tableau lisible (prettytable)
+    #[default]
+    Table,
+    // Tableau JSON d'objets
+    Json,
+    // CSV avec ligne d'en-tête
+    Csv,
+    // Valeurs séparées par des tabulations, sans en-tête
+    Tsv,
+}
+
+// Type affichable sous forme de lignes. Les noms de `FIELDS` sont ceux de la
+// sérialisation serde : ils servent d'en-têtes CSV et ne doivent pas changer.
+pub trait Tabular: Serialize {
+    const FIELDS: &'static [&'static str];
+    // En-têtes du tableau lisible, dans la langue de l'interface
+    fn labels() -> Vec<&'static str>;
+    // Valeurs brutes (CSV, TSV), indépendantes de la langue
+    fn cells(&self) -> Vec<String>;
+    // Valeurs du tableau lisible (montants et dates au format local)
+    fn display_cells(&self) -> Vec<String>;
+}
+
+impl Tabular for Budget {
+    const FIELDS: &'static [&'static str] =
+        &["id", "budget_name", "total_amount", "spent_amount", "remaining_amount", "overspend_policy"];
+
+    fn labels() -> Vec<&'static str> {
+        vec![tr!(ColId), tr!(ColBudgetName), tr!(ColTotal), tr!(ColSpent), tr!(ColRemaining), tr!(ColPolicy)]
+    }
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            self.budget_name.clone(),
+            format!("{:.2}", self.total_amount),
+            format!("{:.2}", self.spent_amount),
+            format!("{:.2}", self.remaining_amount),
+            self.overspend_policy.as_str().to_string(),
+        ]
+    }
+
+    fn display_cells(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            self.budget_name.clone(),
+            i18n::amount(self.total_amount),
+            i18n::amount(self.spent_amount),
+            i18n::amount(self.remaining_amount),
+            self.overspend_policy.label().to_string(),
+        ]
+    }
+}
+
+impl Tabular for TransactionRecord {
+    const FIELDS: &'static [&'static str] =
+        &["id", "budget_name", "transaction_name", "amount", "transaction_date", "payee", "notes", "tags", "category"];
+
+    fn labels() -> Vec<&'static str> {
+        vec![
+            tr!(ColId), tr!(ColBudget), tr!(ColTransaction), tr!(ColAmount),
+            tr!(ColDate), tr!(ColPayee), tr!(ColNotes), tr!(ColTags), tr!(ColCategory),
+        ]
+    }
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            self.budget_name.clone(),
+            self.transaction_name.clone(),
+            format!("{:.2}", self.amount),
+            self.transaction_date.clone().unwrap_or_default(),
+            self.payee.clone(),
+            self.notes.clone(),
+            self.tags.join(" "),
+            self.category.clone(),
+        ]
+    }
+
+    fn display_cells(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            self.budget_name.clone(),
+            self.transaction_name.clone(),
+            i18n::amount(self.amount),
+            self.transaction_date.as_deref().map(i18n::date).unwrap_or_default(),
+            self.payee.clone(),
+            self.notes.clone(),
+            self.tags.join(" "),
+            self.category.clone(),
+        ]
+    }
+}
+
+impl Tabular for AlertRule {
+    const FIELDS: &'static [&'static str] = &["id", "kind", "threshold"];
+
+    fn labels() -> Vec<&'static str> {
+        vec![tr!(ColId), tr!(ColRuleKind), tr!(ColThreshold)]
+    }
+
+    fn cells(&self) -> Vec<String> {
+        vec![self.id.to_string(), self.kind.as_str().to_string(), self.threshold.to_string()]
+    }
+
+    fn display_cells(&self) -> Vec<String> {
+        vec![self.id.to_string(), self.kind.label().to_string(), i18n::number(self.threshold, 2)]
+    }
+}
+
+impl Tabular for ImportProfile {
+    const FIELDS: &'static [&'static str] = &[
+        "id", "name", "delimiter", "encoding", "header_row", "date_format", "decimal_separator", "sign",
+        "date_column", "name_column", "amount_column", "debit_column", "credit_column", "payee_column",
+        "notes_column", "budget_column", "default_budget",
+    ];
+
+    // Le tableau lisible regroupe les colonnes du fichier en une seule case
+    fn labels() -> Vec<&'static str> {
+        vec![
+            tr!(ColProfile), tr!(ColDelimiter), tr!(ColEncoding), tr!(ColHeaderRow), tr!(ColDecimal),
+            tr!(ColDateFormat), tr!(ColSign), tr!(ColColumns), tr!(ColBudget),
+        ]
+    }
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            self.name.clone(),
+            self.delimiter.to_string(),
+            self.encoding.as_str().to_string(),
+            self.header_row.to_string(),
+            self.date_format.clone(),
+            self.decimal_separator.to_string(),
+            self.sign.as_str().to_string(),
+            self.date_column.clone(),
+            self.name_column.clone(),
+            self.amount_column.clone(),
+            self.debit_column.clone(),
+            self.credit_column.clone(),
+            self.payee_column.clone(),
+            self.notes_column.clone(),
+            self.budget_column.clone(),
+            self.default_budget.clone(),
+        ]
+    }
+
+    fn display_cells(&self) -> Vec<String> {
+        let delimiter = if self.delimiter == '\t' { "tab".to_string() } else { self.delimiter.to_string() };
+        vec![
+            self.name.clone(),
+            delimiter,
+            self.encoding.as_str().to_string(),
+            self.header_row.to_string(),
+            self.decimal_separator.to_string(),
+            self.date_format.clone(),
+            self.sign.label().to_string(),
+            self.columns_summary(),
+            self.default_budget.clone(),
+        ]
+    }
+}
+
+impl Tabular for AccountBudget {
+    const FIELDS: &'static [&'static str] = &["account", "budget_name"];
+
+    fn labels() -> Vec<&'static str> {
+        vec![tr!(ColAccount), tr!(ColBudget)]
+    }
+
+    fn cells(&self) -> Vec<String> {
+        vec![self.account.clone(), self.budget_name.clone()]
+    }
+
+    fn display_cells(&self) -> Vec<String> {
+        self.cells()
+    }
+}
+
+impl Tabular for CategoryBudget {
+    const FIELDS: &'static [&'static str] = &["category", "budget_name"];
+
+    fn labels() -> Vec<&'static str> {
+        vec![tr!(ColCategory), tr!(ColBudget)]
+    }
+
+    fn cells(&self) -> Vec<String> {
+        vec![self.category.clone(), self.budget_name.clone()]
+    }
+
+    fn display_cells(&self) -> Vec<String> {
+        self.cells()
+    }
+}
+
+impl Tabular for BudgetAlias {
+    const FIELDS: &'static [&'static str] = &["alias", "budget_name"];
+
+    fn labels() -> Vec<&'static str> {
+        vec![tr!(ColAlias), tr!(ColBudget)]
+    }
+
+    fn cells(&self) -> Vec<String> {
+        vec![self.alias.clone(), self.budget_name.clone()]
+    }
+
+    fn display_cells(&self) -> Vec<String> {
+        self.cells()
+    }
+}
+
+impl Tabular for UnassignedTransaction {
+    const FIELDS: &'static [&'static str] =
+        &["id", "budget_name", "transaction_name", "amount", "transaction_date", "payee", "notes", "tags", "category", "account"];
+
+    fn labels() -> Vec<&'static str> {
+        vec![
+            tr!(ColId), tr!(ColRequestedBudget), tr!(ColTransaction), tr!(ColAmount),
+            tr!(ColDate), tr!(ColPayee), tr!(ColNotes), tr!(ColTags), tr!(ColCategory), tr!(ColAccount),
+        ]
+    }
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            self.budget_name.clone(),
+            self.transaction_name.clone(),
+            format!("{:.2}", self.amount),
+            self.transaction_date.clone().unwrap_or_default(),
+            self.payee.clone(),
+            self.notes.clone(),
+            self.tags.join(" "),
+            self.category.clone(),
+            self.account.clone(),
+        ]
+    }
+
+    fn display_cells(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            self.budget_name.clone(),
+            self.transaction_name.clone(),
+            i18n::amount(self.amount),
+            self.transaction_date.as_deref().map(i18n::date).unwrap_or_default(),
+            self.payee.clone(),
+            self.notes.clone(),
+            self.tags.join(" "),
+            self.category.clone(),
+            self.account.clone(),
+        ]
+    }
+}
+
+impl Tabular for AssignmentRule {
+    const FIELDS: &'static [&'static str] =
+        &["id", "priority", "contains", "regex", "payee", "min_amount", "max_amount", "budget_name", "category", "tags"];
+
+    fn labels() -> Vec<&'static str> {
+        vec![tr!(ColId), tr!(ColPriority), tr!(ColConditions), tr!(ColBudget), tr!(ColCategory), tr!(ColTags)]
+    }
+
+    fn cells(&self) -> Vec<String> {
+        let amount = |amount: Option<f64>| amount.map(|amount| format!("{:.2}", amount)).unwrap_or_default();
+        vec![
+            self.id.to_string(),
+            self.priority.to_string(),
+            self.contains.clone(),
+            self.regex.clone(),
+            self.payee.clone(),
+            amount(self.min_amount),
+            amount(self.max_amount),
+            self.budget_name.clone(),
+            self.category.clone(),
+            self.tags.join(" "),
+        ]
+    }
+
+    fn display_cells(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            self.priority.to_string(),
+            self.conditions(),
+            self.budget_name.clone(),
+            self.category.clone(),
+            self.tags.join(" "),
+        ]
+    }
+}
+
+impl Tabular for RuleSuggestion {
+    const FIELDS: &'static [&'static str] = &["field", "pattern", "budget_name", "matches", "total"];
+
+    fn labels() -> Vec<&'static str> {
+        vec![tr!(ColConditions), tr!(ColBudget), tr!(ColMatches)]
+    }
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.field.to_string(),
+            self.pattern.clone(),
+            self.budget_name.clone(),
+            self.matches.to_string(),
+            self.total.to_string(),
+        ]
+    }
+
+    fn display_cells(&self) -> Vec<String> {
+        vec![self.rule().conditions(), self.budget_name.clone(), format!("{} / {}", self.matches, self.total)]
+    }
+}
+
+//==================================Render a listing====================================
+pub fn render<T: Tabular>(items: &[T], format: OutputFormat, mut out: impl Write) -> Result<(), Box<dyn Error>> {
+    match format {
+        OutputFormat::Table => {
+            let mut table = Table::new();
+            table.set_titles(Row::new(T::labels().iter().map(|label| Cell::new(label)).collect()));
+            for item in items {
+                table.add_row(Row::new(item.display_cells().iter().map(|cell| Cell::new(cell)).collect()));
+            }
+            table.print(&mut out)?;
+        }
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut out, items)?;
+            writeln!(out)?;
+        }
+        OutputFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(out);
+            writer.write_record(T::FIELDS)?;
+            for item in items {
+                writer.write_record(item.cells())?;
+            }
+            writer.flush()?;
+        }
+        OutputFormat::Tsv => {
+            for item in items {
+                let cells: Vec<String> = item.cells().iter().map(|cell| cell.replace(['\t', '\n'], " ")).collect();
+                writeln!(out, "{}", cells.join("\t"))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+// Liste de transactions ; en mode tableau, le nombre et le total sont affichés en dessous
+pub fn print_transactions(records: &[TransactionRecord], format: OutputFormat) -> Result<(), Box<dyn Error>> {
+    if format == OutputFormat::Table && records.is_empty() {
+        println!("{}", tr!(NoTransactionFound));
+        return Ok(());
+    }
+    render(records, format, std::io::stdout())?;
+    if format == OutputFormat::Table {
+        let total: f64 = records.iter().map(|record| record.amount).sum();
+        println!("{}", tr!(TransactionsTotal, records.len(), i18n::amount(total)));
+    }
+    Ok(())
+}
+
+// Active ou désactive les codes ANSI ; en mode "auto", seulement si la
+// sortie est un terminal (pas de couleurs dans une redirection ou un pipe)
+pub fn apply_color_mode(mode: ColorMode) {
+    let enabled = match mode {
+        // Les bibliothèques détectent déjà le terminal ; il suffit de couper les couleurs sinon
+        ColorMode::Auto if console::Term::stdout().is_term() => return,
+        ColorMode::Auto | ColorMode::Never => false,
+        ColorMode::Always => true,
+    };
+    console::set_colors_enabled(enabled);
+    console::set_colors_enabled_stderr(enabled);
+    colored::control::set_override(enabled);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{AlertRuleKind, OverspendPolicy};
+
+    fn budget() -> Budget {
+        Budget {
+            id: 1,
+            budget_name: "Courses, maison".into(),
+            total_amount: 100.0,
+            spent_amount: 12.5,
+            remaining_amount: 87.5,
+            overspend_policy: OverspendPolicy::Warn,
+        }
+    }
+
+    fn record() -> TransactionRecord {
+        TransactionRecord {
+            id: 7,
+            budget_name: "Loisirs".into(),
+            transaction_name: "Café".into(),
+            amount: 3.5,
+            transaction_date: Some("2026-03-15".into()),
+            payee: String::new(),
+            notes: "avec\tPaul\net Léa".into(),
+            tags: vec!["sortie".into(), "travail".into()],
+            category: "Sorties".into(),
+        }
+    }
+
+    fn rendered<T: Tabular>(items: &[T], format: OutputFormat) -> String {
+        let mut out = Vec::new();
+        render(items, format, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    // Les clés JSON sont les en-têtes CSV
+    fn assert_json_keys<T: Tabular>(item: &T) {
+        let value = serde_json::to_value(item).unwrap();
+        let mut keys: Vec<&str> = value.as_object().unwrap().keys().map(String::as_str).collect();
+        let mut fields = T::FIELDS.to_vec();
+        keys.sort();
+        fields.sort();
+        assert_eq!(keys, fields);
+        assert_eq!(item.cells().len(), T::FIELDS.len());
+    }
+
+    #[test]
+    fn csv_has_a_header_and_raw_values() {
+        assert_eq!(
+            rendered(&[budget()], OutputFormat::Csv),
+            "id,budget_name,total_amount,spent_amount,remaining_amount,overspend_policy\n1,\"Courses, maison\",100.00,12.50,87.50,warn\n"
+        );
+        assert_eq!(rendered::<Budget>(&[], OutputFormat::Csv).lines().count(), 1);
+    }
+
+    #[test]
+    fn tsv_keeps_one_line_per_item() {
+        assert_eq!(rendered(&[record()], OutputFormat::Tsv), "7\tLoisirs\tCafé\t3.50\t2026-03-15\t\tavec Paul et Léa\tsortie travail\tSorties\n");
+        assert_eq!(rendered::<TransactionRecord>(&[], OutputFormat::Tsv), "");
+    }
+
+    #[test]
+    fn json_uses_the_csv_field_names() {
+        assert_json_keys(&budget());
+        assert_json_keys(&record());
+        assert_json_keys(&AlertRule { id: 1, kind: AlertRuleKind::PercentUsed, threshold: 90.0 });
+
+        let value: serde_json::Value = serde_json::from_str(&rendered(&[budget(), budget()], OutputFormat::Json)).unwrap();
+        assert_eq!(value.as_array().map(Vec::len), Some(2));
+        assert_eq!(value[0]["overspend_policy"], "warn");
+    }
+
+    #[test]
+    fn table_lists_every_item() {
+        let table = rendered(&[budget(), Budget { budget_name: "Loisirs".into(), ..budget() }], OutputFormat::Table);
+        assert!(table.contains("Courses, maison") && table.contains("Loisirs"));
+    }
+}
@@ -0,0 +1,260 @@
+use chrono::{Datelike, Duration, Local, NaiveDate};
+use crate::i18n::{lang, tr, Lang};
+
+// Lecture tolérante des montants et des dates saisis par l'utilisateur,
+// partagée par le menu, la ligne de commande, le tableau de bord et les imports.
+// Les erreurs sont des messages prêts à afficher (comme attendu par clap et dialoguer).
+
+//==================================Amounts====================================
+// Montant saisi librement :
+//   12.50  12,50  1 250  1'250  1.250,50  1,250.50  45 €  $45  12.5+3.2  (4*2,5)-1
+// Une virgule seule est décimale, sauf en anglais devant exactement trois
+// chiffres ("1,250" = 1250). Plusieurs points ou virgules séparent les milliers.
+pub fn amount(input: &str) -> Result<f64, String> {
+    let invalid = || tr!(NotANumber, input.trim());
+    let mut text: String = input.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_lowercase();
+    let currency = crate::config::get().currency.to_lowercase();
+    for symbol in [currency.as_str(), "€", "$", "£", "eur", "usd", "chf", "gbp"] {
+        if !symbol.is_empty() {
+            text = text.replace(symbol, "");
+        }
+    }
+
+    let mut expression = Expression { chars: text.chars().collect(), pos: 0 };
+    let value = expression.sum().ok_or_else(invalid)?;
+    if expression.pos < expression.chars.len() || !value.is_finite() {
+        return Err(invalid());
+    }
+    // Évite d'afficher "-0"
+    Ok(if value == 0.0 { 0.0 } else { value })
+}
+
+// Expression arithmétique : + - * / et parenthèses, avec les priorités usuelles
+struct Expression {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Expression {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn sum(&mut self) -> Option<f64> {
+        let mut value = self.product()?;
+        while let Some(operator @ ('+' | '-')) = self.peek() {
+            self.pos += 1;
+            let right = self.product()?;
+            value = if operator == '+' { value + right } else { value - right };
+        }
+        Some(value)
+    }
+
+    fn product(&mut self) -> Option<f64> {
+        let mut value = self.factor()?;
+        while let Some(operator @ ('*' | '/')) = self.peek() {
+            self.pos += 1;
+            let right = self.factor()?;
+            value = if operator == '*' { value * right } else { value / right };
+        }
+        Some(value)
+    }
+
+    fn factor(&mut self) -> Option<f64> {
+        match self.peek()? {
+            '-' => {
+                self.pos += 1;
+                Some(-self.factor()?)
+            }
+            '+' => {
+                self.pos += 1;
+                self.factor()
+            }
+            '(' => {
+                self.pos += 1;
+                let value = self.sum()?;
+                (self.peek()? == ')').then_some(())?;
+                self.pos += 1;
+                Some(value)
+            }
+            _ => {
+                let start = self.pos;
+                while self.peek().is_some_and(|c| c.is_ascii_digit() || matches!(c, '.' | ',' | '\'' | '’')) {
+                    self.pos += 1;
+                }
+                number(&self.chars[start..self.pos].iter().collect::<String>())
+            }
+        }
+    }
+}
+
+// Un nombre sans signe, avec séparateurs de milliers et de décimales
+fn number(literal: &str) -> Option<f64> {
+    let literal = literal.replace(['\'', '’'], "");
+    let commas = literal.matches(',').count();
+    let dots = literal.matches('.').count();
+
+    let normalized = match (commas, dots) {
+        (0, 0) => literal,
+        // Les deux séparateurs : le dernier est décimal
+        (_, _) if commas > 0 && dots > 0 => {
+            let (thousands, decimal) = if literal.rfind(',') > literal.rfind('.') { ('.', ',') } else { (',', '.') };
+            let (int_part, frac_part) = literal.rsplit_once(decimal)?;
+            (!frac_part.is_empty()).then_some(())?;
+            format!("{}.{}", without_thousands(int_part, thousands)?, frac_part)
+        }
+        (1, 0) => {
+            let (int_part, frac_part) = literal.split_once(',')?;
+            if lang() == Lang::En && frac_part.len() == 3 && !int_part.is_empty() {
+                format!("{}{}", int_part, frac_part)
+            } else {
+                format!("{}.{}", int_part, frac_part)
+            }
+        }
+        (0, 1) => literal,
+        (_, 0) => without_thousands(&literal, ',')?,
+        (0, _) => without_thousands(&literal, '.')?,
+        _ => return None,
+    };
+
+    // Au moins un chiffre : "." seul est refusé
+    if !normalized.chars().any(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    normalized.parse().ok()
+}
+
+// Retire les séparateurs de milliers en vérifiant les groupes de trois chiffres
+fn without_thousands(int_part: &str, separator: char) -> Option<String> {
+    let mut groups = int_part.split(separator);
+    let first = groups.next()?;
+    if first.is_empty() || first.len() > 3 {
+        return None;
+    }
+    let mut digits = first.to_string();
+    for group in groups {
+        (group.len() == 3).then_some(())?;
+        digits.push_str(group);
+    }
+    Some(digits)
+}
+
+//==================================Dates====================================
+// Date saisie librement : aujourd'hui/today, hier/yesterday, avant-hier,
+// demain/tomorrow, 2026-03-15, 15/03/2026 (03/15/2026 en anglais), 15/03
+// (année en cours), 15.03.26. Les séparateurs / . - sont acceptés.
+pub fn date(input: &str) -> Result<NaiveDate, String> {
+    let today = Local::now().date_naive();
+    let invalid = || tr!(InvalidDate, input.trim());
+    let text = input.trim().to_lowercase();
+    let offset = match text.as_str() {
+        "aujourd'hui" | "aujourd’hui" | "aujourdhui" | "auj" | "today" => Some(0),
+        "hier" | "yesterday" => Some(-1),
+        "avant-hier" | "avant hier" => Some(-2),
+        "demain" | "tomorrow" => Some(1),
+        _ => None,
+    };
+    if let Some(days) = offset {
+        return Ok(today + Duration::days(days));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(&text, "%Y-%m-%d") {
+        return Ok(date);
+    }
+
+    let parts: Vec<u32> = text
+        .split(['/', '.', '-'])
+        .map(|part| if part.is_empty() || part.len() > 4 { None } else { part.parse().ok() })
+        .collect::<Option<_>>()
+        .ok_or_else(invalid)?;
+    let (first, second, year) = match parts[..] {
+        [first, second] => (first, second, today.year()),
+        [first, second, year] if year < 100 => (first, second, 2000 + year as i32),
+        [first, second, year] => (first, second, year as i32),
+        _ => return Err(invalid()),
+    };
+    let (day, month) = match lang() {
+        Lang::Fr => (first, second),
+        Lang::En => (second, first),
+    };
+    NaiveDate::from_ymd_opt(year, month, day).ok_or_else(invalid)
+}
+
+//==================================Text====================================
+// Minuscules sans accents
+pub fn fold(text: &str) -> String {
+    text.to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'à' | 'â' | 'ä' => 'a',
+            'é' | 'è' | 'ê' | 'ë' => 'e',
+            'î' | 'ï' => 'i',
+            'ô' | 'ö' => 'o',
+            'ù' | 'û' | 'ü' => 'u',
+            'ç' => 'c',
+            c => c,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_separators_currencies_and_expressions() {
+        for (input, expected) in [
+            ("12.50", 12.5),
+            ("12,50", 12.5),
+            ("1 250", 1250.0),
+            ("1'250", 1250.0),
+            ("1.250,50", 1250.5),
+            ("1,250.50", 1250.5),
+            ("45 €", 45.0),
+            ("$45", 45.0),
+            ("12.5+3.5", 16.0),
+            ("(4*2,5)-1", 9.0),
+            ("-0", 0.0),
+        ] {
+            assert_eq!(amount(input), Ok(expected), "{}", input);
+        }
+        for input in ["", ".", "abc", "1.25.0", "12,50,5", "(3", "1/0"] {
+            assert!(amount(input).is_err(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn single_comma_depends_on_language() {
+        // La langue est fixée une seule fois pour tout le processus
+        let expected = match lang() {
+            Lang::Fr => 1.25,
+            Lang::En => 1250.0,
+        };
+        assert_eq!(amount("1,250"), Ok(expected));
+        assert_eq!(amount("1,25"), Ok(1.25));
+    }
+
+    #[test]
+    fn reads_dates() {
+        let today = Local::now().date_naive();
+        let ymd = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+        assert_eq!(date("2026-03-15"), Ok(ymd(2026, 3, 15)));
+        assert_eq!(date("hier"), Ok(today - Duration::days(1)));
+        assert_eq!(date(" Tomorrow "), Ok(today + Duration::days(1)));
+        let (first, second) = match lang() {
+            Lang::Fr => (5, 3),
+            Lang::En => (3, 5),
+        };
+        assert_eq!(date("05/03/2026"), Ok(ymd(2026, second, first)));
+        assert_eq!(date("05.03.26"), Ok(ymd(2026, second, first)));
+        assert_eq!(date("05-03"), Ok(ymd(today.year(), second, first)));
+        for input in ["31/02/2026", "2026/13/01", "15", "1/2/3/4", "bientôt"] {
+            assert!(date(input).is_err(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn folds_case_and_accents() {
+        assert_eq!(fold("Été À Noël"), "ete a noel");
+        assert_eq!(fold("Ça"), "ca");
+    }
+}
@@ -0,0 +1,233 @@
+use chrono::NaiveDate;
+use rusqlite::types::Value;
+use std::fmt;
+use crate::i18n::tr;
+
+// Mini-langage de requête sur les transactions, par exemple :
+//
+//   budget:Courses amount>50 after:2026-01-01 tag:vacances
+//
+// Termes reconnus (tous combinés par ET) :
+//   budget:NOM           budget exact (insensible à la casse)
+//   amount>N  amount>=N  amount<N  amount<=N  amount=N
+//   after:DATE           transactions à partir de cette date (incluse)
+//   before:DATE          transactions avant cette date (exclue)
+//                        (2026-01-01, 15/03, hier... voir `parse::date`)
+//   tag:NOM              transactions portant ce tag
+//   payee:TEXTE          bénéficiaire contenant ce texte
+//   mot                  nom, notes ou bénéficiaire contenant ce mot
+// Les valeurs contenant des espaces s'écrivent entre guillemets : budget:"Sorties été"
+#[derive(Debug, Clone, PartialEq)]
+pub enum Filter {
+    Budget(String),
+    Amount(Comparison, f64),
+    After(NaiveDate),
+    Before(NaiveDate),
+    Tag(String),
+    Payee(String),
+    Text(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Comparison {
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+    Equal,
+}
+
+impl Comparison {
+    fn sql(&self) -> &'static str {
+        match self {
+            Comparison::Greater => ">",
+            Comparison::GreaterOrEqual => ">=",
+            Comparison::Less => "<",
+            Comparison::LessOrEqual => "<=",
+            Comparison::Equal => "=",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    pub filters: Vec<Filter>,
+}
+
+#[derive(Debug)]
+pub struct QueryError(pub String);
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", tr!(InvalidQuery, self.0))
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+impl Query {
+    pub fn parse(input: &str) -> Result<Self, QueryError> {
+        let mut filters = Vec::new();
+        for term in tokenize(input)? {
+            filters.push(parse_term(&term)?);
+        }
+        Ok(Self { filters })
+    }
+
+    // Clause WHERE paramétrée (sur les alias `t` = transactions et `b` = budgets).
+    // Les valeurs saisies ne sont jamais insérées dans le SQL.
+    pub fn to_sql(&self) -> (String, Vec<Value>) {
+        let mut clauses = Vec::new();
+        let mut values = Vec::new();
+
+        for filter in &self.filters {
+            match filter {
+                Filter::Budget(name) => {
+                    clauses.push("b.budget_name = ? COLLATE NOCASE".to_string());
+                    values.push(Value::Text(name.clone()));
+                }
+                Filter::Amount(comparison, amount) => {
+                    clauses.push(format!("t.amount {} ?", comparison.sql()));
+                    values.push(Value::Real(*amount));
+                }
+                Filter::After(date) => {
+                    clauses.push("t.transaction_date >= ?".to_string());
+                    values.push(Value::Text(date.to_string()));
+                }
+                Filter::Before(date) => {
+                    clauses.push("t.transaction_date < ?".to_string());
+                    values.push(Value::Text(date.to_string()));
+                }
+                Filter::Tag(tag) => {
+                    clauses.push("(',' || t.tags || ',') LIKE ? ESCAPE '\\'".to_string());
+                    values.push(Value::Text(format!("%,{},%", escape_like(tag))));
+                }
+                Filter::Payee(payee) => {
+                    clauses.push("t.payee LIKE ? ESCAPE '\\'".to_string());
+                    values.push(Value::Text(format!("%{}%", escape_like(payee))));
+                }
+                Filter::Text(text) => {
+                    clauses.push(
+                        "(t.transaction_name LIKE ? ESCAPE '\\' OR t.notes LIKE ? ESCAPE '\\' OR t.payee LIKE ? ESCAPE '\\')"
+                            .to_string(),
+                    );
+                    let pattern = format!("%{}%", escape_like(text));
+                    values.extend(std::iter::repeat_n(Value::Text(pattern), 3));
+                }
+            }
+        }
+
+        if clauses.is_empty() {
+            ("1 = 1".to_string(), values)
+        } else {
+            (clauses.join(" AND "), values)
+        }
+    }
+}
+
+fn parse_term(term: &str) -> Result<Filter, QueryError> {
+    for (operator, comparison) in [
+        (">=", Comparison::GreaterOrEqual),
+        ("<=", Comparison::LessOrEqual),
+        (">", Comparison::Greater),
+        ("<", Comparison::Less),
+        ("=", Comparison::Equal),
+    ] {
+        if let Some(value) = term.strip_prefix("amount").and_then(|rest| rest.strip_prefix(operator)) {
+            return Ok(Filter::Amount(comparison, crate::parse::amount(value).map_err(QueryError)?));
+        }
+    }
+
+    let Some((key, value)) = term.split_once(':') else {
+        return Ok(Filter::Text(term.to_string()));
+    };
+    if value.is_empty() {
+        return Err(QueryError(tr!(QueryMissingValue, key)));
+    }
+    match key {
+        "budget" => Ok(Filter::Budget(value.to_string())),
+        "after" => Ok(Filter::After(crate::parse::date(value).map_err(QueryError)?)),
+        "before" => Ok(Filter::Before(crate::parse::date(value).map_err(QueryError)?)),
+        "tag" => Ok(Filter::Tag(crate::db::normalize_tag(value))),
+        "payee" => Ok(Filter::Payee(value.to_string())),
+        _ => Err(QueryError(tr!(QueryUnknownKey, key))),
+    }
+}
+
+// Découpe la requête en termes ; les guillemets regroupent les espaces
+fn tokenize(input: &str) -> Result<Vec<String>, QueryError> {
+    let mut terms = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+
+    for c in input.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            c if c.is_whitespace() && !in_quotes => {
+                if !current.is_empty() {
+                    terms.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if in_quotes {
+        return Err(QueryError(tr!(QueryUnclosedQuote).into()));
+    }
+    if !current.is_empty() {
+        terms.push(current);
+    }
+    Ok(terms)
+}
+
+fn escape_like(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{BudgetManager, NewTransaction};
+
+    #[test]
+    fn parses_terms_and_quoted_values() {
+        let query = Query::parse(r#"budget:"Sorties été" amount>=50 after:2026-01-01 tag:#Vacances café"#).unwrap();
+        assert_eq!(
+            query.filters,
+            vec![
+                Filter::Budget("Sorties été".into()),
+                Filter::Amount(Comparison::GreaterOrEqual, 50.0),
+                Filter::After(NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()),
+                Filter::Tag("vacances".into()),
+                Filter::Text("café".into()),
+            ]
+        );
+        assert!(Query::parse("budget:").is_err());
+        assert!(Query::parse("montant:5").is_err());
+        assert!(Query::parse(r#"budget:"Courses"#).is_err());
+    }
+
+    #[test]
+    fn escapes_like_wildcards() {
+        assert_eq!(escape_like(r"50%_a\b"), r"50\%\_a\\b");
+        let (sql, values) = Query::parse("payee:100%").unwrap().to_sql();
+        assert_eq!(sql, "t.payee LIKE ? ESCAPE '\\'");
+        assert_eq!(values, vec![Value::Text(r"%100\%%".into())]);
+    }
+
+    #[test]
+    fn wildcards_match_literally() {
+        let manager = BudgetManager::new(":memory:").unwrap();
+        manager.add_budget("Courses", 1000.0).unwrap();
+        for name in ["remise 50%", "remise 500", "code_promo", "codeXpromo"] {
+            manager.add_transaction("Courses", NewTransaction::new(name, 1.0), false).unwrap();
+        }
+        let names = |input: &str| -> Vec<String> {
+            let records = manager.query_transactions(&Query::parse(input).unwrap()).unwrap();
+            records.into_iter().map(|record| record.transaction_name).collect()
+        };
+        assert_eq!(names("50%"), vec!["remise 50%"]);
+        assert_eq!(names("code_"), vec!["code_promo"]);
+        assert_eq!(names("budget:courses").len(), 4);
+    }
+}
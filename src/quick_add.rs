@@ -0,0 +1,202 @@
+use chrono::{Local, NaiveDate};
+use console::Style;
+use crate::db::{normalize_tag, NewTransaction};
+use crate::i18n::{self, tr};
+use crate::parse;
+
+// Ajout rapide : une seule saisie comme "café 3,50 Loisirs hier #travail".
+//   #mot          tag
+//   3,50  45€     montant (le dernier nombre de la saisie, voir `parse::amount`)
+//   hier  15/03   date (voir `parse::date`) ; aujourd'hui par défaut
+//   Loisirs       budget existant, reconnu malgré la casse, les accents,
+//                 un début de nom ("lois") ou une faute de frappe ("loisir")
+// Les mots restants forment le nom de la transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuickAdd {
+    pub name: String,
+    pub amount: f64,
+    // None si aucun budget n'est reconnu, ou si plusieurs correspondent autant
+    pub budget: Option<String>,
+    pub date: Option<NaiveDate>,
+    pub tags: Vec<String>,
+    // Catégorie donnée par une règle d'affectation (voir `run_quick_add`)
+    pub category: String,
+}
+
+//==================================Function to parse a quick-add input====================================
+impl QuickAdd {
+    pub fn parse(input: &str, budget_names: &[String]) -> Result<Self, String> {
+        let currency = crate::config::get().currency.to_lowercase();
+        let mut words: Vec<&str> = Vec::new();
+        let mut tags = Vec::new();
+        let mut date = None;
+        for word in input.split_whitespace() {
+            if word.len() > 1 && word.starts_with('#') {
+                tags.push(normalize_tag(word));
+            } else if ["€", "$", "£", currency.as_str()].contains(&word.to_lowercase().as_str()) {
+                // Symbole monétaire séparé du montant ("3,50 €")
+            } else if date.is_none() && looks_like_date(word) && parse::date(word).is_ok() {
+                date = parse::date(word).ok();
+            } else {
+                words.push(word);
+            }
+        }
+
+        // Dernier mot lisible comme un montant ("2 pizzas 18" : 18)
+        let amount_index = words
+            .iter()
+            .rposition(|word| word.chars().any(|c| c.is_ascii_digit()) && parse::amount(word).is_ok())
+            .ok_or_else(|| tr!(QuickAddNoAmount).to_string())?;
+        let amount = parse::amount(words.remove(amount_index))?;
+
+        let budget = match best_budget(&words, budget_names) {
+            Some((start, len, name)) => {
+                words.drain(start..start + len);
+                Some(name)
+            }
+            None => None,
+        };
+
+        if words.is_empty() {
+            return Err(tr!(QuickAddNoName).to_string());
+        }
+        Ok(Self { name: words.join(" "), amount, budget, date, tags, category: String::new() })
+    }
+
+    pub fn transaction(&self) -> NewTransaction {
+        NewTransaction {
+            tags: self.tags.clone(),
+            category: self.category.clone(),
+            transaction_date: self.date.map(|date| date.to_string()),
+            ..NewTransaction::new(self.name.clone(), self.amount)
+        }
+    }
+
+    // Aperçu affiché avant l'enregistrement
+    pub fn print_preview(&self) {
+        let label_style = Style::new().bold();
+        let today = Local::now().date_naive();
+        let mut lines = vec![
+            (tr!(ColTransaction), self.name.clone()),
+            (tr!(ColAmount), i18n::amount(self.amount)),
+            (tr!(ColBudget), self.budget.clone().unwrap_or_else(|| "?".to_string())),
+            (tr!(ColDate), i18n::date(&self.date.unwrap_or(today).to_string())),
+            (tr!(ColTags), self.tags.join(", ")),
+        ];
+        if !self.category.is_empty() {
+            lines.push((tr!(ColCategory), self.category.clone()));
+        }
+        println!("{}", tr!(QuickAddPreview));
+        for (label, value) in lines {
+            println!("  {:<12} {}", label_style.apply_to(label), value);
+        }
+    }
+}
+
+// Une date s'écrit avec des séparateurs ou un mot-clé ; "3.50" reste un montant
+fn looks_like_date(word: &str) -> bool {
+    word.contains(['/', '-']) || word.matches('.').count() == 2 || !word.chars().any(|c| c.is_ascii_digit())
+}
+
+// Budget le plus proche d'une suite de mots : (position, nombre de mots, nom).
+// Les noms de plusieurs mots ("Sorties été") sont comparés à autant de mots consécutifs.
+fn best_budget(words: &[&str], budget_names: &[String]) -> Option<(usize, usize, String)> {
+    let mut best: Option<(u8, usize, usize, &String)> = None;
+    let mut tie = false;
+    for name in budget_names {
+        // Un nom de plusieurs mots peut aussi être abrégé en un seul ("sorties")
+        let full_len = name.split_whitespace().count().max(1);
+        let lens = if full_len > 1 { vec![full_len, 1] } else { vec![1] };
+        for len in lens {
+            for start in 0..(words.len() + 1).saturating_sub(len) {
+                let candidate = words[start..start + len].join(" ");
+                let Some(score) = match_score(&candidate, name) else { continue };
+                match best {
+                    Some((best_score, _, _, best_name)) if score < best_score || (score == best_score && best_name == name) => {}
+                    Some((best_score, ..)) if score == best_score => tie = true,
+                    _ => {
+                        best = Some((score, start, len, name));
+                        tie = false;
+                    }
+                }
+            }
+        }
+    }
+    best.filter(|_| !tie).map(|(_, start, len, name)| (start, len, name.clone()))
+}
+
+// 3 : même nom ; 2 : début du nom (3 lettres au moins) ; 1 : une faute de frappe
+fn match_score(candidate: &str, name: &str) -> Option<u8> {
+    let (candidate, name) = (parse::fold(candidate), parse::fold(name));
+    if candidate == name {
+        Some(3)
+    } else if candidate.chars().count() >= 3 && name.starts_with(&candidate) {
+        Some(2)
+    } else if candidate.chars().count() >= 4 && edit_distance(&candidate, &name) <= 1 {
+        Some(1)
+    } else {
+        None
+    }
+}
+
+// Distance de Levenshtein
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn budgets(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_name_amount_budget_date_and_tags() {
+        let quick = QuickAdd::parse("café 3,50 loisir hier #Travail", &budgets(&["Courses", "Loisirs"])).unwrap();
+        assert_eq!(quick.name, "café");
+        assert_eq!(quick.amount, 3.5);
+        assert_eq!(quick.budget.as_deref(), Some("Loisirs"));
+        assert_eq!(quick.date, Some(Local::now().date_naive() - Duration::days(1)));
+        assert_eq!(quick.tags, vec!["travail"]);
+
+        // Le dernier nombre est le montant ; un nom de plusieurs mots peut être abrégé
+        let quick = QuickAdd::parse("2 pizzas 18 sorties", &budgets(&["Sorties été"])).unwrap();
+        assert_eq!((quick.name.as_str(), quick.amount), ("2 pizzas", 18.0));
+        assert_eq!(quick.budget.as_deref(), Some("Sorties été"));
+
+        assert!(QuickAdd::parse("café", &budgets(&[])).is_err());
+        assert!(QuickAdd::parse("3,50 Loisirs", &budgets(&["Loisirs"])).is_err());
+    }
+
+    #[test]
+    fn ties_leave_the_budget_open() {
+        // Même début de nom, ou une faute de frappe à égale distance de deux budgets
+        assert_eq!(best_budget(&["cou"], &budgets(&["Courses", "Courrier"])), None);
+        assert_eq!(best_budget(&["noyer"], &budgets(&["Loyer", "Soyer"])), None);
+        // Le meilleur score l'emporte : le nom exact avant le début d'un autre nom
+        assert_eq!(best_budget(&["cours"], &budgets(&["Courses", "Cours"])), Some((0, 1, "Cours".to_string())));
+        // Deux mots qui désignent le même budget ne sont pas une égalité
+        assert_eq!(best_budget(&["loisirs", "loisirs"], &budgets(&["Loisirs"])), Some((0, 1, "Loisirs".to_string())));
+    }
+
+    #[test]
+    fn levenshtein_distance() {
+        assert_eq!(edit_distance("loisir", "loisirs"), 1);
+        assert_eq!(edit_distance("courses", "coruses"), 2);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("café", "cafe"), 1);
+    }
+}
@@ -0,0 +1,305 @@
+use clap::{CommandFactory, Parser};
+use console::Style;
+use rustyline::completion::{Completer, Pair};
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+use std::borrow::Cow;
+use std::error::Error;
+use crate::cli::{run_command, Command, ReplLine};
+use crate::db::BudgetManager;
+use crate::i18n::tr;
+use crate::output::OutputFormat;
+
+// Mode commande : une ligne = une sous-commande de la ligne de commande,
+// par exemple `add Courses "Carrefour" 42.10` ou `budget list`.
+// L'historique est conservé d'une session à l'autre dans HISTORY_FILE.
+const HISTORY_FILE: &str = ".budget_manager_history";
+const PROMPT: &str = "budget> ";
+
+//==================================Function to run the command mode====================================
+pub fn run(budget_manager: &BudgetManager, format: OutputFormat, config_path: &str) -> Result<(), Box<dyn Error>> {
+    let error_style = Style::new().red();
+    let mut editor: Editor<ReplHelper, DefaultHistory> = Editor::new()?;
+    editor.set_helper(Some(ReplHelper::new()));
+    // Pas d'historique au premier lancement
+    let _ = editor.load_history(HISTORY_FILE);
+
+    println!("{}", tr!(ReplWelcome));
+    loop {
+        // Les noms de budgets proposés à la complétion suivent les modifications
+        if let Some(helper) = editor.helper_mut() {
+            helper.budgets = budget_manager.list_budgets()?.into_iter().map(|budget| budget.budget_name).collect();
+        }
+
+        let line = match editor.readline(PROMPT) {
+            Ok(line) => line,
+            // Ctrl-C abandonne la ligne en cours, Ctrl-D quitte
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(err) => return Err(err.into()),
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        editor.add_history_entry(line)?;
+        if matches!(line, "exit" | "quit") {
+            break;
+        }
+
+        if let Err(err) = execute(budget_manager, line, format, config_path) {
+            eprintln!("{}", error_style.apply_to(tr!(Error, err)));
+        }
+    }
+
+    if let Err(err) = editor.save_history(HISTORY_FILE) {
+        eprintln!("{}", error_style.apply_to(tr!(ReplHistoryError, HISTORY_FILE, err)));
+    }
+    Ok(())
+}
+
+// Analyse une ligne avec les mêmes règles que la ligne de commande, puis l'exécute
+fn execute(budget_manager: &BudgetManager, line: &str, format: OutputFormat, config_path: &str) -> Result<(), Box<dyn Error>> {
+    let parsed = match parse_line(line) {
+        Ok(parsed) => parsed,
+        // Aide (`help`, `--help`) ou erreur de syntaxe : clap affiche le message
+        Err(err) => {
+            err.print()?;
+            return Ok(());
+        }
+    };
+    match parsed.command {
+        Command::Repl => println!("{}", tr!(ReplNested)),
+        command => {
+            run_command(budget_manager, command, parsed.format.unwrap_or(format), config_path)?;
+        }
+    }
+    Ok(())
+}
+
+// Une ligne de commande du mode commande ou d'un fichier de commandes
+pub fn parse_line(line: &str) -> Result<ReplLine, clap::Error> {
+    let (words, open_quote) = split_words(line);
+    if open_quote {
+        return Err(clap::Error::raw(clap::error::ErrorKind::InvalidValue, format!("{}\n", tr!(ReplUnclosedQuote))));
+    }
+    ReplLine::try_parse_from(words.into_iter().map(|(_, word)| word))
+}
+
+// Découpe une ligne en mots (position de début, texte) ; les guillemets simples
+// ou doubles regroupent les espaces. Indique aussi si un guillemet reste ouvert.
+fn split_words(line: &str) -> (Vec<(usize, String)>, bool) {
+    let mut words = Vec::new();
+    let mut current: Option<(usize, String)> = None;
+    let mut quote: Option<char> = None;
+    for (index, c) in line.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => current.get_or_insert((index, String::new())).1.push(c),
+            (None, '"' | '\'') => {
+                current.get_or_insert((index, String::new()));
+                quote = Some(c);
+            }
+            (None, c) if c.is_whitespace() => words.extend(current.take()),
+            (None, c) => current.get_or_insert((index, String::new())).1.push(c),
+        }
+    }
+    words.extend(current);
+    (words, quote.is_some())
+}
+
+//==================================Completion and hints====================================
+struct ReplHelper {
+    budgets: Vec<String>,
+    // Arbre des sous-commandes, pour la complétion et l'aide en ligne
+    commands: clap::Command,
+}
+
+impl ReplHelper {
+    fn new() -> Self {
+        let mut commands = ReplLine::command();
+        commands.build();
+        Self { budgets: Vec::new(), commands }
+    }
+
+    // Sous-commande atteinte par les mots déjà saisis, avec son chemin
+    // et le nombre d'arguments positionnels qui suivent
+    fn resolve<'a>(&'a self, words: &[String]) -> (&'a clap::Command, Vec<&'a str>, usize) {
+        let mut command = &self.commands;
+        let mut path = Vec::new();
+        let mut positionals = 0;
+        let mut skip_value = false;
+        for word in words {
+            if std::mem::take(&mut skip_value) {
+                continue;
+            }
+            if let Some(long) = word.strip_prefix("--") {
+                skip_value = command
+                    .get_arguments()
+                    .find(|arg| arg.get_long() == Some(long))
+                    .is_some_and(|arg| arg.get_action().takes_values());
+            } else if let Some(sub) = command.find_subcommand(word).filter(|_| positionals == 0) {
+                command = sub;
+                path.push(sub.get_name());
+            } else {
+                positionals += 1;
+            }
+        }
+        (command, path, positionals)
+    }
+
+    // Vrai si le mot en cours est un nom de budget existant
+    fn expects_budget(path: &[&str], previous: Option<&str>, positionals: usize) -> bool {
+        match (path, previous) {
+            (_, Some("--budget")) => true,
+            (["budget", sub], Some("--name")) => *sub != "add",
+            (["add"], _) => positionals == 0 && !previous.is_some_and(|word| word.starts_with("--")),
+            _ => false,
+        }
+    }
+}
+
+impl Completer for ReplHelper {
+    type Candidate = Pair;
+
+    fn complete(&self, line: &str, pos: usize, _ctx: &Context<'_>) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let before = &line[..pos];
+        let (mut words, open_quote) = split_words(before);
+        // Mot en cours de saisie : vide juste après une espace
+        let (start, prefix) = if before.is_empty() || (!open_quote && before.ends_with(char::is_whitespace)) {
+            (pos, String::new())
+        } else {
+            words.pop().unwrap_or((pos, String::new()))
+        };
+        let words: Vec<String> = words.into_iter().map(|(_, word)| word).collect();
+        let (command, path, positionals) = self.resolve(&words);
+
+        let candidates: Vec<String> = if Self::expects_budget(&path, words.last().map(String::as_str), positionals) {
+            self.budgets.clone()
+        } else if prefix.starts_with('-') {
+            command.get_arguments().filter_map(|arg| arg.get_long()).map(|long| format!("--{}", long)).collect()
+        } else if positionals == 0 {
+            let mut names: Vec<String> = command.get_subcommands().map(|sub| sub.get_name().to_string()).collect();
+            if path.is_empty() {
+                names.extend(["exit".to_string(), "quit".to_string()]);
+            }
+            names
+        } else {
+            Vec::new()
+        };
+
+        let pairs = candidates
+            .into_iter()
+            .filter(|candidate| candidate.to_lowercase().starts_with(&prefix.to_lowercase()))
+            .map(|candidate| {
+                let replacement = if candidate.contains(char::is_whitespace) {
+                    format!("\"{}\" ", candidate)
+                } else {
+                    format!("{} ", candidate)
+                };
+                Pair { display: candidate, replacement }
+            })
+            .collect();
+        Ok((start, pairs))
+    }
+}
+
+impl Hinter for ReplHelper {
+    type Hint = String;
+
+    // Après le nom d'une commande, rappelle ses arguments (ex : <BUDGET> <NAME> <AMOUNT>)
+    fn hint(&self, line: &str, pos: usize, _ctx: &Context<'_>) -> Option<String> {
+        if pos < line.len() || !line.ends_with(' ') {
+            return None;
+        }
+        let (words, open_quote) = split_words(line);
+        let words: Vec<String> = words.into_iter().map(|(_, word)| word).collect();
+        let (command, path, positionals) = self.resolve(&words);
+        if open_quote || path.is_empty() || positionals > 0 || words.len() != path.len() || command.has_subcommands() {
+            return None;
+        }
+        // "Usage: budget add --name <NAME> ..." : on ne garde que les arguments
+        let usage = command.clone().render_usage().to_string();
+        let arguments = usage.lines().next()?.split_whitespace().skip(1 + path.len()).collect::<Vec<_>>().join(" ");
+        (!arguments.is_empty()).then_some(arguments)
+    }
+}
+
+impl Highlighter for ReplHelper {
+    fn highlight_hint<'h>(&self, hint: &'h str) -> Cow<'h, str> {
+        Cow::Owned(Style::new().dim().apply_to(hint).to_string())
+    }
+}
+
+impl Validator for ReplHelper {}
+
+impl Helper for ReplHelper {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn helper() -> ReplHelper {
+        let mut helper = ReplHelper::new();
+        helper.budgets = vec!["Courses".to_string(), "Vacances été".to_string()];
+        helper
+    }
+
+    // Position du mot complété et remplacements proposés, curseur en fin de ligne
+    fn complete(line: &str) -> (usize, Vec<String>) {
+        let history = DefaultHistory::new();
+        let (start, pairs) = helper().complete(line, line.len(), &Context::new(&history)).unwrap();
+        (start, pairs.into_iter().map(|pair| pair.replacement).collect())
+    }
+
+    fn hint(line: &str) -> Option<String> {
+        let history = DefaultHistory::new();
+        helper().hint(line, line.len(), &Context::new(&history))
+    }
+
+    #[test]
+    fn splits_words_on_unquoted_spaces() {
+        let line = r#"add "Vacances été" 'a b'  42"#;
+        let (words, open_quote) = split_words(line);
+        let expected = [(0, "add"), (line.find('"').unwrap(), "Vacances été"), (line.find('\'').unwrap(), "a b"), (line.find("42").unwrap(), "42")];
+        assert_eq!(words, expected.map(|(index, word)| (index, word.to_string())));
+        assert!(!open_quote);
+        assert!(split_words("add \"Cour").1);
+        assert_eq!(split_words("add \"\"").0, vec![(0, "add".to_string()), (4, String::new())]);
+    }
+
+    #[test]
+    fn completes_commands_and_options() {
+        assert_eq!(complete("bu"), (0, vec!["budget ".to_string()]));
+        assert_eq!(complete("budget e"), (7, vec!["edit ".to_string()]));
+        assert!(complete("").1.iter().any(|candidate| candidate == "quit "));
+        assert!(!complete("budget ").1.iter().any(|candidate| candidate == "quit "));
+        assert_eq!(complete("tx add --n").1, ["--name ", "--notes "]);
+        // Après les arguments positionnels, plus de sous-commande à proposer
+        assert!(complete("add Courses ").1.is_empty());
+    }
+
+    #[test]
+    fn completes_existing_budget_names() {
+        assert_eq!(complete("add ").1, ["Courses ", "\"Vacances été\" "]);
+        assert_eq!(complete("add va"), (4, vec!["\"Vacances été\" ".to_string()]));
+        assert_eq!(complete("budget edit --name c"), (19, vec!["Courses ".to_string()]));
+        assert_eq!(complete("tx list --budget ").1.len(), 2);
+        // Le nom d'un nouveau budget n'est pas proposé
+        assert!(complete("budget add --name C").1.is_empty());
+    }
+
+    #[test]
+    fn hints_the_arguments_of_a_command() {
+        let hint = hint("tx add ").unwrap();
+        assert!(hint.contains("--budget <BUDGET>") && !hint.contains("tx") && !hint.contains("add"), "{}", hint);
+        assert!(self::hint("add ").is_some_and(|hint| hint.contains("<BUDGET>")));
+        assert_eq!(self::hint("tx add"), None);
+        assert_eq!(self::hint("budget "), None);
+        assert_eq!(self::hint("add Courses "), None);
+    }
+}